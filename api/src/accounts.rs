@@ -15,7 +15,7 @@ use crate::{
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    AccountData, Address, AptosErrorCode, AsConverter, LedgerInfo, MoveModuleBytecode,
+    AccountData, Address, AptosErrorCode, AsConverter, LedgerInfo, MoveModule, MoveModuleBytecode,
     MoveModuleId, MoveResource, MoveStructTag, StateKeyWrapper, U64,
 };
 use aptos_types::{
@@ -26,7 +26,9 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
-    identifier::Identifier, language_storage::StructTag, move_resource::MoveStructType,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    move_resource::MoveStructType,
     resolver::MoveResolver,
 };
 use poem_openapi::{
@@ -178,6 +180,67 @@ impl AccountsApi {
         })
         .await
     }
+
+    /// Get account module ABIs
+    ///
+    /// Retrieves the ABIs of all account modules for a given account at a specific ledger version,
+    /// without their bytecode. If the ledger version is not specified in the request, the latest
+    /// ledger version is used.
+    ///
+    /// ABIs are only available as JSON.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/accounts/:address/modules/abis",
+        method = "get",
+        operation_id = "get_account_module_abis",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_module_abis(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This cursor cannot be derived manually client-side. Instead, you must
+        /// call this endpoint once without this query parameter specified, and
+        /// then use the cursor returned in the X-Aptos-Cursor header in the
+        /// response.
+        start: Query<Option<StateKeyWrapper>>,
+        /// Max number of account module ABIs to retrieve
+        ///
+        /// If not provided, defaults to default page size.
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<MoveModule>> {
+        fail_point_poem("endpoint_get_account_module_abis")?;
+        self.context
+            .check_api_output_enabled("Get account module ABIs", &accept_type)?;
+        if accept_type == AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                "Module ABIs are only available as JSON",
+                AptosErrorCode::InvalidInput,
+            ));
+        }
+
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let account = Account::new(
+                context,
+                address.0,
+                ledger_version.0,
+                start.0.map(StateKey::from),
+                limit.0,
+            )?;
+            account.module_abis()
+        })
+        .await
+    }
 }
 
 /// A struct representing Account related lookups for resources and modules
@@ -389,49 +452,14 @@ impl Account {
     /// Note: For the BCS response, if results are being returned in pages, i.e. with the
     /// `start` and `limit` query parameters, the results will only be sorted within each page.
     pub fn modules(self, accept_type: &AcceptType) -> BasicResultWith404<Vec<MoveModuleBytecode>> {
-        // check account exists
-        self.verify_account_or_object_resource()?;
-        let max_account_modules_page_size = self.context.max_account_modules_page_size();
-        let (modules, next_state_key) = self
-            .context
-            .get_modules_by_pagination(
-                self.address.into(),
-                self.start.as_ref(),
-                self.ledger_version,
-                // Just use the max as the default
-                determine_limit(
-                    self.limit,
-                    max_account_modules_page_size,
-                    max_account_modules_page_size,
-                    &self.latest_ledger_info,
-                )? as u64,
-            )
-            .context("Failed to get modules from storage")
-            .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
-                    err,
-                    AptosErrorCode::InternalError,
-                    &self.latest_ledger_info,
-                )
-            })?;
+        let (modules, next_state_key) = self.module_bytes_page()?;
 
         match accept_type {
             AcceptType::Json => {
                 // Read bytecode and parse ABIs for output
                 let mut converted_modules = Vec::new();
                 for (_, module) in modules {
-                    converted_modules.push(
-                        MoveModuleBytecode::new(module.clone())
-                            .try_parse_abi()
-                            .context("Failed to parse move module ABI")
-                            .map_err(|err| {
-                                BasicErrorWith404::internal_with_code(
-                                    err,
-                                    AptosErrorCode::InternalError,
-                                    &self.latest_ledger_info,
-                                )
-                            })?,
-                    );
+                    converted_modules.push(self.parse_module(module)?);
                 }
                 BasicResponse::try_from_json((
                     converted_modules,
@@ -456,6 +484,70 @@ impl Account {
         }
     }
 
+    /// Retrieves the ABIs of the move modules associated with the account, as JSON
+    ///
+    /// Pages the same way as [`Account::modules`], so the same cursors apply.
+    pub fn module_abis(self) -> BasicResultWith404<Vec<MoveModule>> {
+        let (modules, next_state_key) = self.module_bytes_page()?;
+
+        let mut abis = Vec::new();
+        for (module_id, module) in modules {
+            let abi = self.parse_module(module)?.abi.ok_or_else(|| {
+                BasicErrorWith404::internal_with_code(
+                    format!("Failed to parse move module ABI of {}", module_id),
+                    AptosErrorCode::InternalError,
+                    &self.latest_ledger_info,
+                )
+            })?;
+            abis.push(abi);
+        }
+        BasicResponse::try_from_json((abis, &self.latest_ledger_info, BasicResponseStatus::Ok))
+            .map(|v| v.with_cursor(next_state_key))
+    }
+
+    /// Retrieves a page of the account's module bytecode, along with the cursor for the next page
+    fn module_bytes_page(
+        &self,
+    ) -> Result<(Vec<(ModuleId, Vec<u8>)>, Option<StateKey>), BasicErrorWith404> {
+        // check account exists
+        self.verify_account_or_object_resource()?;
+        let max_account_modules_page_size = self.context.max_account_modules_page_size();
+        self.context
+            .get_modules_by_pagination(
+                self.address.into(),
+                self.start.as_ref(),
+                self.ledger_version,
+                // Just use the max as the default
+                determine_limit(
+                    self.limit,
+                    max_account_modules_page_size,
+                    max_account_modules_page_size,
+                    &self.latest_ledger_info,
+                )? as u64,
+            )
+            .context("Failed to get modules from storage")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &self.latest_ledger_info,
+                )
+            })
+    }
+
+    fn parse_module(&self, module: Vec<u8>) -> Result<MoveModuleBytecode, BasicErrorWith404> {
+        MoveModuleBytecode::new(module)
+            .try_parse_abi()
+            .context("Failed to parse move module ABI")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &self.latest_ledger_info,
+                )
+            })
+    }
+
     /// Retrieves an event key from a [`MoveStructTag`] and a [`Identifier`] field name
     ///
     /// e.g. If there's the `CoinStore` module, it has a field named `withdraw_events` for
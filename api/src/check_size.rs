@@ -1,22 +1,39 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::accept_type::AcceptType;
+use aptos_api_types::{mime_types, AptosError, AptosErrorCode};
 use hyper::Method;
 use poem::{
     error::SizedLimitError,
+    http::{header::CONTENT_TYPE, StatusCode},
     web::headers::{self, HeaderMapExt},
-    Endpoint, Middleware, Request, Result,
+    Endpoint, Error, FromRequest, Middleware, Request, Response, Result,
 };
+use std::collections::BTreeMap;
 
 /// This middleware confirms that the Content-Length header is set and the
 /// value is within the acceptable range. It only applies to POST requests.
+///
+/// The acceptable range defaults to `max_size`, but can be overridden for
+/// specific routes by path prefix, in which case the longest matching prefix
+/// wins.
 pub struct PostSizeLimit {
     max_size: u64,
+    route_max_sizes: BTreeMap<String, u64>,
 }
 
 impl PostSizeLimit {
     pub fn new(max_size: u64) -> Self {
-        Self { max_size }
+        Self {
+            max_size,
+            route_max_sizes: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_route_limits(mut self, route_max_sizes: BTreeMap<String, u64>) -> Self {
+        self.route_max_sizes = route_max_sizes;
+        self
     }
 }
 
@@ -27,6 +44,7 @@ impl<E: Endpoint> Middleware<E> for PostSizeLimit {
         PostSizeLimitEndpoint {
             inner: ep,
             max_size: self.max_size,
+            route_max_sizes: self.route_max_sizes.clone(),
         }
     }
 }
@@ -35,6 +53,63 @@ impl<E: Endpoint> Middleware<E> for PostSizeLimit {
 pub struct PostSizeLimitEndpoint<E> {
     inner: E,
     max_size: u64,
+    route_max_sizes: BTreeMap<String, u64>,
+}
+
+impl<E> PostSizeLimitEndpoint<E> {
+    /// Returns the limit for the given path, preferring the longest route
+    /// prefix that matches on a path segment boundary.
+    fn max_size_for_path(&self, path: &str) -> u64 {
+        self.route_max_sizes
+            .iter()
+            .filter(|(prefix, _)| {
+                let prefix = prefix.trim_end_matches('/');
+                path == prefix
+                    || path
+                        .strip_prefix(prefix)
+                        .map_or(false, |rest| rest.starts_with('/'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.max_size, |(_, max_size)| *max_size)
+    }
+}
+
+/// Builds an error response with the given status, encoding the error as BCS
+/// if that's what the client asked for, so SDKs can decode it the same way as
+/// other errors.
+pub(crate) fn error_response(
+    accept_type: &AcceptType,
+    status: StatusCode,
+    message: String,
+) -> Error {
+    let error = AptosError::new_with_error_code(message, AptosErrorCode::WebFrameworkError);
+    let (body, content_type) = match accept_type {
+        AcceptType::Json => (
+            serde_json::to_vec(&error).expect("AptosError serializes to JSON"),
+            mime_types::JSON,
+        ),
+        AcceptType::Bcs => (
+            bcs::to_bytes(&error).expect("AptosError serializes to BCS"),
+            mime_types::BCS,
+        ),
+    };
+    Error::from_response(
+        Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, content_type)
+            .body(body),
+    )
+}
+
+fn payload_too_large(accept_type: &AcceptType, content_length: u64, max_size: u64) -> Error {
+    error_response(
+        accept_type,
+        StatusCode::PAYLOAD_TOO_LARGE,
+        format!(
+            "Request body of {} bytes exceeds the limit of {} bytes",
+            content_length, max_size
+        ),
+    )
 }
 
 #[async_trait::async_trait]
@@ -51,8 +126,13 @@ impl<E: Endpoint> Endpoint for PostSizeLimitEndpoint<E> {
             .typed_get::<headers::ContentLength>()
             .ok_or(SizedLimitError::MissingContentLength)?;
 
-        if content_length.0 > self.max_size {
-            return Err(SizedLimitError::PayloadTooLarge.into());
+        let max_size = self.max_size_for_path(req.original_uri().path());
+        if content_length.0 > max_size {
+            // An unsupported Accept header shouldn't hide that the payload is too large
+            let accept_type = AcceptType::from_request_without_body(&req)
+                .await
+                .unwrap_or(AcceptType::Json);
+            return Err(payload_too_large(&accept_type, content_length.0, max_size));
         }
 
         self.inner.call(req).await
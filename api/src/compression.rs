@@ -0,0 +1,234 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{accept_type::AcceptType, check_size::error_response};
+use aptos_config::config::CompressionConfig;
+use flate2::{read::GzDecoder, write::GzEncoder};
+use poem::{
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, VARY},
+        HeaderValue, StatusCode,
+    },
+    Body, Endpoint, Error, FromRequest, IntoResponse, Middleware, Request, Response, Result,
+};
+use std::io::{Read, Write};
+
+/// The content codings supported for request and response bodies.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    Gzip,
+    Zstd,
+}
+
+impl Encoding {
+    fn from_token(token: &str) -> Option<Self> {
+        match token.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "zstd" => Some(Encoding::Zstd),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// Picks the encoding to compress a response with, given the value of the
+/// request's Accept-Encoding header. Encodings with a higher quality value are
+/// preferred, with zstd winning ties.
+pub fn negotiate_encoding(accept_encoding: &str) -> Option<Encoding> {
+    accept_encoding
+        .split(',')
+        .filter_map(|coding| {
+            let mut parts = coding.split(';');
+            let encoding = Encoding::from_token(parts.next()?)?;
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (quality > 0.0).then_some((encoding, quality))
+        })
+        .max_by(|(a, a_quality), (b, b_quality)| {
+            a_quality
+                .total_cmp(b_quality)
+                .then_with(|| (*a == Encoding::Zstd).cmp(&(*b == Encoding::Zstd)))
+        })
+        .map(|(encoding, _)| encoding)
+}
+
+/// Decompresses `data`, failing if the decompressed data is larger than
+/// `max_size` bytes. Returns None in that case, so a decompression bomb is
+/// never fully inflated in memory.
+pub fn decompress(
+    encoding: Encoding,
+    data: &[u8],
+    max_size: u64,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let reader: Box<dyn Read + '_> = match encoding {
+        Encoding::Gzip => Box::new(GzDecoder::new(data)),
+        Encoding::Zstd => Box::new(zstd::stream::read::Decoder::new(data)?),
+    };
+    let mut decompressed = vec![];
+    reader
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() as u64 > max_size {
+        return Ok(None);
+    }
+    Ok(Some(decompressed))
+}
+
+/// Compresses `data` at the level configured for `encoding`.
+pub fn compress(
+    encoding: Encoding,
+    data: &[u8],
+    config: &CompressionConfig,
+) -> anyhow::Result<Vec<u8>> {
+    Ok(match encoding {
+        Encoding::Gzip => {
+            let mut encoder = GzEncoder::new(vec![], flate2::Compression::new(config.gzip_level));
+            encoder.write_all(data)?;
+            encoder.finish()?
+        },
+        Encoding::Zstd => zstd::bulk::compress(data, config.zstd_level)?,
+    })
+}
+
+/// This middleware decompresses request bodies sent with a Content-Encoding of
+/// gzip or zstd, and compresses response bodies for clients that accept it.
+///
+/// It should run after `PostSizeLimit`, which checks the size of the body as
+/// sent. The decompressed body is checked against its own limit instead.
+pub struct Compression {
+    config: CompressionConfig,
+}
+
+impl Compression {
+    pub fn new(config: CompressionConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for Compression {
+    type Output = CompressionEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        CompressionEndpoint {
+            inner: ep,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Endpoint for Compression middleware.
+pub struct CompressionEndpoint<E> {
+    inner: E,
+    config: CompressionConfig,
+}
+
+impl<E> CompressionEndpoint<E> {
+    /// Replaces a compressed request body with the decompressed one.
+    async fn decompress_request(&self, req: &mut Request, content_encoding: &str) -> Result<()> {
+        let accept_type = AcceptType::from_request_without_body(req).await?;
+        let encoding = Encoding::from_token(content_encoding).ok_or_else(|| {
+            error_response(
+                &accept_type,
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!(
+                    "Unsupported Content-Encoding {}, expected gzip or zstd",
+                    content_encoding
+                ),
+            )
+        })?;
+
+        let body = req.take_body().into_bytes().await?;
+        let max_size = self.config.max_decompressed_content_length;
+        let decompressed = decompress(encoding, &body, max_size)
+            .map_err(|err| {
+                error_response(
+                    &accept_type,
+                    StatusCode::BAD_REQUEST,
+                    format!(
+                        "Failed to decompress {} request body: {}",
+                        encoding.as_str(),
+                        err
+                    ),
+                )
+            })?
+            .ok_or_else(|| {
+                error_response(
+                    &accept_type,
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    format!(
+                        "Decompressed request body exceeds the limit of {} bytes",
+                        max_size
+                    ),
+                )
+            })?;
+
+        let headers = req.headers_mut();
+        headers.remove(CONTENT_ENCODING);
+        headers.insert(CONTENT_LENGTH, HeaderValue::from(decompressed.len()));
+        req.set_body(decompressed);
+        Ok(())
+    }
+
+    /// Compresses the response body, unless it's too small to be worth it.
+    async fn compress_response(&self, mut resp: Response, encoding: Encoding) -> Result<Response> {
+        resp.headers_mut()
+            .append(VARY, HeaderValue::from_static("accept-encoding"));
+        if resp.headers().contains_key(CONTENT_ENCODING) {
+            return Ok(resp);
+        }
+
+        let body = resp.take_body().into_bytes().await?;
+        if body.len() < self.config.min_response_size {
+            resp.set_body(body);
+            return Ok(resp);
+        }
+        let compressed = compress(encoding, &body, &self.config).map_err(Error::from)?;
+        resp.headers_mut().insert(
+            CONTENT_ENCODING,
+            HeaderValue::from_static(encoding.as_str()),
+        );
+        resp.headers_mut().remove(CONTENT_LENGTH);
+        resp.set_body(Body::from(compressed));
+        Ok(resp)
+    }
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for CompressionEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> Result<Self::Output> {
+        if !self.config.enabled {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        if let Some(content_encoding) = req
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+        {
+            if !content_encoding.trim().eq_ignore_ascii_case("identity") {
+                self.decompress_request(&mut req, &content_encoding).await?;
+            }
+        }
+
+        let response_encoding = req
+            .headers()
+            .get(ACCEPT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .and_then(negotiate_encoding);
+        let resp = self.inner.call(req).await?.into_response();
+        match response_encoding {
+            Some(encoding) => self.compress_response(resp, encoding).await,
+            None => Ok(resp),
+        }
+    }
+}
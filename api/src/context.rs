@@ -4,6 +4,10 @@
 
 use crate::{
     accept_type::AcceptType,
+    metrics::{
+        CACHE_HIT, CACHE_MISS, EPOCH_CACHE, GAS_ESTIMATION_CACHE_LOOKUPS, RESOURCE_CACHE_LOOKUPS,
+        VERSION_CACHE,
+    },
     response::{
         bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
         block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
@@ -44,17 +48,37 @@ use aptos_types::{
 };
 use aptos_utils::aptos_try;
 use aptos_vm::{data_cache::AsMoveResolver, move_vm_ext::AptosMoveResolver};
+use bytes::Bytes;
 use futures::{channel::oneshot, SinkExt};
+use lru::LruCache;
 use move_core_types::{
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     move_resource::MoveResource,
 };
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
     ops::{Bound::Included, Deref},
-    sync::{Arc, RwLock, RwLockWriteGuard},
-    time::Instant,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, RwLock, RwLockWriteGuard,
+    },
+    time::{Duration, Instant},
 };
+use tokio::sync::watch;
+
+/// Number of events read from storage at a time when filtering events by type
+const EVENT_TYPE_FILTER_BATCH_SIZE: u64 = 1000;
+
+/// A reserved slot for a request waiting on a transaction to be committed
+pub struct WaitForTransactionGuard(Arc<AtomicUsize>);
+
+impl Drop for WaitForTransactionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
 
 // Context holds application scope context
 #[derive(Clone)]
@@ -66,6 +90,11 @@ pub struct Context {
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
+    resource_cache: Arc<Mutex<VersionedCache<(AccountAddress, StructTag), Bytes>>>,
+    gas_estimation_version_cache: Arc<Mutex<VersionedCache<(), GasEstimation>>>,
+    hot_resources: Arc<HashSet<StructTag>>,
+    wait_for_transaction_connections: Arc<AtomicUsize>,
+    committed_version: Arc<watch::Sender<Option<Version>>>,
 }
 
 impl std::fmt::Debug for Context {
@@ -81,6 +110,27 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let resource_cache_config = &node_config.api.resource_cache;
+        let hot_resources = if resource_cache_config.enabled {
+            resource_cache_config
+                .hot_resources
+                .iter()
+                .filter_map(|resource_type| match StructTag::from_str(resource_type) {
+                    Ok(resource_type) => Some(resource_type),
+                    Err(err) => {
+                        warn!(
+                            "Not caching invalid hot resource type {}: {}",
+                            resource_type, err
+                        );
+                        None
+                    },
+                })
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let resource_cache = VersionedCache::new(resource_cache_config.max_entries);
+
         Self {
             chain_id,
             db,
@@ -101,6 +151,11 @@ impl Context {
                 block_executor_onchain_config: OnChainExecutionConfig::default_if_missing()
                     .block_executor_onchain_config(),
             })),
+            resource_cache: Arc::new(Mutex::new(resource_cache)),
+            gas_estimation_version_cache: Arc::new(Mutex::new(VersionedCache::new(1))),
+            hot_resources: Arc::new(hot_resources),
+            wait_for_transaction_connections: Arc::new(AtomicUsize::new(0)),
+            committed_version: Arc::new(watch::channel(None).0),
         }
     }
 
@@ -112,6 +167,10 @@ impl Context {
         self.node_config.api.max_events_page_size
     }
 
+    pub fn max_events_scanned_by_type_filter(&self) -> u64 {
+        self.node_config.api.max_events_scanned_by_type_filter
+    }
+
     pub fn max_account_resources_page_size(&self) -> u16 {
         self.node_config.api.max_account_resources_page_size
     }
@@ -166,6 +225,10 @@ impl Context {
         self.node_config.api.content_length_limit()
     }
 
+    pub fn route_content_length_limits(&self) -> &BTreeMap<String, u64> {
+        &self.node_config.api.route_content_length_limits
+    }
+
     pub fn failpoints_enabled(&self) -> bool {
         self.node_config.api.failpoints_enabled
     }
@@ -174,6 +237,49 @@ impl Context {
         self.node_config.api.max_submit_transaction_batch_size
     }
 
+    pub fn entry_function_payloads_only(&self) -> bool {
+        self.node_config.api.entry_function_payloads_only
+    }
+
+    pub fn max_wait_for_transaction(&self) -> Duration {
+        Duration::from_secs(self.node_config.api.max_wait_for_transaction_secs)
+    }
+
+    pub fn committed_version_check_interval(&self) -> Duration {
+        Duration::from_millis(self.node_config.api.committed_version_check_interval_ms)
+    }
+
+    /// Publishes the latest committed version to the subscribers of
+    /// [`Self::subscribe_to_committed_version`], if it changed since it was last published
+    pub fn notify_committed_version(&self) {
+        if let Ok(version) = self.db.get_latest_version() {
+            self.committed_version
+                .send_if_modified(|committed_version| {
+                    let changed = *committed_version != Some(version);
+                    *committed_version = Some(version);
+                    changed
+                });
+        }
+    }
+
+    /// Subscribes to the latest committed version, as published by
+    /// [`Self::notify_committed_version`]
+    pub fn subscribe_to_committed_version(&self) -> watch::Receiver<Option<Version>> {
+        self.committed_version.subscribe()
+    }
+
+    /// Reserves one of the limited slots for requests waiting on a transaction to be committed,
+    /// returning `None` if they are all taken. The slot is freed when the guard is dropped.
+    pub fn try_reserve_wait_for_transaction(&self) -> Option<WaitForTransactionGuard> {
+        let max_connections = self.node_config.api.max_wait_for_transaction_connections;
+        self.wait_for_transaction_connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |connections| {
+                (connections < max_connections).then_some(connections + 1)
+            })
+            .ok()
+            .map(|_| WaitForTransactionGuard(self.wait_for_transaction_connections.clone()))
+    }
+
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
         let (req_sender, callback) = oneshot::channel();
         self.mp_sender
@@ -855,6 +961,93 @@ impl Context {
         }
     }
 
+    /// Returns up to `limit` events from `start` downwards, newest first. Without `start`,
+    /// begins at the latest event.
+    pub fn get_events_descending(
+        &self,
+        event_key: &EventKey,
+        start: Option<u64>,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<EventWithVersion>> {
+        Ok(self
+            .db
+            .get_events_rev_iterator(event_key, start, limit as u64, ledger_version)?
+            .collect::<aptos_storage_interface::Result<_>>()?)
+    }
+
+    /// Same as [`Self::get_events`], or [`Self::get_events_descending`] if `descending`, but
+    /// only returns events of the given type.
+    ///
+    /// The filter is applied while paging through storage, so `limit` refers to the number of
+    /// matching events rather than the number of events scanned. At most
+    /// [`Self::max_events_scanned_by_type_filter`] events are scanned; if the scan stops before
+    /// the end of the event stream, the sequence number to continue it from is returned as well.
+    pub fn get_events_of_type(
+        &self,
+        event_key: &EventKey,
+        start: Option<u64>,
+        limit: u16,
+        ledger_version: u64,
+        event_type: &TypeTag,
+        descending: bool,
+    ) -> Result<(Vec<EventWithVersion>, Option<u64>)> {
+        let limit = limit as usize;
+        // Scanning nothing would return the `start` as the cursor, so clients would never advance
+        let max_scanned = self.max_events_scanned_by_type_filter().max(1);
+        let mut matching = Vec::with_capacity(limit);
+        let mut num_scanned = 0;
+        let (order, mut next_seq_num) = match start {
+            Some(start) if !descending => (Order::Ascending, Some(start)),
+            Some(start) => (Order::Descending, Some(start)),
+            None => (Order::Descending, Some(u64::MAX)),
+        };
+
+        while let Some(seq_num) = next_seq_num {
+            if matching.len() >= limit || num_scanned >= max_scanned {
+                break;
+            }
+            let batch_size = EVENT_TYPE_FILTER_BATCH_SIZE.min(max_scanned - num_scanned);
+            let batch =
+                self.db
+                    .get_events(event_key, seq_num, order, batch_size, ledger_version)?;
+            let is_last_batch = (batch.len() as u64) < batch_size;
+            let batch_len = batch.len();
+
+            let mut num_scanned_in_batch = 0;
+            for event in batch {
+                num_scanned_in_batch += 1;
+                let seq_num = event.event.v1()?.sequence_number();
+                next_seq_num = match order {
+                    Order::Ascending => seq_num.checked_add(1),
+                    Order::Descending => seq_num.checked_sub(1),
+                };
+                if event.event.type_tag() == event_type {
+                    matching.push(event);
+                    if matching.len() >= limit {
+                        break;
+                    }
+                }
+            }
+            num_scanned += num_scanned_in_batch as u64;
+
+            // The whole event stream has been scanned
+            if is_last_batch && num_scanned_in_batch == batch_len {
+                next_seq_num = None;
+            }
+        }
+
+        // Without a `start`, the latest events are scanned. The cursor would continue towards
+        // older events, which isn't where an ascending page continues, so it is not returned.
+        let cursor = if order == Order::Descending && !descending {
+            matching.reverse();
+            None
+        } else {
+            next_seq_num
+        };
+        Ok((matching, cursor))
+    }
+
     fn next_bucket(&self, gas_unit_price: u64) -> u64 {
         match self
             .node_config
@@ -876,6 +1069,54 @@ impl Context {
         }
     }
 
+    /// Reads a resource at the given ledger version. If the resource type is one of the
+    /// configured hot resources, the value is served from and stored in the resource cache.
+    pub fn get_resource_bytes_cached<E>(
+        &self,
+        version: Version,
+        address: AccountAddress,
+        resource_type: &StructTag,
+        read_from_storage: impl FnOnce() -> Result<Option<Bytes>, E>,
+    ) -> Result<Option<Bytes>, E> {
+        if !self.hot_resources.contains(resource_type) {
+            return read_from_storage();
+        }
+        let resource_type_label = resource_type.to_canonical_string();
+        let ttl = Duration::from_millis(self.node_config.api.resource_cache.ttl_ms);
+        let key = (address, resource_type.clone());
+
+        let cached = self.resource_cache.lock().unwrap().get(version, &key, ttl);
+        if let Some(bytes) = cached {
+            RESOURCE_CACHE_LOOKUPS
+                .with_label_values(&[&resource_type_label, CACHE_HIT])
+                .inc();
+            return Ok(Some(bytes));
+        }
+        RESOURCE_CACHE_LOOKUPS
+            .with_label_values(&[&resource_type_label, CACHE_MISS])
+            .inc();
+
+        // Don't hold the lock while reading from storage
+        let maybe_bytes = read_from_storage()?;
+        if let Some(bytes) = &maybe_bytes {
+            self.resource_cache
+                .lock()
+                .unwrap()
+                .put(version, key, bytes.clone());
+        }
+        Ok(maybe_bytes)
+    }
+
+    /// Returns the ledger version the resource cache currently holds values for.
+    pub fn resource_cache_version(&self) -> Option<Version> {
+        self.resource_cache.lock().unwrap().version
+    }
+
+    /// Returns the ledger version the gas estimation cache currently holds an estimation for.
+    pub fn gas_estimation_cache_version(&self) -> Option<Version> {
+        self.gas_estimation_version_cache.lock().unwrap().version
+    }
+
     fn cached_gas_estimation<T>(&self, cache: &T, current_epoch: u64) -> Option<GasEstimation>
     where
         T: Deref<Target = GasEstimationCache>,
@@ -954,11 +1195,57 @@ impl Context {
             });
         }
 
+        // Estimations only depend on the ledger, so serve repeated requests at the same ledger
+        // version from the version keyed cache
+        let resource_cache_config = &self.node_config.api.resource_cache;
+        if !resource_cache_config.enabled {
+            return self.estimate_gas_price_from_blocks(
+                ledger_info,
+                min_gas_unit_price,
+                block_config,
+            );
+        }
+        let version = ledger_info.version();
+        let ttl = Duration::from_millis(resource_cache_config.ttl_ms);
+        let cached = self
+            .gas_estimation_version_cache
+            .lock()
+            .unwrap()
+            .get(version, &(), ttl);
+        if let Some(estimation) = cached {
+            GAS_ESTIMATION_CACHE_LOOKUPS
+                .with_label_values(&[VERSION_CACHE, CACHE_HIT])
+                .inc();
+            return Ok(estimation);
+        }
+        GAS_ESTIMATION_CACHE_LOOKUPS
+            .with_label_values(&[VERSION_CACHE, CACHE_MISS])
+            .inc();
+
+        let estimation =
+            self.estimate_gas_price_from_blocks(ledger_info, min_gas_unit_price, block_config)?;
+        self.gas_estimation_version_cache
+            .lock()
+            .unwrap()
+            .put(version, (), estimation);
+        Ok(estimation)
+    }
+
+    fn estimate_gas_price_from_blocks<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        min_gas_unit_price: u64,
+        block_config: BlockExecutorConfigFromOnchain,
+    ) -> Result<GasEstimation, E> {
+        let config = &self.node_config.api.gas_estimation;
         let epoch = ledger_info.epoch.0;
 
         // 0. (0) Return cached result if it exists
         let cache = self.gas_estimation_cache.read().unwrap();
         if let Some(cached_gas_estimation) = self.cached_gas_estimation(&cache, epoch) {
+            GAS_ESTIMATION_CACHE_LOOKUPS
+                .with_label_values(&[EPOCH_CACHE, CACHE_HIT])
+                .inc();
             return Ok(cached_gas_estimation);
         }
         drop(cache);
@@ -967,8 +1254,14 @@ impl Context {
         let mut cache = self.gas_estimation_cache.write().unwrap();
         // Retry cached result after acquiring write lock
         if let Some(cached_gas_estimation) = self.cached_gas_estimation(&cache, epoch) {
+            GAS_ESTIMATION_CACHE_LOOKUPS
+                .with_label_values(&[EPOCH_CACHE, CACHE_HIT])
+                .inc();
             return Ok(cached_gas_estimation);
         }
+        GAS_ESTIMATION_CACHE_LOOKUPS
+            .with_label_values(&[EPOCH_CACHE, CACHE_MISS])
+            .inc();
         // Clear the cache if the epoch has changed
         if let Some(cached_epoch) = cache.last_updated_epoch {
             if cached_epoch != epoch {
@@ -1312,6 +1605,49 @@ pub struct GasEstimationCache {
     min_inclusion_prices: BTreeMap<(u64, u64), u64>,
 }
 
+/// An LRU cache that only holds values for the latest ledger version it has seen: reading at a
+/// newer version drops all entries, and reading at an older version bypasses the cache.
+pub struct VersionedCache<K: Hash + Eq, V: Clone> {
+    /// The ledger version all entries were read at
+    version: Option<Version>,
+    /// key -> (time of read, value)
+    entries: LruCache<K, (Instant, V)>,
+}
+
+impl<K: Hash + Eq, V: Clone> VersionedCache<K, V> {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            version: None,
+            entries: LruCache::new(max_entries.max(1)),
+        }
+    }
+
+    /// Returns the value cached for `key` at `version`, if it was read less than `ttl` ago.
+    fn get(&mut self, version: Version, key: &K, ttl: Duration) -> Option<V> {
+        match self.version {
+            Some(cached_version) if cached_version > version => None,
+            Some(cached_version) if cached_version == version => self
+                .entries
+                .get(key)
+                .filter(|(time, _)| time.elapsed() < ttl)
+                .map(|(_, value)| value.clone()),
+            _ => {
+                self.entries.clear();
+                self.version = Some(version);
+                None
+            },
+        }
+    }
+
+    /// Caches the value of `key` read at `version`, unless another read has moved the cache to
+    /// a different version in the meantime.
+    fn put(&mut self, version: Version, key: K, value: V) {
+        if self.version == Some(version) {
+            self.entries.put(key, (Instant::now(), value));
+        }
+    }
+}
+
 pub struct GasLimitCache {
     last_updated_epoch: Option<u64>,
     block_executor_onchain_config: BlockExecutorConfigFromOnchain,
@@ -2,12 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_api_types::{mime_types::JSON, AptosError, AptosErrorCode};
+use aptos_storage_interface::AptosDbError;
+use aptos_types::state_store::errors::StateviewError;
 use poem::{
-    http::header::{HeaderValue, CONTENT_TYPE},
+    http::{
+        header::{HeaderValue, CONTENT_TYPE, RETRY_AFTER},
+        StatusCode,
+    },
     IntoResponse, Response,
 };
 use poem_openapi::payload::Json;
 
+/// How long clients should wait before retrying a request that failed with a 503
+const RETRY_AFTER_SECS: &str = "1";
+
 // The way I'm determining which errors are framework errors is very janky, as
 // is the way I'm building the response. See:
 // - https://github.com/poem-web/poem/issues/343
@@ -33,7 +41,13 @@ pub async fn convert_error(error: poem::Error) -> impl poem::IntoResponse {
             .insert(CONTENT_TYPE, HeaderValue::from_static(JSON));
         response
     } else {
-        error.into_response()
+        let mut response = error.into_response();
+        if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from_static(RETRY_AFTER_SECS));
+        }
+        response
     }
 }
 
@@ -44,3 +58,52 @@ fn build_error_response(error_string: String) -> Response {
     ))
     .into_response()
 }
+
+/// What went wrong when reading from storage, as far as the client is concerned.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StorageErrorKind {
+    /// The data was pruned. Includes the oldest version that is still available, if storage
+    /// reported it.
+    Pruned { oldest_version: Option<u64> },
+    /// The data doesn't exist.
+    NotFound,
+    /// Anything else, which is likely a bug or a problem with the node.
+    Other,
+}
+
+/// Looks through the chain of `error` for the storage error types, so the context added on the
+/// way up to the API doesn't hide them.
+pub fn classify_storage_error(error: &anyhow::Error) -> StorageErrorKind {
+    for cause in error.chain() {
+        match cause.downcast_ref::<AptosDbError>() {
+            Some(AptosDbError::NotFound(_)) => return StorageErrorKind::NotFound,
+            Some(AptosDbError::Pruned {
+                min_readable_version,
+                ..
+            }) => {
+                return StorageErrorKind::Pruned {
+                    oldest_version: Some(*min_readable_version),
+                }
+            },
+            Some(AptosDbError::MissingRootError(_)) => {
+                return StorageErrorKind::Pruned {
+                    oldest_version: None,
+                }
+            },
+            _ => (),
+        }
+        match cause.downcast_ref::<StateviewError>() {
+            Some(StateviewError::NotFound(_)) => return StorageErrorKind::NotFound,
+            Some(StateviewError::Pruned {
+                min_readable_version,
+                ..
+            }) => {
+                return StorageErrorKind::Pruned {
+                    oldest_version: Some(*min_readable_version),
+                }
+            },
+            _ => (),
+        }
+    }
+    StorageErrorKind::Other
+}
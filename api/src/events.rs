@@ -16,11 +16,12 @@ use crate::{
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    verify_field_identifier, Address, AptosErrorCode, AsConverter, EventOrder, IdentifierWrapper,
+    LedgerInfo, MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
 use aptos_types::event::EventKey;
 use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -65,7 +66,20 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Only return events of this type e.g. `0x1::coin::DepositEvent`
+        ///
+        /// The filter is applied before pagination, so `limit` refers to the
+        /// number of matching events. A bounded number of events is scanned
+        /// per request; if the scan stops early, pass the cursor returned in
+        /// the X-Aptos-Cursor header as `start` to continue it.
+        event_type: Query<Option<MoveStructTag>>,
+        /// Order of the returned events, `asc` or `desc`.
+        ///
+        /// With `desc`, events are returned newest first, starting at `start`, or at the
+        /// most recent event if unspecified. Defaults to `asc`.
+        order: Query<Option<EventOrder>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        let event_type = parse_event_type(event_type.0)?;
         fail_point_poem("endpoint_get_events_by_event_key")?;
         self.context
             .check_api_output_enabled("Get events by event key", &accept_type)?;
@@ -85,6 +99,8 @@ impl EventsApi {
                 accept_type,
                 page,
                 EventKey::new(creation_number.0 .0, address.0.into()),
+                event_type,
+                order.0.unwrap_or(EventOrder::Asc),
             )
         })
         .await
@@ -121,6 +137,18 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Only return events of this type e.g. `0x1::coin::DepositEvent`
+        ///
+        /// The filter is applied before pagination, so `limit` refers to the
+        /// number of matching events. A bounded number of events is scanned
+        /// per request; if the scan stops early, pass the cursor returned in
+        /// the X-Aptos-Cursor header as `start` to continue it.
+        event_type: Query<Option<MoveStructTag>>,
+        /// Order of the returned events, `asc` or `desc`.
+        ///
+        /// With `desc`, events are returned newest first, starting at `start`, or at the
+        /// most recent event if unspecified. Defaults to `asc`.
+        order: Query<Option<EventOrder>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         event_handle
             .0
@@ -134,6 +162,7 @@ impl EventsApi {
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
             })?;
+        let event_type = parse_event_type(event_type.0)?;
         fail_point_poem("endpoint_get_events_by_event_handle")?;
         self.context
             .check_api_output_enabled("Get events by event handle", &accept_type)?;
@@ -147,38 +176,59 @@ impl EventsApi {
         api_spawn_blocking(move || {
             let account = Account::new(api.context.clone(), address.0, None, None, None)?;
             let key = account.find_event_key(event_handle.0, field_name.0.into())?;
-            api.list(account.latest_ledger_info, accept_type, page, key)
+            api.list(
+                account.latest_ledger_info,
+                accept_type,
+                page,
+                key,
+                event_type,
+                order.0.unwrap_or(EventOrder::Asc),
+            )
         })
         .await
     }
 }
 
 impl EventsApi {
-    /// List events from an [`EventKey`]
+    /// List events from an [`EventKey`] in the given order, optionally only those of the given
+    /// type
     fn list(
         &self,
         latest_ledger_info: LedgerInfo,
         accept_type: AcceptType,
         page: Page,
         event_key: EventKey,
+        event_type: Option<TypeTag>,
+        order: EventOrder,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         let ledger_version = latest_ledger_info.version();
-        let events = self
-            .context
-            .get_events(
+        let limit = page.limit(&latest_ledger_info)?;
+        let (events, cursor) = match (&event_type, order) {
+            (Some(event_type), _) => self.context.get_events_of_type(
                 &event_key,
                 page.start_option(),
-                page.limit(&latest_ledger_info)?,
+                limit,
                 ledger_version,
+                event_type,
+                order == EventOrder::Desc,
+            ),
+            (None, EventOrder::Asc) => self
+                .context
+                .get_events(&event_key, page.start_option(), limit, ledger_version)
+                .map(|events| (events, None)),
+            (None, EventOrder::Desc) => self
+                .context
+                .get_events_descending(&event_key, page.start_option(), limit, ledger_version)
+                .map(|events| (events, None)),
+        }
+        .context(format!("Failed to find events by key {}", event_key))
+        .map_err(|err| {
+            BasicErrorWith404::internal_with_code(
+                err,
+                AptosErrorCode::InternalError,
+                &latest_ledger_info,
             )
-            .context(format!("Failed to find events by key {}", event_key))
-            .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
-                    err,
-                    AptosErrorCode::InternalError,
-                    &latest_ledger_info,
-                )
-            })?;
+        })?;
 
         match accept_type {
             AcceptType::Json => {
@@ -198,10 +248,33 @@ impl EventsApi {
                     })?;
 
                 BasicResponse::try_from_json((events, &latest_ledger_info, BasicResponseStatus::Ok))
+                    .map(|v| v.with_sequence_number_cursor(cursor))
             },
             AcceptType::Bcs => {
                 BasicResponse::try_from_bcs((events, &latest_ledger_info, BasicResponseStatus::Ok))
+                    .map(|v| v.with_sequence_number_cursor(cursor))
             },
         }
     }
 }
+
+/// Validates the `event_type` query parameter and converts it into a [`TypeTag`]
+fn parse_event_type(
+    event_type: Option<MoveStructTag>,
+) -> Result<Option<TypeTag>, BasicErrorWith404> {
+    event_type
+        .map(|event_type| {
+            event_type
+                .verify(0)
+                .and_then(|_| StructTag::try_from(event_type))
+                .map(|struct_tag| TypeTag::Struct(Box::new(struct_tag)))
+                .context("'event_type' invalid")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code_no_info(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                    )
+                })
+        })
+        .transpose()
+}
@@ -10,6 +10,7 @@ mod basic;
 mod bcs_payload;
 mod blocks;
 mod check_size;
+mod compression;
 pub mod context;
 mod error_converter;
 mod events;
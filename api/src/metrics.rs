@@ -77,3 +77,26 @@ pub static GAS_ESTIMATE: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub const CACHE_HIT: &str = "hit";
+pub const CACHE_MISS: &str = "miss";
+pub const VERSION_CACHE: &str = "version";
+pub const EPOCH_CACHE: &str = "epoch";
+
+pub static RESOURCE_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_resource_cache_lookups",
+        "API resource cache lookups grouped by resource type and result (hit or miss)",
+        &["resource_type", "result"]
+    )
+    .unwrap()
+});
+
+pub static GAS_ESTIMATION_CACHE_LOOKUPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_gas_estimation_cache_lookups",
+        "API gas estimation cache lookups grouped by cache (version or epoch) and result (hit or miss)",
+        &["cache", "result"]
+    )
+    .unwrap()
+});
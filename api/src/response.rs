@@ -29,7 +29,11 @@
 
 // TODO: https://github.com/aptos-labs/aptos-core/issues/2279
 
-use super::{accept_type::AcceptType, bcs_payload::Bcs};
+use super::{
+    accept_type::AcceptType,
+    bcs_payload::Bcs,
+    error_converter::{classify_storage_error, StorageErrorKind},
+};
 use aptos_api_types::{Address, AptosError, AptosErrorCode, HashValue, LedgerInfo};
 use move_core_types::{
     identifier::{IdentStr, Identifier},
@@ -158,7 +162,8 @@ macro_rules! generate_error_response {
                 error_code: aptos_api_types::AptosErrorCode,
                 ledger_info: &aptos_api_types::LedgerInfo
             )-> Self where Self: Sized {
-                let error = aptos_api_types::AptosError::new_with_error_code(err, error_code);
+                let mut error = aptos_api_types::AptosError::new_with_error_code(err, error_code);
+                error.retriable |= $crate::response::is_retriable_status($status);
                 let payload = poem_openapi::payload::Json(Box::new(error));
 
                 Self::from($enum_name::$name(
@@ -177,7 +182,8 @@ macro_rules! generate_error_response {
                 err: Err,
                 error_code: aptos_api_types::AptosErrorCode,
             )-> Self where Self: Sized {
-                let error = aptos_api_types::AptosError::new_with_error_code(err, error_code);
+                let mut error = aptos_api_types::AptosError::new_with_error_code(err, error_code);
+                error.retriable |= $crate::response::is_retriable_status($status);
                 let payload = poem_openapi::payload::Json(Box::new(error));
 
                 Self::from($enum_name::$name(
@@ -198,7 +204,8 @@ macro_rules! generate_error_response {
                 vm_status: aptos_types::vm_status::StatusCode,
                 ledger_info: &aptos_api_types::LedgerInfo
             ) -> Self where Self: Sized {
-                let error = aptos_api_types::AptosError::new_with_vm_status(err, error_code, vm_status);
+                let mut error = aptos_api_types::AptosError::new_with_vm_status(err, error_code, vm_status);
+                error.retriable |= $crate::response::is_retriable_status($status);
                 let payload = poem_openapi::payload::Json(Box::new(error));
                 Self::from($enum_name::$name(
                     payload,
@@ -213,9 +220,10 @@ macro_rules! generate_error_response {
             }
 
             fn [<$name:snake _from_aptos_error>](
-                aptos_error: aptos_api_types::AptosError,
+                mut aptos_error: aptos_api_types::AptosError,
                 ledger_info: &aptos_api_types::LedgerInfo
             ) -> Self where Self: Sized {
+                aptos_error.retriable |= $crate::response::is_retriable_status($status);
                 let payload = poem_openapi::payload::Json(Box::new(aptos_error));
                 Self::from($enum_name::$name(
                     payload,
@@ -471,11 +479,28 @@ macro_rules! generate_success_response {
                 }
                 self
             }
+
+            pub fn with_sequence_number_cursor(mut self, new_cursor: Option<u64>) -> Self {
+                match self {
+                    $(
+                    [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
+                        *cursor = new_cursor.map(|c| c.to_string());
+                    }
+                    )*
+                }
+                self
+            }
         }
         }
     };
 }
 
+/// Whether errors returned with the given status are transient, regardless of their error code,
+/// so clients may retry the same request later.
+pub fn is_retriable_status(status: u16) -> bool {
+    status == 503
+}
+
 // Generate a success response that only has an option for 200.
 generate_success_response!(BasicResponse, (200, Ok));
 
@@ -606,12 +631,40 @@ pub fn transaction_not_found_by_hash<E: NotFoundError>(
 
 pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
     E::gone_with_code(
-        format!("Ledger version({}) has been pruned", ledger_version),
+        format!(
+            "Ledger version({}) has been pruned, oldest available version is {}",
+            ledger_version, ledger_info.oldest_ledger_version
+        ),
         AptosErrorCode::VersionPruned,
         ledger_info,
     )
 }
 
+/// Converts an error from reading storage into a response. Reads of pruned data are a 410 with
+/// the oldest available version, reads of data that doesn't exist are a 404 with
+/// `not_found_code`, and anything else is a 500.
+pub fn storage_error<E: NotFoundError + GoneError + InternalError>(
+    err: anyhow::Error,
+    not_found_code: AptosErrorCode,
+    ledger_info: &LedgerInfo,
+) -> E {
+    match classify_storage_error(&err) {
+        StorageErrorKind::Pruned { oldest_version } => E::gone_with_code(
+            format!(
+                "{:#}, oldest available version is {}",
+                err,
+                oldest_version.unwrap_or_else(|| ledger_info.oldest_ledger_version.0)
+            ),
+            AptosErrorCode::VersionPruned,
+            ledger_info,
+        ),
+        StorageErrorKind::NotFound => E::not_found_with_code(err, not_found_code, ledger_info),
+        StorageErrorKind::Other => {
+            E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+        },
+    }
+}
+
 pub fn account_not_found<E: NotFoundError>(
     address: Address,
     ledger_version: u64,
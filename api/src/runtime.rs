@@ -4,9 +4,9 @@
 
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    compression::Compression, context::Context, error_converter::convert_error, events::EventsApi,
+    index::IndexApi, log::middleware_log, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::{ApiConfig, NodeConfig};
@@ -142,6 +142,8 @@ pub fn attach_poem_to_runtime(
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let route_size_limits = context.route_content_length_limits().clone();
+    let compression_config = config.api.compression.clone();
 
     let api_service = get_api_service(context.clone());
 
@@ -187,6 +189,22 @@ pub fn attach_poem_to_runtime(
     let actual_address = *actual_address
         .as_socket_addr()
         .context("Failed to get socket addr from local addr for Poem webserver")?;
+
+    // Notify the transaction lookups waiting on a commit whenever a new version is committed
+    if config.api.max_wait_for_transaction_secs > 0 {
+        let context = context.clone();
+        runtime_handle.spawn(async move {
+            let mut interval = tokio::time::interval(context.committed_version_check_interval());
+            loop {
+                interval.tick().await;
+                let context = context.clone();
+                tokio::task::spawn_blocking(move || context.notify_committed_version())
+                    .await
+                    .unwrap_or(());
+            }
+        });
+    }
+
     runtime_handle.spawn(async move {
         let cors = Cors::new()
             // To allow browsers to use cookies (for cookie-based sticky
@@ -211,8 +229,9 @@ pub fn attach_poem_to_runtime(
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
                     ),
             )
+            .with(Compression::new(compression_config))
             .with(cors)
-            .with(PostSizeLimit::new(size_limit))
+            .with(PostSizeLimit::new(size_limit).with_route_limits(route_size_limits))
             // NOTE: Make sure to keep this after all the `with` middleware.
             .catch_all_error(convert_error)
             .around(middleware_log);
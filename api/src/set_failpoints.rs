@@ -2,15 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::context::Context;
-#[allow(unused_imports)]
-use anyhow::{format_err, Result};
+use anyhow::{bail, ensure, format_err, Result};
 #[cfg(feature = "failpoints")]
 use aptos_logger::prelude::*;
+#[allow(unused_imports)]
 use poem::{
     handler,
-    web::{Data, Query},
+    http::StatusCode,
+    web::{Data, Json, Query},
 };
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The fields supported by the extended action syntax.
+const SUPPORTED_FIELDS: &[&str] = &["prob", "limit", "action"];
+
+/// The action types supported by the fail crate.
+const SUPPORTED_ACTIONS: &[&str] = &[
+    "off", "return", "sleep", "panic", "print", "pause", "yield", "delay",
+];
 
 #[derive(Deserialize, Serialize)]
 pub struct FailpointConf {
@@ -18,20 +28,191 @@ pub struct FailpointConf {
     actions: String,
 }
 
+/// A failpoint action in the extended syntax, e.g. `prob=0.1,limit=100,action=return(err)`.
+///
+/// The action is taken with probability `prob` each time the failpoint is hit, and at most
+/// `limit` times. Only hits that take the action count towards the limit, and once the limit is
+/// exhausted the failpoint is a no-op.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FailpointAction {
+    pub prob: Option<f64>,
+    pub limit: Option<usize>,
+    pub action: String,
+}
+
+impl FailpointAction {
+    /// Translates the action into the fail crate's native `[<pct>%][<cnt>*]<type>[(<arg>)]`
+    /// syntax.
+    pub fn to_native_syntax(&self) -> String {
+        let mut actions = String::new();
+        if let Some(prob) = self.prob {
+            // Go through f32 (which the fail crate parses into) to avoid printing float noise
+            actions.push_str(&format!("{}%", (prob * 100.0) as f32));
+        }
+        if let Some(limit) = self.limit {
+            actions.push_str(&format!("{}*", limit));
+        }
+        actions.push_str(&self.action);
+        actions
+    }
+}
+
+impl FromStr for FailpointAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (mut prob, mut limit, mut action) = (None, None, None);
+        for field in split_top_level(s, ',') {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| format_err!("expected <field>=<value>, got '{}'", field))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "prob" => {
+                    ensure!(prob.is_none(), "field 'prob' is set more than once");
+                    let value: f64 = value
+                        .parse()
+                        .map_err(|e| format_err!("invalid prob '{}': {}", value, e))?;
+                    ensure!(
+                        value > 0.0 && value <= 1.0,
+                        "prob must be in (0, 1], got {}",
+                        value
+                    );
+                    prob = Some(value);
+                },
+                "limit" => {
+                    ensure!(limit.is_none(), "field 'limit' is set more than once");
+                    let value: usize = value
+                        .parse()
+                        .map_err(|e| format_err!("invalid limit '{}': {}", value, e))?;
+                    ensure!(value > 0, "limit must be positive");
+                    limit = Some(value);
+                },
+                "action" => {
+                    ensure!(action.is_none(), "field 'action' is set more than once");
+                    validate_action(value)?;
+                    action = Some(value.to_string());
+                },
+                _ => bail!("unknown field '{}'", key),
+            }
+        }
+
+        let action = action.ok_or_else(|| format_err!("field 'action' is required"))?;
+        ensure!(
+            action != "off" || (prob.is_none() && limit.is_none()),
+            "prob and limit can't be used with the 'off' action"
+        );
+        Ok(Self {
+            prob,
+            limit,
+            action,
+        })
+    }
+}
+
+/// Checks that `action` is a single fail crate task, i.e. `<type>[(<arg>)]`.
+fn validate_action(action: &str) -> Result<()> {
+    let (action_type, arg) = match action.split_once('(') {
+        Some((action_type, rest)) => {
+            let arg = rest
+                .strip_suffix(')')
+                .ok_or_else(|| format_err!("unbalanced parentheses in action '{}'", action))?;
+            (action_type, Some(arg))
+        },
+        None => (action, None),
+    };
+    ensure!(
+        SUPPORTED_ACTIONS.contains(&action_type),
+        "unknown action '{}', supported actions: {}",
+        action_type,
+        SUPPORTED_ACTIONS.join(", ")
+    );
+    match (action_type, arg) {
+        ("sleep" | "delay", arg) => {
+            let arg = arg.ok_or_else(|| format_err!("'{}' requires a duration", action_type))?;
+            arg.parse::<u64>().map_err(|e| {
+                format_err!("invalid duration '{}' for '{}': {}", arg, action_type, e)
+            })?;
+        },
+        ("off" | "pause" | "yield", Some(_)) => {
+            bail!("'{}' doesn't take an argument", action_type)
+        },
+        _ => {},
+    }
+    Ok(())
+}
+
+/// Splits `s` on `separator`, ignoring separators nested in parentheses.
+fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            },
+            _ => {},
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Parses the actions of a failpoint configuration. Actions in the extended syntax (i.e. with
+/// `<field>=<value>` pairs) are validated and translated into the fail crate's native syntax,
+/// anything else is passed through to the fail crate as is.
+pub fn parse_actions(actions: &str) -> Result<(String, Option<FailpointAction>)> {
+    let is_extended = split_top_level(actions, ',')
+        .iter()
+        .any(|field| split_top_level(field, '=').len() > 1);
+    if !is_extended {
+        return Ok((actions.to_string(), None));
+    }
+
+    let action: FailpointAction = actions.parse().map_err(|e| {
+        format_err!(
+            "Invalid failpoint actions '{}': {}. Supported fields: {}",
+            actions,
+            e,
+            SUPPORTED_FIELDS.join(", ")
+        )
+    })?;
+    Ok((action.to_native_syntax(), Some(action)))
+}
+
+/// The failpoint configuration that was installed by a set call.
+#[derive(Debug, Serialize)]
+pub struct SetFailpointResponse {
+    pub name: String,
+    /// The actions in the fail crate's native syntax
+    pub actions: String,
+    /// The parsed actions, if they were given in the extended syntax
+    pub config: Option<FailpointAction>,
+}
+
 #[cfg(feature = "failpoints")]
 #[handler]
 pub fn set_failpoint_poem(
     context: Data<&std::sync::Arc<Context>>,
     Query(failpoint_conf): Query<FailpointConf>,
-) -> poem::Result<String> {
+) -> poem::Result<Json<SetFailpointResponse>> {
     if context.failpoints_enabled() {
-        fail::cfg(&failpoint_conf.name, &failpoint_conf.actions)
+        let (actions, config) = parse_actions(&failpoint_conf.actions)
+            .map_err(|e| poem::Error::from_string(e.to_string(), StatusCode::BAD_REQUEST))?;
+        fail::cfg(&failpoint_conf.name, &actions)
             .map_err(|e| poem::Error::from(anyhow::anyhow!(e)))?;
         info!(
             "Configured failpoint {} to {}",
-            failpoint_conf.name, failpoint_conf.actions
+            failpoint_conf.name, actions
         );
-        Ok(format!("Set failpoint {}", failpoint_conf.name))
+        Ok(Json(SetFailpointResponse {
+            name: failpoint_conf.name,
+            actions,
+            config,
+        }))
     } else {
         Err(poem::Error::from(anyhow::anyhow!(
             "Failpoints are not enabled at a config level"
@@ -45,7 +226,7 @@ pub fn set_failpoint_poem(
 pub fn set_failpoint_poem(
     context: Data<&std::sync::Arc<Context>>,
     Query(failpoint_conf): Query<FailpointConf>,
-) -> poem::Result<String> {
+) -> poem::Result<Json<SetFailpointResponse>> {
     Err(poem::Error::from(anyhow::anyhow!(
         "Failpoints are not enabled at a feature level"
     )))
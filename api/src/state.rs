@@ -6,9 +6,9 @@ use crate::{
     context::api_spawn_blocking,
     failpoint::fail_point_poem,
     response::{
-        api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_forbidden, build_not_found, module_not_found, resource_not_found, storage_error,
+        table_item_not_found, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, InternalError,
     },
     ApiTags, Context,
 };
@@ -95,6 +95,9 @@ impl StateApi {
     /// Retrieves an individual module from a given account and at a specific ledger version. If the
     /// ledger version is not specified in the request, the latest ledger version is used.
     ///
+    /// With `abi_only`, the `bytecode` field is left out of the JSON response. This isn't
+    /// supported for BCS, where the module bytecode is the whole payload.
+    ///
     /// The Aptos nodes prune account state history, via a configurable time window.
     /// If the requested ledger version has been pruned, the server responds with a 410.
     #[oai(
@@ -114,6 +117,8 @@ impl StateApi {
         ///
         /// If not provided, it will be the latest version
         ledger_version: Query<Option<U64>>,
+        /// If true, only the module ABI is returned, without its bytecode
+        abi_only: Query<Option<bool>>,
     ) -> BasicResultWith404<MoveModuleBytecode> {
         verify_module_identifier(module_name.0.as_str())
             .context("'module_name' invalid")
@@ -125,7 +130,13 @@ impl StateApi {
             .check_api_output_enabled("Get account module", &accept_type)?;
         let api = self.clone();
         api_spawn_blocking(move || {
-            api.module(&accept_type, address.0, module_name.0, ledger_version.0)
+            api.module(
+                &accept_type,
+                address.0,
+                module_name.0,
+                ledger_version.0,
+                abi_only.0.unwrap_or_default(),
+            )
         })
         .await
     }
@@ -293,17 +304,21 @@ impl StateApi {
             })?;
 
         let (ledger_info, ledger_version, state_view) = self.context.state_view(ledger_version)?;
-        let bytes = state_view
-            .as_move_resolver()
-            .get_resource(&address.into(), &resource_type)
+        let bytes = self
+            .context
+            .get_resource_bytes_cached(ledger_version, address.into(), &resource_type, || {
+                state_view
+                    .as_move_resolver()
+                    .get_resource(&address.into(), &resource_type)
+            })
             .context(format!(
                 "Failed to query DB to check for {} at {}",
                 resource_type, address
             ))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::ResourceNotFound,
                     &ledger_info,
                 )
             })?
@@ -338,7 +353,7 @@ impl StateApi {
 
     /// Retrieve the module
     ///
-    /// JSON: Parse ABI and bytecode
+    /// JSON: Parse ABI and bytecode, leaving out the bytecode if `abi_only` is set
     /// BCS: Leave bytecode as is BCS encoded
     pub fn module(
         &self,
@@ -346,7 +361,15 @@ impl StateApi {
         address: Address,
         name: IdentifierWrapper,
         ledger_version: Option<U64>,
+        abi_only: bool,
     ) -> BasicResultWith404<MoveModuleBytecode> {
+        if abi_only && accept_type == &AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                "abi_only is not supported for BCS, the module bytecode is the whole payload",
+                AptosErrorCode::InvalidInput,
+            ));
+        }
+
         let module_id = ModuleId::new(address.into(), name.into());
         let access_path = AccessPath::code_access_path(module_id.clone());
         let state_key = StateKey::access_path(access_path);
@@ -357,9 +380,9 @@ impl StateApi {
             .get_state_value_bytes(&state_key)
             .context(format!("Failed to query DB to check for {:?}", state_key))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::ModuleNotFound,
                     &ledger_info,
                 )
             })?
@@ -379,6 +402,11 @@ impl StateApi {
                             &ledger_info,
                         )
                     })?;
+                let module = if abi_only {
+                    module.into_abi_only()
+                } else {
+                    module
+                };
 
                 BasicResponse::try_from_json((module, &ledger_info, BasicResponseStatus::Ok))
             },
@@ -450,9 +478,9 @@ impl StateApi {
                 key
             ))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::TableItemNotFound,
                     &ledger_info,
                 )
             })?
@@ -507,9 +535,9 @@ impl StateApi {
                 table_item_request.key,
             ))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::TableItemNotFound,
                     &ledger_info,
                 )
             })?
@@ -566,9 +594,9 @@ impl StateApi {
             .get_state_value(&state_key)
             .context(format!("Failed fetching state value. key: {}", request.key,))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::StateValueNotFound,
                     &ledger_info,
                 )
             })?
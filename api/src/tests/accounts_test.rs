@@ -4,7 +4,7 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, find_value};
-use aptos_api_types::{MoveModuleBytecode, MoveResource, StateKeyWrapper};
+use aptos_api_types::{mime_types, MoveModule, MoveModuleBytecode, MoveResource, StateKeyWrapper};
 use aptos_cached_packages::aptos_stdlib;
 use serde_json::json;
 use std::str::FromStr;
@@ -333,6 +333,67 @@ async fn test_get_account_modules_with_pagination() {
     assert_eq!(modules, all_modules[10..].to_vec());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_module_abis_with_pagination() {
+    let context = new_test_context(current_function_name!());
+    let address = "0x1";
+
+    // `abi` isn't deserialized as part of `MoveModuleBytecode`, so pull it out of the JSON
+    let all_modules = context.get(&account_modules(address)).await;
+    let all_abis: Vec<MoveModule> = all_modules
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|module| serde_json::from_value(module["abi"].clone()).unwrap())
+        .collect();
+    assert!(all_abis.len() >= 10);
+
+    // The listing has no bytecode, just the same ABIs as the full module listing
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", account_module_abis(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let abis: Vec<MoveModule> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(abis, all_abis);
+    let abis: Vec<serde_json::Value> = serde_json::from_slice(resp.body()).unwrap();
+    assert!(abis.iter().all(|abi| abi.get("bytecode").is_none()));
+
+    // Page through with the cursor
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}?limit=5", account_module_abis(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let cursor_header = resp
+        .headers()
+        .get("X-Aptos-Cursor")
+        .expect("Cursor header was missing");
+    let cursor_header = StateKeyWrapper::from_str(cursor_header.to_str().unwrap()).unwrap();
+    let abis: Vec<MoveModule> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(abis, all_abis[0..5].to_vec());
+
+    let req = warp::test::request().method("GET").path(&format!(
+        "/v1{}?limit=1000&start={}",
+        account_module_abis(address),
+        cursor_header
+    ));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let abis: Vec<MoveModule> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(abis, all_abis[5..].to_vec());
+
+    // ABIs are only served as JSON
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path(&format!("/v1{}", account_module_abis(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_items_limit_params() {
     let context = new_test_context(current_function_name!());
@@ -377,6 +438,10 @@ fn account_modules(address: &str) -> String {
     format!("/accounts/{}/modules", address)
 }
 
+fn account_module_abis(address: &str) -> String {
+    format!("/accounts/{}/modules/abis", address)
+}
+
 fn account_modules_with_ledger_version(address: &str, ledger_version: i128) -> String {
     format!(
         "{}?ledger_version={}",
@@ -0,0 +1,102 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{mime_types, AptosError};
+use aptos_config::config::NodeConfig;
+use bytes::Bytes;
+use hyper::Response;
+use std::collections::BTreeMap;
+
+fn new_test_context_with_route_limits(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.content_length_limit = Some(1_000);
+    node_config.api.route_content_length_limits = BTreeMap::from([
+        ("/v1/view".to_string(), 100),
+        ("/v1/transactions".to_string(), 10_000),
+    ]);
+    new_test_context_with_config(test_name, node_config)
+}
+
+async fn post(
+    context: &TestContext,
+    path: &str,
+    size: usize,
+    accept: Option<&str>,
+) -> Response<Bytes> {
+    let mut req = warp::test::request()
+        .method("POST")
+        .header("Content-Type", mime_types::JSON)
+        .path(&context.prepend_path(path))
+        .body(vec![b'a'; size]);
+    if let Some(accept) = accept {
+        req = req.header("Accept", accept);
+    }
+    context.reply(req).await
+}
+
+fn assert_payload_too_large_json(resp: &Response<Bytes>, limit: u64) {
+    assert_eq!(resp.status(), 413);
+    assert_eq!(resp.headers()["content-type"], mime_types::JSON);
+    let error: AptosError = serde_json::from_slice(resp.body()).unwrap();
+    assert!(
+        error.message.contains(&format!("limit of {} bytes", limit)),
+        "{}",
+        error.message
+    );
+}
+
+fn assert_payload_too_large_bcs(resp: &Response<Bytes>, limit: u64) {
+    assert_eq!(resp.status(), 413);
+    assert_eq!(resp.headers()["content-type"], mime_types::BCS);
+    let error: AptosError = bcs::from_bytes(resp.body()).unwrap();
+    assert!(
+        error.message.contains(&format!("limit of {} bytes", limit)),
+        "{}",
+        error.message
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_route_content_length_limits() {
+    let context = new_test_context_with_route_limits(current_function_name!());
+
+    // The view route has a smaller limit than the default.
+    let resp = post(&context, "/view", 200, None).await;
+    assert_payload_too_large_json(&resp, 100);
+
+    // The transactions route, and anything nested under it, has a larger one, so the request
+    // makes it through to the handler which rejects the garbage body instead.
+    let resp = post(&context, "/transactions/encode_submission", 2_000, None).await;
+    assert_eq!(resp.status(), 400);
+    let resp = post(&context, "/transactions", 20_000, None).await;
+    assert_payload_too_large_json(&resp, 10_000);
+
+    // Routes without an override fall back to the default.
+    let resp = post(&context, "/tables/0x1/item", 2_000, None).await;
+    assert_payload_too_large_json(&resp, 1_000);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_route_content_length_limits_bcs_error() {
+    let context = new_test_context_with_route_limits(current_function_name!());
+
+    let resp = post(&context, "/view", 200, Some(mime_types::BCS)).await;
+    assert_payload_too_large_bcs(&resp, 100);
+
+    let resp = post(&context, "/transactions", 20_000, Some(mime_types::BCS)).await;
+    assert_payload_too_large_bcs(&resp, 10_000);
+
+    let resp = post(&context, "/tables/0x1/item", 2_000, Some(mime_types::BCS)).await;
+    assert_payload_too_large_bcs(&resp, 1_000);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_route_content_length_limits_unsupported_accept() {
+    let context = new_test_context_with_route_limits(current_function_name!());
+
+    // The payload is rejected for its size, with a JSON error body
+    let resp = post(&context, "/view", 200, Some("text/html")).await;
+    assert_payload_too_large_json(&resp, 100);
+}
@@ -0,0 +1,114 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use crate::compression::{negotiate_encoding, Encoding};
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{mime_types, AptosError};
+use aptos_config::config::NodeConfig;
+use flate2::{read::GzDecoder, write::GzEncoder};
+use std::io::{Read, Write};
+
+fn new_test_context_with_compression(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.compression.enabled = true;
+    node_config.api.compression.max_decompressed_content_length = 100_000;
+    new_test_context_with_config(test_name, node_config)
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn test_negotiate_encoding() {
+    assert_eq!(negotiate_encoding("gzip"), Some(Encoding::Gzip));
+    assert_eq!(negotiate_encoding("gzip, zstd"), Some(Encoding::Zstd));
+    assert_eq!(negotiate_encoding("zstd;q=0.5, gzip"), Some(Encoding::Gzip));
+    assert_eq!(negotiate_encoding("br, GZIP;q=0.8"), Some(Encoding::Gzip));
+    assert_eq!(negotiate_encoding("gzip;q=0, zstd;q=0"), None);
+    assert_eq!(negotiate_encoding("br, deflate"), None);
+    assert_eq!(negotiate_encoding(""), None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_post_gzip_compressed_bcs_transaction() {
+    let mut context = new_test_context_with_compression(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = gzip(&bcs::to_bytes(&txn).unwrap());
+
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("POST")
+                .path(&context.prepend_path("/transactions"))
+                .header("Content-Type", mime_types::BCS_SIGNED_TRANSACTION)
+                .header("Content-Encoding", "gzip")
+                .body(body),
+        )
+        .await;
+    assert_eq!(resp.status(), 202, "{:?}", resp.body());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_reject_invalid_compressed_request_body() {
+    let context = new_test_context_with_compression(current_function_name!());
+    let post = |content_encoding: &'static str, body: Vec<u8>| {
+        warp::test::request()
+            .method("POST")
+            .path(&context.prepend_path("/view"))
+            .header("Content-Type", mime_types::JSON)
+            .header("Content-Encoding", content_encoding)
+            .body(body)
+    };
+
+    // A small body that inflates past the limit is rejected without being fully inflated
+    let resp = context.reply(post("gzip", gzip(&[0; 1_000_000]))).await;
+    assert_eq!(resp.status(), 413);
+    let error: AptosError = serde_json::from_slice(resp.body()).unwrap();
+    assert!(
+        error.message.contains("limit of 100000 bytes"),
+        "{}",
+        error.message
+    );
+
+    let resp = context.reply(post("gzip", b"not gzip".to_vec())).await;
+    assert_eq!(resp.status(), 400);
+
+    let resp = context.reply(post("br", b"{}".to_vec())).await;
+    assert_eq!(resp.status(), 415);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_compressed_response() {
+    let context = new_test_context_with_compression(current_function_name!());
+    let get = |accept_encoding: &'static str| {
+        warp::test::request()
+            .method("GET")
+            .path(&context.prepend_path("/accounts/0x1/modules"))
+            .header("Accept-Encoding", accept_encoding)
+    };
+
+    let resp = context.reply(get("identity")).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("content-encoding"));
+    let uncompressed = resp.body().to_vec();
+
+    let resp = context.reply(get("gzip")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-encoding"], "gzip");
+    let mut body = vec![];
+    GzDecoder::new(resp.body().as_ref())
+        .read_to_end(&mut body)
+        .unwrap();
+    assert_eq!(body, uncompressed);
+
+    let resp = context.reply(get("gzip, zstd")).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-encoding"], "zstd");
+    let body = zstd::stream::decode_all(resp.body().as_ref()).unwrap();
+    assert_eq!(body, uncompressed);
+}
@@ -0,0 +1,229 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use crate::{
+    error_converter::{classify_storage_error, StorageErrorKind},
+    response::{storage_error, AptosErrorResponse, BasicErrorWith404, ServiceUnavailableError},
+};
+use aptos_api_test_context::current_function_name;
+use aptos_api_types::{AptosError, AptosErrorCode, LedgerInfo};
+use aptos_config::config::{LedgerPrunerConfig, NodeConfig};
+use aptos_storage_interface::AptosDbError;
+use aptos_types::{
+    chain_id::ChainId,
+    state_store::{errors::StateviewError, state_key::StateKey, TStateView},
+};
+
+fn ledger_info() -> LedgerInfo {
+    LedgerInfo {
+        chain_id: ChainId::test().id(),
+        epoch: 1.into(),
+        ledger_version: 100.into(),
+        oldest_ledger_version: 10.into(),
+        ledger_timestamp: 0.into(),
+        block_height: 50.into(),
+        oldest_block_height: 5.into(),
+    }
+}
+
+fn pruned_error() -> anyhow::Error {
+    anyhow::Error::from(AptosDbError::Pruned {
+        data_type: "StateValue".to_string(),
+        requested_version: 5,
+        min_readable_version: 20,
+    })
+    .context("Failed to query DB")
+}
+
+#[test]
+fn test_classify_storage_error() {
+    assert_eq!(
+        classify_storage_error(&pruned_error()),
+        StorageErrorKind::Pruned {
+            oldest_version: Some(20)
+        }
+    );
+    let err = anyhow::Error::from(StateviewError::from(AptosDbError::Pruned {
+        data_type: "State merkle".to_string(),
+        requested_version: 5,
+        min_readable_version: 30,
+    }));
+    assert_eq!(classify_storage_error(&err), StorageErrorKind::Pruned {
+        oldest_version: Some(30)
+    });
+    let err = anyhow::Error::from(AptosDbError::MissingRootError(5));
+    assert_eq!(classify_storage_error(&err), StorageErrorKind::Pruned {
+        oldest_version: None
+    });
+
+    let err = anyhow::Error::from(AptosDbError::NotFound("Transaction 5".to_string()))
+        .context("Failed to get transaction");
+    assert_eq!(classify_storage_error(&err), StorageErrorKind::NotFound);
+    let err = anyhow::Error::from(StateviewError::NotFound("State value".to_string()));
+    assert_eq!(classify_storage_error(&err), StorageErrorKind::NotFound);
+
+    let err = anyhow::Error::from(AptosDbError::RocksDbError("IO error".to_string()));
+    assert_eq!(classify_storage_error(&err), StorageErrorKind::Other);
+}
+
+#[test]
+fn test_storage_error_statuses() {
+    let ledger_info = ledger_info();
+
+    match storage_error(
+        pruned_error(),
+        AptosErrorCode::ResourceNotFound,
+        &ledger_info,
+    ) {
+        BasicErrorWith404::Gone(error, ..) => {
+            assert!(matches!(error.error_code, AptosErrorCode::VersionPruned));
+            assert!(
+                error.message.contains("oldest available version is 20"),
+                "{}",
+                error.message
+            );
+            assert!(!error.retriable);
+        },
+        err => panic!("Expected a 410, got {:?}", err),
+    }
+
+    // Without a version from storage, the oldest ledger version is reported
+    let err = anyhow::Error::from(AptosDbError::MissingRootError(5));
+    match storage_error(err, AptosErrorCode::ResourceNotFound, &ledger_info) {
+        BasicErrorWith404::Gone(error, ..) => assert!(
+            error.message.contains("oldest available version is 10"),
+            "{}",
+            error.message
+        ),
+        err => panic!("Expected a 410, got {:?}", err),
+    }
+
+    let err = anyhow::Error::from(AptosDbError::NotFound("State value".to_string()));
+    match storage_error(err, AptosErrorCode::TableItemNotFound, &ledger_info) {
+        BasicErrorWith404::NotFound(error, ..) => {
+            assert!(matches!(
+                error.error_code,
+                AptosErrorCode::TableItemNotFound
+            ));
+            assert!(!error.retriable);
+        },
+        err => panic!("Expected a 404, got {:?}", err),
+    }
+
+    let err = anyhow::Error::from(AptosDbError::RocksDbError("IO error".to_string()));
+    match storage_error(err, AptosErrorCode::ResourceNotFound, &ledger_info) {
+        BasicErrorWith404::Internal(error, ..) => {
+            assert!(matches!(error.error_code, AptosErrorCode::InternalError));
+            assert!(!error.retriable);
+        },
+        err => panic!("Expected a 500, got {:?}", err),
+    }
+}
+
+#[test]
+fn test_service_unavailable_is_retriable() {
+    let mut err = BasicErrorWith404::service_unavailable_with_code(
+        "Node is behind",
+        AptosErrorCode::InternalError,
+        &ledger_info(),
+    );
+    assert!(err.inner_mut().retriable);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_missing_resource_is_not_found() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/accounts/0x1/resource/0x1::missing::Resource"),
+        )
+        .await;
+    assert_eq!(resp.status(), 404);
+    let error: AptosError = serde_json::from_slice(resp.body()).unwrap();
+    assert!(matches!(error.error_code, AptosErrorCode::ResourceNotFound));
+    assert!(!error.retriable);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_lagging_node_is_unavailable_with_retry_after() {
+    let context = new_test_context(current_function_name!());
+    // The ledger of the test context is still at the genesis timestamp, so it's far behind
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/-/healthy?duration_secs=1"),
+        )
+        .await;
+    assert_eq!(resp.status(), 503);
+    assert!(resp.headers().contains_key("retry-after"));
+    let error: AptosError = serde_json::from_slice(resp.body()).unwrap();
+    assert!(matches!(
+        error.error_code,
+        AptosErrorCode::HealthCheckFailed
+    ));
+    assert!(error.retriable);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pruned_state_is_gone() {
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .storage_pruner_config
+        .ledger_pruner_config = LedgerPrunerConfig {
+        enable: true,
+        prune_window: 1,
+        batch_size: 1,
+        user_pruning_window_offset: 0,
+    };
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let ledger_info = context.get_latest_ledger_info();
+    let oldest_version = ledger_info.oldest_ledger_version.0;
+    assert!(oldest_version > 0);
+
+    // Requests for pruned versions are turned away before reading storage
+    let resp = context
+        .reply(
+            warp::test::request()
+                .method("GET")
+                .path("/v1/accounts/0x1/resource/0x1::account::Account?ledger_version=0"),
+        )
+        .await;
+    assert_eq!(resp.status(), 410);
+    let error: AptosError = serde_json::from_slice(resp.body()).unwrap();
+    assert!(matches!(error.error_code, AptosErrorCode::VersionPruned));
+
+    // Storage reports the pruned versions itself when a read gets past that check, e.g. when the
+    // pruner moves on during the request
+    let err = context
+        .context
+        .state_view_at_version(0)
+        .unwrap()
+        .get_state_value_bytes(&StateKey::raw(vec![]))
+        .unwrap_err();
+    match storage_error(
+        anyhow::Error::from(err).context("Failed to query DB"),
+        AptosErrorCode::StateValueNotFound,
+        &ledger_info,
+    ) {
+        BasicErrorWith404::Gone(error, ..) => {
+            assert!(matches!(error.error_code, AptosErrorCode::VersionPruned));
+            assert!(
+                error
+                    .message
+                    .contains(&format!("oldest available version is {}", oldest_version)),
+                "{}",
+                error.message
+            );
+        },
+        err => panic!("Expected a 410, got {:?}", err),
+    }
+}
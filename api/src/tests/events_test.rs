@@ -3,10 +3,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
+use crate::context::Context;
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::mime_types;
+use aptos_config::config::NodeConfig;
+use aptos_storage_interface::{DbReader, Order, Result};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    contract_event::{ContractEvent, EventWithVersion},
+    event::EventKey,
+};
+use move_core_types::language_storage::{StructTag, TypeTag};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 use serde_json::json;
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 
 static ACCOUNT_ADDRESS: &str = "0xa550c18";
 static CREATION_NUMBER: &str = "0";
@@ -138,6 +149,336 @@ async fn test_get_events_by_invalid_account_event_handle_field_type() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_filter_by_event_type() {
+    let mut context = new_test_context(current_function_name!());
+
+    // Create an account and send it a few transfers, so it has several deposit events
+    let mut sender = context.create_account().await;
+    let receiver = context.create_account().await;
+    for amount in 1..=4 {
+        let txn = context.account_transfer_to(&mut sender, receiver.address(), amount);
+        context.commit_block(&vec![txn]).await;
+    }
+    let deposit_events = format!(
+        "/accounts/{}/events/{}/deposit_events",
+        receiver.address(),
+        utf8_percent_encode(
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            NON_ALPHANUMERIC
+        )
+    );
+
+    // Filtering by the handle's event type returns all events
+    let all_events = context.get(&deposit_events).await;
+    let all_events = all_events.as_array().unwrap();
+    assert_eq!(all_events.len(), 5);
+    let resp = context
+        .get(&format!(
+            "{}?event_type=0x1::coin::DepositEvent",
+            deposit_events
+        ))
+        .await;
+    assert_eq!(resp.as_array().unwrap(), all_events);
+
+    // Filtering by any other event type returns no events
+    let resp = context
+        .get(&format!(
+            "{}?event_type=0x1::coin::WithdrawEvent",
+            deposit_events
+        ))
+        .await;
+    assert!(resp.as_array().unwrap().is_empty());
+
+    // Pagination applies to the matching events
+    let resp = context
+        .get(&format!(
+            "{}?event_type=0x1::coin::DepositEvent&start=1&limit=2",
+            deposit_events
+        ))
+        .await;
+    assert_eq!(resp.as_array().unwrap(), &all_events[1..3]);
+    let resp = context
+        .get(&format!(
+            "{}?event_type=0x1::coin::DepositEvent&limit=2",
+            deposit_events
+        ))
+        .await;
+    assert_eq!(resp.as_array().unwrap(), &all_events[3..]);
+
+    // The same filter applies to the creation number path
+    let creation_number = all_events[0]["guid"]["creation_number"].as_str().unwrap();
+    let resp = context
+        .get(&format!(
+            "/accounts/{}/events/{}?event_type=0x1::coin::DepositEvent&start=0&limit=3",
+            receiver.address(),
+            creation_number
+        ))
+        .await;
+    assert_eq!(resp.as_array().unwrap(), &all_events[..3]);
+
+    // BCS responses honor the filter too
+    for (event_type, expected_len) in [("DepositEvent", 5), ("WithdrawEvent", 0)] {
+        let req = warp::test::request()
+            .method("GET")
+            .header("Accept", mime_types::BCS)
+            .path(&context.prepend_path(&format!(
+                "{}?event_type=0x1::coin::{}",
+                deposit_events, event_type
+            )));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let events: Vec<EventWithVersion> = bcs::from_bytes(resp.body()).unwrap();
+        assert_eq!(events.len(), expected_len);
+        assert!(events
+            .iter()
+            .all(|event| event.event.type_tag().to_string() == "0x1::coin::DepositEvent"));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_in_descending_order() {
+    let mut context = new_test_context(current_function_name!());
+
+    let mut sender = context.create_account().await;
+    let receiver = context.create_account().await;
+    for amount in 1..=4 {
+        let txn = context.account_transfer_to(&mut sender, receiver.address(), amount);
+        context.commit_block(&vec![txn]).await;
+    }
+    let deposit_events = format!(
+        "/accounts/{}/events/{}/deposit_events",
+        receiver.address(),
+        utf8_percent_encode(
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            NON_ALPHANUMERIC
+        )
+    );
+    let all_events = context.get(&format!("{}?start=0", deposit_events)).await;
+    let all_events = all_events.as_array().unwrap();
+    assert_eq!(all_events.len(), 5);
+    let newest_first = |events: &[serde_json::Value]| -> Vec<serde_json::Value> {
+        events.iter().rev().cloned().collect()
+    };
+
+    // Without a start, begins at the latest event
+    let resp = context.get(&format!("{}?order=desc", deposit_events)).await;
+    assert_eq!(resp, serde_json::Value::Array(newest_first(all_events)));
+    let resp = context
+        .get(&format!("{}?order=desc&limit=2", deposit_events))
+        .await;
+    assert_eq!(
+        resp,
+        serde_json::Value::Array(newest_first(&all_events[3..]))
+    );
+
+    // With a start, goes down from it to sequence number 0
+    let resp = context
+        .get(&format!("{}?order=desc&start=2&limit=10", deposit_events))
+        .await;
+    assert_eq!(
+        resp,
+        serde_json::Value::Array(newest_first(&all_events[..3]))
+    );
+    let resp = context
+        .get(&format!("{}?order=desc&start=0", deposit_events))
+        .await;
+    assert_eq!(
+        resp,
+        serde_json::Value::Array(newest_first(&all_events[..1]))
+    );
+
+    // Beyond the latest event there's nothing
+    let resp = context
+        .get(&format!("{}?order=desc&start=100", deposit_events))
+        .await;
+    assert!(resp.as_array().unwrap().is_empty());
+
+    // Ascending is the default, and the order applies with an event type filter too
+    let resp = context
+        .get(&format!("{}?order=asc&start=0", deposit_events))
+        .await;
+    assert_eq!(resp.as_array().unwrap(), all_events);
+    let resp = context
+        .get(&format!(
+            "{}?order=desc&event_type=0x1::coin::DepositEvent&start=3&limit=2",
+            deposit_events
+        ))
+        .await;
+    assert_eq!(
+        resp,
+        serde_json::Value::Array(newest_first(&all_events[2..4]))
+    );
+
+    context
+        .expect_status_code(400)
+        .get(&format!("{}?order=sideways", deposit_events))
+        .await;
+}
+
+/// A database holding a single event stream that interleaves two event types
+struct InterleavedEventsDb {
+    events: Vec<EventWithVersion>,
+}
+
+impl DbReader for InterleavedEventsDb {
+    fn get_events(
+        &self,
+        _event_key: &EventKey,
+        start: u64,
+        order: Order,
+        limit: u64,
+        _ledger_version: u64,
+    ) -> Result<Vec<EventWithVersion>> {
+        let events = self.events.iter().filter(|event| {
+            let seq_num = event.event.v1().unwrap().sequence_number();
+            match order {
+                Order::Ascending => seq_num >= start,
+                Order::Descending => seq_num <= start,
+            }
+        });
+        Ok(match order {
+            Order::Ascending => events.take(limit as usize).cloned().collect(),
+            Order::Descending => events.rev().take(limit as usize).cloned().collect(),
+        })
+    }
+}
+
+/// Twelve events where every third event is a listing and the rest are sales
+fn marketplace_events() -> (EventKey, TypeTag, TypeTag, Vec<EventWithVersion>) {
+    let listing_type = TypeTag::Struct(Box::new(
+        StructTag::from_str("0xabc::marketplace::ListingCreated").unwrap(),
+    ));
+    let sale_type = TypeTag::Struct(Box::new(
+        StructTag::from_str("0xabc::marketplace::ListingSold").unwrap(),
+    ));
+
+    let event_key = EventKey::new(0, AccountAddress::from_hex_literal("0xabc").unwrap());
+    let events = (0..12)
+        .map(|seq_num| {
+            let type_tag = if seq_num % 3 == 0 {
+                listing_type.clone()
+            } else {
+                sale_type.clone()
+            };
+            EventWithVersion::new(
+                seq_num,
+                ContractEvent::new_v1(event_key, seq_num, type_tag, vec![]),
+            )
+        })
+        .collect();
+    (event_key, listing_type, sale_type, events)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_of_type_with_interleaved_event_types() {
+    let (event_key, listing_type, sale_type, events) = marketplace_events();
+    let expected_listings: Vec<_> = events
+        .iter()
+        .filter(|event| event.event.type_tag() == &listing_type)
+        .cloned()
+        .collect();
+
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_events_scanned_by_type_filter = 5;
+    let context = Context::new(
+        ChainId::test(),
+        Arc::new(InterleavedEventsDb { events }),
+        futures::channel::mpsc::channel(1).0,
+        node_config,
+    );
+
+    // Each request scans at most 5 events, so the listings are returned over several pages,
+    // each ending with the cursor to continue from
+    let mut listings = vec![];
+    let mut cursors = vec![];
+    let mut start = Some(0);
+    while let Some(seq_num) = start {
+        let (page, cursor) = context
+            .get_events_of_type(&event_key, Some(seq_num), 10, 11, &listing_type, false)
+            .unwrap();
+        listings.extend(page);
+        cursors.push(cursor);
+        start = cursor;
+    }
+    assert_eq!(cursors, vec![Some(5), Some(10), None]);
+    assert_eq!(listings, expected_listings);
+
+    // The limit counts matching events, and the cursor continues right after the last one
+    let (page, cursor) = context
+        .get_events_of_type(&event_key, Some(1), 1, 11, &listing_type, false)
+        .unwrap();
+    assert_eq!(page, expected_listings[1..2]);
+    assert_eq!(cursor, Some(4));
+
+    // Descending scans start at the latest event and continue towards older ones
+    let (page, cursor) = context
+        .get_events_of_type(&event_key, None, 10, 11, &sale_type, true)
+        .unwrap();
+    let seq_nums: Vec<_> = page
+        .iter()
+        .map(|event| event.event.v1().unwrap().sequence_number())
+        .collect();
+    assert_eq!(seq_nums, vec![11, 10, 8, 7]);
+    assert_eq!(cursor, Some(6));
+
+    // Ascending requests without a start return the latest matching events, without a cursor
+    let (page, cursor) = context
+        .get_events_of_type(&event_key, None, 10, 11, &listing_type, false)
+        .unwrap();
+    assert_eq!(page, expected_listings[3..]);
+    assert_eq!(cursor, None);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_of_type_cursor_always_advances() {
+    let (event_key, listing_type, _, events) = marketplace_events();
+    let expected_listings: Vec<_> = events
+        .iter()
+        .filter(|event| event.event.type_tag() == &listing_type)
+        .cloned()
+        .collect();
+
+    // Even without a scan limit, at least one event is scanned per request
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_events_scanned_by_type_filter = 0;
+    let context = Context::new(
+        ChainId::test(),
+        Arc::new(InterleavedEventsDb { events }),
+        futures::channel::mpsc::channel(1).0,
+        node_config,
+    );
+
+    let mut listings = vec![];
+    let mut start = Some(0);
+    while let Some(seq_num) = start {
+        let (page, cursor) = context
+            .get_events_of_type(&event_key, Some(seq_num), 10, 11, &listing_type, false)
+            .unwrap();
+        if let Some(cursor) = cursor {
+            assert!(cursor > seq_num);
+        }
+        listings.extend(page);
+        start = cursor;
+    }
+    assert_eq!(listings, expected_listings);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_filter_by_invalid_event_type() {
+    let context = new_test_context(current_function_name!());
+
+    for event_type in ["0x1::coin", "0x1::coin::1Deposit", "not_a_type"] {
+        context
+            .expect_status_code(400)
+            .get(&format!(
+                "/accounts/{}/events/{}?event_type={}",
+                ACCOUNT_ADDRESS, CREATION_NUMBER, event_type
+            ))
+            .await;
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_module_events() {
     let mut context = new_test_context(current_function_name!());
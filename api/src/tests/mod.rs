@@ -4,7 +4,10 @@
 
 mod accounts_test;
 mod blocks_test;
+mod check_size_test;
+mod compression_test;
 mod converter_test;
+mod error_converter_test;
 mod events_test;
 mod index_test;
 mod invalid_post_request_test;
@@ -13,6 +16,7 @@ mod multisig_transactions_test;
 mod objects;
 mod resource_groups;
 mod secp256k1_ecdsa;
+mod set_failpoints_test;
 mod simulation_test;
 mod state_test;
 mod string_resource_test;
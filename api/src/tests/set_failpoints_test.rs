@@ -0,0 +1,127 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::set_failpoints::{parse_actions, FailpointAction};
+
+fn parse_extended(actions: &str) -> FailpointAction {
+    match parse_actions(actions) {
+        Ok((_, Some(action))) => action,
+        result => panic!("Expected extended actions, got {:?}", result),
+    }
+}
+
+fn assert_invalid(actions: &str, expected_message: &str) {
+    let error = parse_actions(actions).unwrap_err().to_string();
+    assert!(error.contains(expected_message), "{}", error);
+    assert!(
+        error.contains("Supported fields: prob, limit, action"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_parse_extended_actions() {
+    let (native, action) = parse_actions("prob=0.1,limit=100,action=return(err)").unwrap();
+    assert_eq!(native, "10%100*return(err)");
+    assert_eq!(
+        action,
+        Some(FailpointAction {
+            prob: Some(0.1),
+            limit: Some(100),
+            action: "return(err)".to_string(),
+        })
+    );
+
+    // Fields can come in any order, and only the action is required
+    let (native, _) = parse_actions(" action=sleep(500) , prob=1 ").unwrap();
+    assert_eq!(native, "100%sleep(500)");
+    let (native, _) = parse_actions("limit=1,action=panic").unwrap();
+    assert_eq!(native, "1*panic");
+    let (native, _) = parse_actions("action=off").unwrap();
+    assert_eq!(native, "off");
+
+    // Separators nested in the action's argument are kept
+    assert_eq!(
+        parse_extended("action=return(a=b,c),prob=0.5").action,
+        "return(a=b,c)"
+    );
+}
+
+#[test]
+fn test_native_actions_pass_through() {
+    for actions in [
+        "return",
+        "50%return(err)",
+        "5*sleep(100)->off",
+        "print(a=b)",
+    ] {
+        assert_eq!(
+            parse_actions(actions).unwrap(),
+            (actions.to_string(), None),
+            "{}",
+            actions
+        );
+    }
+}
+
+#[test]
+fn test_probability_bounds() {
+    assert_eq!(parse_extended("prob=1,action=return").prob, Some(1.0));
+    assert_eq!(parse_extended("prob=0.001,action=return").prob, Some(0.001));
+    for prob in ["0", "-0.5", "1.01", "10"] {
+        assert_invalid(
+            &format!("prob={},action=return", prob),
+            "prob must be in (0, 1]",
+        );
+    }
+    assert_invalid("prob=ten,action=return", "invalid prob 'ten'");
+}
+
+#[test]
+fn test_limit() {
+    assert_eq!(parse_extended("limit=3,action=return").limit, Some(3));
+    assert_invalid("limit=0,action=return", "limit must be positive");
+    assert_invalid("limit=-1,action=return", "invalid limit '-1'");
+    assert_invalid("limit=1.5,action=return", "invalid limit '1.5'");
+}
+
+#[cfg(feature = "failpoints")]
+#[test]
+fn test_limit_exhaustion() {
+    let name = "api::test_limit_exhaustion";
+    let (actions, _) = parse_actions("limit=2,action=return(err)").unwrap();
+    fail::cfg(name, &actions).unwrap();
+
+    // The action is taken `limit` times, after which the failpoint is a no-op
+    let hits: Vec<_> = (0..4)
+        .map(|_| fail::eval(name, |arg| arg.unwrap_or_default()))
+        .collect();
+    assert_eq!(hits, vec![
+        Some("err".to_string()),
+        Some("err".to_string()),
+        None,
+        None
+    ]);
+    fail::remove(name);
+}
+
+#[test]
+fn test_invalid_actions() {
+    assert_invalid("prob=0.1", "field 'action' is required");
+    assert_invalid("prob=0.1,foo=1,action=return", "unknown field 'foo'");
+    assert_invalid("prob=0.1,action", "expected <field>=<value>, got 'action'");
+    assert_invalid(
+        "prob=0.1,prob=0.2,action=return",
+        "field 'prob' is set more than once",
+    );
+    assert_invalid("action=explode", "unknown action 'explode'");
+    assert_invalid("action=return(err", "unbalanced parentheses");
+    assert_invalid("action=sleep", "'sleep' requires a duration");
+    assert_invalid("action=delay(soon)", "invalid duration 'soon'");
+    assert_invalid("action=pause(1)", "'pause' doesn't take an argument");
+    assert_invalid(
+        "limit=1,action=off",
+        "prob and limit can't be used with the 'off' action",
+    );
+}
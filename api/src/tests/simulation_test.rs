@@ -4,7 +4,12 @@
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
 use aptos_crypto::ed25519::Ed25519Signature;
+use aptos_framework::natives::code::{ModuleMetadata, MoveOption, PackageMetadata, UpgradePolicy};
 use aptos_types::transaction::authenticator::TransactionAuthenticator;
+use move_binary_format::file_format::{
+    empty_module, AddressIdentifierIndex, IdentifierIndex, ModuleHandle,
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 use serde_json::json;
 
 async fn simulate_aptos_transfer(
@@ -82,3 +87,72 @@ async fn test_simulate_transaction_with_insufficient_balance() {
     let resp = simulate_aptos_transfer(&mut context, false, LARGE_TRANSFER_AMOUNT, 200).await;
     assert!(!resp[0]["success"].as_bool().is_some_and(|v| v));
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_publish_with_module_relation_error() {
+    let mut context = new_test_context(current_function_name!());
+    let account = &mut context.gen_account();
+    let txn = context.mint_user_account(account).await;
+    context.commit_block(&vec![txn]).await;
+
+    // The compiler rejects friends at other addresses, so the module is put together by hand
+    let mut module = empty_module();
+    module.address_identifiers[0] = account.address();
+    module.identifiers[0] = Identifier::new("m").unwrap();
+    module.address_identifiers.push(AccountAddress::ONE);
+    module.identifiers.push(Identifier::new("n").unwrap());
+    module.friend_decls.push(ModuleHandle {
+        address: AddressIdentifierIndex(1),
+        name: IdentifierIndex(1),
+    });
+    let friend_id = module.module_id_for_handle(&module.friend_decls[0]);
+    let mut code = vec![];
+    module.serialize(&mut code).unwrap();
+
+    let metadata = PackageMetadata {
+        name: "pkg".to_string(),
+        upgrade_policy: UpgradePolicy::compat(),
+        upgrade_number: 0,
+        source_digest: String::new(),
+        manifest: vec![],
+        modules: vec![ModuleMetadata {
+            name: "m".to_string(),
+            source: vec![],
+            source_map: vec![],
+            extension: MoveOption::none(),
+        }],
+        deps: vec![],
+        extension: MoveOption::none(),
+    };
+
+    let resp = context
+        .simulate_transaction(
+            account,
+            json!({
+                "type": "entry_function_payload",
+                "function": "0x1::code::publish_package_txn",
+                "type_arguments": [],
+                "arguments": [
+                    format!("0x{}", hex::encode(bcs::to_bytes(&metadata).unwrap())),
+                    [format!("0x{}", hex::encode(&code))],
+                ]
+            }),
+            200,
+        )
+        .await;
+    let resp = &resp.as_array().unwrap()[0];
+
+    // The verifier's message names both modules, even though the on-chain status keeps only
+    // the status code
+    assert!(!resp["success"].as_bool().unwrap());
+    let vm_status = resp["vm_status"].as_str().unwrap();
+    assert!(
+        vm_status.contains(&format!(
+            "{} -> {}: friend declared outside of the account address",
+            module.self_id(),
+            friend_id
+        )),
+        "{}",
+        vm_status
+    );
+}
@@ -1,15 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
+use crate::metrics::{CACHE_HIT, CACHE_MISS, RESOURCE_CACHE_LOOKUPS};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::mime_types;
+use aptos_config::config::NodeConfig;
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
-use move_core_types::account_address::AccountAddress;
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
 use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::{convert::TryInto, path::PathBuf};
+use std::{convert::TryInto, path::PathBuf, str::FromStr};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource() {
@@ -102,6 +105,31 @@ async fn test_get_account_module() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_module_abi_only() {
+    let context = new_test_context(current_function_name!());
+    let full = context.get(&get_account_module("0x1", "guid")).await;
+    let resp = context
+        .get(&format!(
+            "{}?abi_only=true",
+            get_account_module("0x1", "guid")
+        ))
+        .await;
+    assert!(resp.get("bytecode").is_none());
+    assert_eq!(resp["abi"], full["abi"]);
+
+    // The bytecode is the whole BCS payload, so it can't be left out
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", mime_types::BCS)
+        .path(&context.prepend_path(&format!(
+            "{}?abi_only=true",
+            get_account_module("0x1", "guid")
+        )));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_module_by_invalid_address() {
     let mut context = new_test_context(current_function_name!());
@@ -215,6 +243,66 @@ async fn test_merkle_leaves_with_nft_transfer() {
     assert_eq!(num_leaves_after_return_nft, num_leaves_at_beginning + 1);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_hot_resource_from_cache() {
+    // Use a resource type that isn't cached by default, so other tests don't count towards it
+    let resource_type = "0x1::version::Version";
+    let mut node_config = NodeConfig::default();
+    node_config.api.resource_cache.hot_resources = vec![resource_type.to_string()];
+    // Long enough that cached resources don't expire during the test
+    node_config.api.resource_cache.ttl_ms = 60_000;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let label = StructTag::from_str(resource_type)
+        .unwrap()
+        .to_canonical_string();
+    let lookups = |result| {
+        RESOURCE_CACHE_LOOKUPS
+            .with_label_values(&[&label, result])
+            .get()
+    };
+
+    // The first read at a version goes to storage, the second one is served from the cache
+    let version = context.get_latest_ledger_info().version();
+    let resp = context
+        .get(&get_account_resource("0x1", resource_type))
+        .await;
+    assert_eq!(
+        context
+            .get(&get_account_resource("0x1", resource_type))
+            .await,
+        resp
+    );
+    assert_eq!((lookups(CACHE_HIT), lookups(CACHE_MISS)), (1, 1));
+    assert_eq!(context.context.resource_cache_version(), Some(version));
+
+    // Once the ledger version advances, the cache is invalidated
+    let mut account = context.gen_account();
+    let txn = context.create_user_account(&mut account).await;
+    context.commit_block(&vec![txn]).await;
+    let new_version = context.get_latest_ledger_info().version();
+    assert!(new_version > version);
+    context
+        .get(&get_account_resource("0x1", resource_type))
+        .await;
+    assert_eq!((lookups(CACHE_HIT), lookups(CACHE_MISS)), (1, 2));
+    assert_eq!(context.context.resource_cache_version(), Some(new_version));
+
+    // Reads at an older version bypass the cache, leaving it at the latest version
+    assert_eq!(
+        context
+            .get(&get_account_resource_with_version(
+                "0x1",
+                resource_type,
+                version
+            ))
+            .await,
+        resp
+    );
+    assert_eq!((lookups(CACHE_HIT), lookups(CACHE_MISS)), (1, 3));
+    assert_eq!(context.context.resource_cache_version(), Some(new_version));
+}
+
 #[ignore] // TODO: deactivate because of module-bundle publish not longer there; reactivate.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_table_item() {
@@ -3,20 +3,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
-use crate::tests::new_test_context_with_config;
+use crate::{
+    metrics::{CACHE_HIT, GAS_ESTIMATION_CACHE_LOOKUPS, VERSION_CACHE},
+    tests::new_test_context_with_config,
+};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::mime_types;
 use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
-    PrivateKey, SigningKey, Uniform,
+    signing_message, PrivateKey, SigningKey, Uniform,
 };
 use aptos_sdk::types::LocalAccount;
 use aptos_types::{
     account_address::AccountAddress,
     transaction::{
         authenticator::{AuthenticationKey, TransactionAuthenticator},
-        EntryFunction, Script, SignedTransaction,
+        EntryFunction, ModuleBundle, RawTransactionWithData, Script, SignedTransaction,
+        TransactionPayload,
     },
     utility_coin::APTOS_COIN_TYPE,
 };
@@ -570,6 +575,56 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_for_transaction_by_hash() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+    let txn_hash = pending_txn["hash"].as_str().unwrap().to_owned();
+
+    let waiter = context.clone();
+    let wait = tokio::spawn(async move {
+        waiter
+            .get(&format!(
+                "/transactions/by_hash/{}?wait_for_seconds=10",
+                txn_hash
+            ))
+            .await
+    });
+
+    // Give the request time to start waiting on the pending transaction.
+    sleep(Duration::from_millis(500)).await;
+    context.commit_mempool_txns(1).await;
+
+    let txn = wait.await.unwrap();
+    assert_eq!(txn["type"], "user_transaction");
+    assert_eq!(txn["hash"], pending_txn["hash"]);
+    assert_eq!(txn["success"], true);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_for_transaction_by_hash_times_out() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_wait_for_transaction_secs = 1;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    // The requested wait is capped by the config.
+    let start = std::time::Instant::now();
+    let resp = context
+        .expect_status_code(404)
+        .get("/transactions/by_hash/0xdadfeddcca7cb6396c735e9094c76c6e4e9cb3e3ef814730693aed59bd87b31d?wait_for_seconds=60")
+        .await;
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_secs(1), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(10), "{:?}", elapsed);
+    assert_eq!(resp["error_code"], "transaction_not_found");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -586,6 +641,116 @@ async fn test_signing_message_with_entry_function_payload() {
     test_signing_message_with_payload(context, txn, payload).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_signing_message_for_multi_agent_and_fee_payer() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let sender = context.root_account().await;
+    let body = json!({
+        "sender": sender.address().to_hex_literal(),
+        "sequence_number": sender.sequence_number().to_string(),
+        "gas_unit_price": txn.gas_unit_price().to_string(),
+        "max_gas_amount": txn.max_gas_amount().to_string(),
+        "expiration_timestamp_secs": txn.expiration_timestamp_secs().to_string(),
+        "payload": {
+            "type": "entry_function_payload",
+            "function": "0x1::aptos_account::create_account",
+            "type_arguments": [],
+            "arguments": [account.address().to_hex_literal()]
+        },
+    });
+    let raw_txn = txn.into_raw_transaction();
+    let secondary_signers = vec![AccountAddress::random(), AccountAddress::random()];
+    let fee_payer = AccountAddress::random();
+    let hex_addresses = |addresses: &[AccountAddress]| -> Vec<String> {
+        addresses.iter().map(|a| a.to_hex_literal()).collect()
+    };
+
+    let cases = [
+        (json!({}), raw_txn.signing_message().unwrap()),
+        (
+            json!({ "secondary_signers": hex_addresses(&secondary_signers) }),
+            signing_message(&RawTransactionWithData::new_multi_agent(
+                raw_txn.clone(),
+                secondary_signers.clone(),
+            ))
+            .unwrap(),
+        ),
+        (
+            json!({ "fee_payer": fee_payer.to_hex_literal() }),
+            signing_message(&RawTransactionWithData::new_fee_payer(
+                raw_txn.clone(),
+                vec![],
+                fee_payer,
+            ))
+            .unwrap(),
+        ),
+        (
+            json!({
+                "secondary_signers": hex_addresses(&secondary_signers),
+                "fee_payer": fee_payer.to_hex_literal(),
+            }),
+            signing_message(&RawTransactionWithData::new_fee_payer(
+                raw_txn.clone(),
+                secondary_signers.clone(),
+                fee_payer,
+            ))
+            .unwrap(),
+        ),
+    ];
+    for (signers, expected) in cases {
+        let mut request = body.clone();
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(signers.as_object().unwrap().clone());
+        let resp = context
+            .post("/transactions/encode_submission", request.clone())
+            .await;
+        let signing_msg = context
+            .api_specific_config
+            .unwrap_signing_message_response(resp);
+        assert_eq!(signing_msg.inner(), expected.as_slice(), "{}", request);
+
+        // BCS responses carry the same signing message
+        let req = warp::test::request()
+            .method("POST")
+            .header("Accept", mime_types::BCS)
+            .path(&context.prepend_path("/transactions/encode_submission"))
+            .json(&request);
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let signing_msg: Vec<u8> = bcs::from_bytes(resp.body()).unwrap();
+        assert_eq!(signing_msg, expected, "{}", request);
+    }
+
+    // Secondary signers must be distinct, and exclude the sender
+    for secondary_signers in [vec![secondary_signers[0], secondary_signers[0]], vec![
+        secondary_signers[0],
+        sender.address(),
+    ]] {
+        let mut request = body.clone();
+        request["secondary_signers"] = json!(hex_addresses(&secondary_signers));
+        request["fee_payer"] = json!(fee_payer.to_hex_literal());
+        context
+            .expect_status_code(400)
+            .post("/transactions/encode_submission", request)
+            .await;
+    }
+
+    // The fee payer must be neither the sender nor a secondary signer
+    for fee_payer in [sender.address(), secondary_signers[1]] {
+        let mut request = body.clone();
+        request["secondary_signers"] = json!(hex_addresses(&secondary_signers));
+        request["fee_payer"] = json!(fee_payer.to_hex_literal());
+        context
+            .expect_status_code(400)
+            .post("/transactions/encode_submission", request)
+            .await;
+    }
+}
+
 async fn test_signing_message_with_payload(
     mut context: TestContext,
     txn: SignedTransaction,
@@ -1199,6 +1364,117 @@ async fn test_gas_estimation_empty() {
     context.check_golden_output(resp);
 }
 
+async fn submit_payload_types(context: &mut TestContext) -> Vec<(u16, String)> {
+    let mut root_account = context.root_account().await;
+    let receiver = context.gen_account();
+    let factory = context.transaction_factory();
+    let txns = vec![
+        context.account_transfer(&mut root_account, &receiver, 1),
+        root_account.sign_with_transaction_builder(
+            factory
+                .script(Script::new(
+                    hex::decode("a11ceb0b030000").unwrap(),
+                    vec![],
+                    vec![],
+                ))
+                .expiration_timestamp_secs(u64::MAX),
+        ),
+        root_account.sign_with_transaction_builder(
+            factory
+                .payload(TransactionPayload::ModuleBundle(ModuleBundle::new(vec![])))
+                .expiration_timestamp_secs(u64::MAX),
+        ),
+    ];
+
+    let mut results = vec![];
+    for txn in txns {
+        let req = warp::test::request()
+            .method("POST")
+            .path(&context.prepend_path("/transactions"))
+            .header("content-type", mime_types::BCS_SIGNED_TRANSACTION)
+            .body(bcs::to_bytes(&txn).unwrap());
+        let resp = context.reply(req).await;
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        let message = body["message"].as_str().unwrap_or_default().to_string();
+        results.push((resp.status().as_u16(), message));
+    }
+    results
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_transaction_all_payload_types_allowed_by_default() {
+    let mut context = new_test_context(current_function_name!());
+    let results = submit_payload_types(&mut context).await;
+
+    // Entry function and script payloads make it into mempool.
+    assert_eq!(results[0].0, 202);
+    assert_eq!(results[1].0, 202);
+    // Module bundles are deprecated and rejected by the VM, but not by the API.
+    for (_, message) in &results {
+        assert!(!message.contains("only entry function payloads are allowed"));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_transaction_entry_function_payloads_only() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.entry_function_payloads_only = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let results = submit_payload_types(&mut context).await;
+
+    assert_eq!(results[0].0, 202);
+    assert_eq!(results[1].0, 400);
+    assert_eq!(
+        results[1].1,
+        "Script payloads are not accepted by this node, only entry function payloads are allowed"
+    );
+    assert_eq!(results[2].0, 400);
+    assert_eq!(
+        results[2].1,
+        "ModuleBundle payloads are not accepted by this node, only entry function payloads are allowed"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_batch_entry_function_payloads_only() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.entry_function_payloads_only = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let mut root_account = context.root_account().await;
+    let receiver = context.gen_account();
+    let factory = context.transaction_factory();
+    let txns = vec![
+        root_account.sign_with_transaction_builder(
+            factory
+                .script(Script::new(
+                    hex::decode("a11ceb0b030000").unwrap(),
+                    vec![],
+                    vec![],
+                ))
+                .expiration_timestamp_secs(u64::MAX),
+        ),
+        context.account_transfer(&mut root_account, &receiver, 1),
+    ];
+
+    let req = warp::test::request()
+        .method("POST")
+        .path(&context.prepend_path("/transactions/batch"))
+        .header("content-type", mime_types::BCS_SIGNED_TRANSACTION)
+        .body(bcs::to_bytes(&txns).unwrap());
+    let resp = context.reply(req).await;
+
+    // The disallowed payload fails on its own, the rest of the batch is still submitted.
+    assert_eq!(resp.status().as_u16(), 206);
+    let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+    let failures = body["transaction_failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0]["transaction_index"], 0);
+    assert_eq!(
+        failures[0]["error"]["message"],
+        "Script payloads are not accepted by this node, only entry function payloads are allowed"
+    );
+}
+
 async fn fill_block(
     block: &mut Vec<SignedTransaction>,
     ctx: &mut TestContext,
@@ -1318,6 +1594,43 @@ async fn test_gas_estimation_cache() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_gas_estimation_version_cache() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.gas_estimation.enabled = true;
+    // Long enough that cached estimations don't expire during the test
+    node_config.api.resource_cache.ttl_ms = 60_000;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let version_cache_hits = || {
+        GAS_ESTIMATION_CACHE_LOOKUPS
+            .with_label_values(&[VERSION_CACHE, CACHE_HIT])
+            .get()
+    };
+
+    context.commit_block(&[]).await;
+    let version = context.get_latest_ledger_info().version();
+    let resp = context.get("/estimate_gas_price").await;
+    assert_eq!(
+        context.context.gas_estimation_cache_version(),
+        Some(version)
+    );
+
+    // A repeated request at the same version is served from the cache
+    let hits = version_cache_hits();
+    assert_eq!(context.get("/estimate_gas_price").await, resp);
+    assert!(version_cache_hits() > hits);
+
+    // Once the ledger version advances, the cache moves on to the new version
+    context.commit_block(&[]).await;
+    let new_version = context.get_latest_ledger_info().version();
+    assert!(new_version > version);
+    context.get("/estimate_gas_price").await;
+    assert_eq!(
+        context.context.gas_estimation_cache_version(),
+        Some(new_version)
+    );
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_gas_estimation_disabled() {
     let mut node_config = NodeConfig::default();
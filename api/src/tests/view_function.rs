@@ -2,8 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
-use aptos_api_test_context::current_function_name;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{mime_types, ViewFunction};
 use aptos_cached_packages::aptos_stdlib;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{EntryFunction, TransactionPayload},
+};
+use move_core_types::{ident_str, language_storage::ModuleId};
 use serde_json::json;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -113,3 +119,130 @@ async fn test_view_tuple() {
         .await;
     context.check_golden_output_no_prune(resp);
 }
+
+fn test_module_id() -> ModuleId {
+    ModuleId::new(
+        AccountAddress::from_hex_literal("0xa550c18").unwrap(),
+        ident_str!("test_module").to_owned(),
+    )
+}
+
+async fn view_add_json(
+    context: &TestContext,
+    ledger_version: Option<u64>,
+    addr: AccountAddress,
+    amount: u128,
+) -> u128 {
+    let path = match ledger_version {
+        Some(version) => format!("/view?ledger_version={}", version),
+        None => "/view".to_string(),
+    };
+    let resp = context
+        .post(
+            &path,
+            json!({
+                "function": "0xa550c18::test_module::add",
+                "arguments": vec![addr.to_string(), amount.to_string()],
+                "type_arguments": [],
+            }),
+        )
+        .await;
+    resp[0].as_str().unwrap().parse().unwrap()
+}
+
+async fn view_add_bcs(
+    context: &TestContext,
+    ledger_version: Option<u64>,
+    addr: AccountAddress,
+    amount: u128,
+) -> u128 {
+    let path = match ledger_version {
+        Some(version) => format!("/view?ledger_version={}", version),
+        None => "/view".to_string(),
+    };
+    let request = ViewFunction {
+        module: test_module_id(),
+        function: ident_str!("add").to_owned(),
+        ty_args: vec![],
+        args: vec![
+            bcs::to_bytes(&addr).unwrap(),
+            bcs::to_bytes(&amount).unwrap(),
+        ],
+    };
+    let req = warp::test::request()
+        .method("POST")
+        .header("Content-Type", mime_types::BCS)
+        .header("Accept", mime_types::BCS)
+        .path(&context.prepend_path(&path))
+        .body(bcs::to_bytes(&request).unwrap());
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let return_vals: Vec<u128> = bcs::from_bytes(resp.body()).unwrap();
+    assert_eq!(return_vals.len(), 1);
+    return_vals[0]
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_u128_with_json_and_bcs_at_versions() {
+    let mut context = new_test_context(current_function_name!());
+    let payload = aptos_stdlib::publish_module_source(
+        "test_module",
+        r#"
+        module 0xa550c18::test_module {
+            struct Value has key { value: u128 }
+
+            public entry fun set(account: &signer, value: u128) acquires Value {
+                let addr = std::signer::address_of(account);
+                if (exists<Value>(addr)) {
+                    borrow_global_mut<Value>(addr).value = value;
+                } else {
+                    move_to(account, Value { value });
+                }
+            }
+
+            #[view]
+            public fun add(addr: address, amount: u128): u128 acquires Value {
+                borrow_global<Value>(addr).value + amount
+            }
+        }
+        "#,
+    );
+
+    let root_account = context.root_account().await;
+    let module_txn =
+        root_account.sign_with_transaction_builder(context.transaction_factory().payload(payload));
+    context.commit_block(&vec![module_txn]).await;
+
+    // Store a value, remember the version, then overwrite it
+    let mut versions = vec![];
+    for value in [1u128, 2] {
+        let set_payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            test_module_id(),
+            ident_str!("set").to_owned(),
+            vec![],
+            vec![bcs::to_bytes(&value).unwrap()],
+        ));
+        let txn = root_account
+            .sign_with_transaction_builder(context.transaction_factory().payload(set_payload));
+        context.commit_block(&vec![txn]).await;
+        versions.push(context.get_latest_ledger_info().version());
+    }
+
+    // An amount that JSON numbers couldn't represent exactly
+    let amount = u128::MAX - 10;
+    let addr = root_account.address();
+    for (ledger_version, expected) in [
+        (Some(versions[0]), amount + 1),
+        (Some(versions[1]), amount + 2),
+        (None, amount + 2),
+    ] {
+        assert_eq!(
+            view_add_json(&context, ledger_version, addr, amount).await,
+            expected
+        );
+        assert_eq!(
+            view_add_bcs(&context, ledger_version, addr, amount).await,
+            expected
+        );
+    }
+}
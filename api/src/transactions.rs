@@ -11,7 +11,7 @@ use crate::{
     generate_error_response, generate_success_response, metrics,
     page::Page,
     response::{
-        api_disabled, api_forbidden, transaction_not_found_by_hash,
+        api_disabled, api_forbidden, storage_error, transaction_not_found_by_hash,
         transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
         BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
         InsufficientStorageError, InternalError,
@@ -44,7 +44,8 @@ use poem_openapi::{
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
+use tokio::time::Instant;
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -188,6 +189,11 @@ impl TransactionsApi {
     ///   1. Hash message bytes: "RawTransaction" bytes + BCS bytes of [Transaction](https://aptos-labs.github.io/aptos-core/aptos_types/transaction/enum.Transaction.html).
     ///   2. Apply hash algorithm `SHA3-256` to the hash message bytes.
     ///   3. Hex-encode the hash bytes with `0x` prefix.
+    ///
+    /// If `wait_for_seconds` is given and the transaction isn't committed yet, the server
+    /// holds the request until the transaction is committed or the wait runs out, instead of
+    /// the client having to poll. If the wait runs out, the response is the same as without
+    /// waiting.
     // TODO: Include a link to an example of how to do this ^
     #[oai(
         path = "/transactions/by_hash/:txn_hash",
@@ -200,12 +206,15 @@ impl TransactionsApi {
         accept_type: AcceptType,
         /// Hash of transaction to retrieve
         txn_hash: Path<HashValue>,
+        /// Seconds to wait for the transaction to be committed, capped by the node's
+        /// configured maximum. Defaults to not waiting.
+        wait_for_seconds: Query<Option<u64>>,
         // TODO: Use a new request type that can't return 507.
     ) -> BasicResultWith404<Transaction> {
         fail_point_poem("endpoint_transaction_by_hash")?;
         self.context
             .check_api_output_enabled("Get transactions by hash", &accept_type)?;
-        self.get_transaction_by_hash_inner(&accept_type, txn_hash.0)
+        self.get_transaction_by_hash_inner(&accept_type, txn_hash.0, wait_for_seconds.0)
             .await
     }
 
@@ -321,6 +330,10 @@ impl TransactionsApi {
             .check_api_output_enabled("Submit transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
         let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        self.check_payload_allowed(&signed_transaction)
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_from_aptos_error(err, &ledger_info)
+            })?;
         self.create(&accept_type, &ledger_info, signed_transaction)
             .await
     }
@@ -518,9 +531,9 @@ impl TransactionsApi {
     /// Encode submission
     ///
     /// This endpoint accepts an EncodeSubmissionRequest, which internally is a
-    /// UserTransactionRequestInner (and optionally secondary signers) encoded
-    /// as JSON, validates the request format, and then returns that request
-    /// encoded in BCS. The client can then use this to create a transaction
+    /// UserTransactionRequestInner (and optionally secondary signers and a fee
+    /// payer) encoded as JSON, validates the request format, and then returns
+    /// that request encoded in BCS. The client can then use this to create a transaction
     /// signature to be used in a SubmitTransactionRequest, which it then
     /// passes to the /transactions POST endpoint.
     ///
@@ -533,6 +546,8 @@ impl TransactionsApi {
     /// - Decode the hex encoded string in the response to bytes.
     /// - Sign the bytes to create the signature.
     /// - Use that as the signature field in something like Ed25519Signature, which you then use to build a TransactionSignature.
+    ///
+    /// With a BCS accept type, the signing message is returned as BCS encoded bytes instead.
     //
     #[oai(
         path = "/transactions/encode_submission",
@@ -678,23 +693,12 @@ impl TransactionsApi {
         &self,
         accept_type: &AcceptType,
         hash: HashValue,
+        wait_for_seconds: Option<u64>,
     ) -> BasicResultWith404<Transaction> {
-        let context = self.context.clone();
         let accept_type = accept_type.clone();
 
-        let ledger_info = api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
-
-        let txn_data = self
-            .get_by_hash(hash.into(), &ledger_info)
-            .await
-            .context(format!("Failed to get transaction by hash {}", hash))
-            .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
-                    err,
-                    AptosErrorCode::InternalError,
-                    &ledger_info,
-                )
-            })?
+        let (ledger_info, txn_data) = self.wait_for_by_hash(hash, wait_for_seconds).await?;
+        let txn_data = txn_data
             .context(format!("Failed to find transaction with hash: {}", hash))
             .map_err(|_| transaction_not_found_by_hash(hash, &ledger_info))?;
 
@@ -713,9 +717,9 @@ impl TransactionsApi {
             .get_by_version(version.0, &ledger_info)
             .context(format!("Failed to get transaction by version {}", version))
             .map_err(|err| {
-                BasicErrorWith404::internal_with_code(
+                storage_error::<BasicErrorWith404>(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::TransactionNotFound,
                     &ledger_info,
                 )
             })?;
@@ -800,6 +804,55 @@ impl TransactionsApi {
         ))
     }
 
+    /// Retrieves a transaction by hash, like `get_by_hash`. If the transaction isn't committed,
+    /// this checks again whenever a new version is committed, until the transaction is found or
+    /// `wait_for_seconds` (capped by the config) runs out, and returns the last result along
+    /// with the ledger info it was read at.
+    ///
+    /// Waiting requests are limited separately, so once the limit is reached lookups return
+    /// right away.
+    async fn wait_for_by_hash(
+        &self,
+        hash: HashValue,
+        wait_for_seconds: Option<u64>,
+    ) -> Result<(LedgerInfo, Option<TransactionData>), BasicErrorWith404> {
+        let wait = Duration::from_secs(wait_for_seconds.unwrap_or(0))
+            .min(self.context.max_wait_for_transaction());
+        let deadline = Instant::now() + wait;
+        let wait_guard = if wait.is_zero() {
+            None
+        } else {
+            self.context.try_reserve_wait_for_transaction()
+        };
+        // Subscribe before the first lookup, so commits made while it runs aren't missed
+        let mut committed_version = self.context.subscribe_to_committed_version();
+
+        loop {
+            let context = self.context.clone();
+            let ledger_info = api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
+            let txn_data = self
+                .get_by_hash(hash.into(), &ledger_info)
+                .await
+                .context(format!("Failed to get transaction by hash {}", hash))
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+
+            if wait_guard.is_none() || matches!(txn_data, Some(TransactionData::OnChain(_))) {
+                return Ok((ledger_info, txn_data));
+            }
+            // Nothing can change until a new version is committed, so only look again then
+            match tokio::time::timeout_at(deadline, committed_version.changed()).await {
+                Ok(Ok(())) => {},
+                Ok(Err(_)) | Err(_) => return Ok((ledger_info, txn_data)),
+            }
+        }
+    }
+
     /// Retrieves a transaction by hash. First the node tries to find the transaction
     /// in the DB. If the transaction is found there, it means the transaction is
     /// committed. If it is not found there, it looks in mempool. If it is found there,
@@ -944,6 +997,28 @@ impl TransactionsApi {
         }
     }
 
+    /// Rejects non entry function payloads if the node is configured to only accept those
+    fn check_payload_allowed(
+        &self,
+        signed_transaction: &SignedTransaction,
+    ) -> Result<(), AptosError> {
+        if !self.context.entry_function_payloads_only() {
+            return Ok(());
+        }
+        let payload_type = match signed_transaction.payload() {
+            TransactionPayload::EntryFunction(_) | TransactionPayload::Multisig(_) => return Ok(()),
+            TransactionPayload::Script(_) => "Script",
+            TransactionPayload::ModuleBundle(_) => "ModuleBundle",
+        };
+        Err(AptosError::new_with_error_code(
+            format!(
+                "{} payloads are not accepted by this node, only entry function payloads are allowed",
+                payload_type
+            ),
+            AptosErrorCode::InvalidInput,
+        ))
+    }
+
     // Validates that the module, function, and args in EntryFunction payload are correctly
     // formatted.
     fn validate_entry_function_payload_format(
@@ -1158,7 +1233,11 @@ impl TransactionsApi {
         // Iterate through transactions keeping track of failures
         let mut txn_failures = Vec::new();
         for (idx, txn) in txns.iter().enumerate() {
-            if let Err(error) = self.create_internal(txn.clone()).await {
+            let result = match self.check_payload_allowed(txn) {
+                Ok(()) => self.create_internal(txn.clone()).await,
+                Err(error) => Err(error),
+            };
+            if let Err(error) = result {
                 txn_failures.push(TransactionsBatchSingleSubmissionFailure {
                     error,
                     transaction_index: idx,
@@ -1295,14 +1374,6 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         request: EncodeSubmissionRequest,
     ) -> BasicResult<HexEncodedBytes> {
-        // We don't want to encourage people to use this API if they can sign the request directly
-        if accept_type == &AcceptType::Bcs {
-            return Err(BasicError::bad_request_with_code_no_info(
-                "BCS is not supported for encode submission",
-                AptosErrorCode::BcsNotSupported,
-            ));
-        }
-
         let ledger_info = self.context.get_latest_ledger_info()?;
         let state_view = self.context.latest_state_view_poem(&ledger_info)?;
         let resolver = state_view.as_move_resolver();
@@ -1314,37 +1385,37 @@ impl TransactionsApi {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?;
 
-        let raw_message = match request.secondary_signers {
-            Some(secondary_signer_addresses) => signing_message(
-                &RawTransactionWithData::new_multi_agent(
+        let secondary_signers: Option<Vec<_>> = request
+            .secondary_signers
+            .map(|signers| signers.into_iter().map(|v| v.into()).collect());
+        let raw_message = match (secondary_signers, request.fee_payer) {
+            (secondary_signers, Some(fee_payer)) => {
+                signing_message(&RawTransactionWithData::new_fee_payer(
                     raw_txn,
-                    secondary_signer_addresses
-                        .into_iter()
-                        .map(|v| v.into())
-                        .collect(),
-                ),
-            )
-            .context("Invalid transaction to generate signing message")
-            .map_err(|err| {
-                BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
-            })?,
-            None => raw_txn
-                .signing_message()
-                .context("Invalid transaction to generate signing message")
-                .map_err(|err| {
-                    BasicError::bad_request_with_code(
-                        err,
-                        AptosErrorCode::InvalidInput,
-                        &ledger_info,
-                    )
-                })?,
-        };
+                    secondary_signers.unwrap_or_default(),
+                    fee_payer.into(),
+                ))
+            },
+            (Some(secondary_signers), None) => signing_message(
+                &RawTransactionWithData::new_multi_agent(raw_txn, secondary_signers),
+            ),
+            (None, None) => raw_txn.signing_message(),
+        }
+        .context("Invalid transaction to generate signing message")
+        .map_err(|err| {
+            BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
+        })?;
 
-        BasicResponse::try_from_json((
-            HexEncodedBytes::from(raw_message),
-            &ledger_info,
-            BasicResponseStatus::Ok,
-        ))
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                HexEncodedBytes::from(raw_message),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((raw_message, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
     }
 }
 
@@ -37,6 +37,10 @@ pub enum ViewFunctionRequest {
 
     #[oai(content_type = "application/x.aptos.view_function+bcs")]
     Bcs(Bcs),
+
+    /// Same as `Bcs`, for clients that send the generic BCS content type
+    #[oai(content_type = "application/x-bcs")]
+    GenericBcs(Bcs),
 }
 
 #[OpenApi]
@@ -107,7 +111,7 @@ fn view_request(
                     )
                 })?
         },
-        ViewFunctionRequest::Bcs(data) => {
+        ViewFunctionRequest::Bcs(data) | ViewFunctionRequest::GenericBcs(data) => {
             bcs::from_bytes_with_limit(data.0.as_slice(), MAX_RECURSIVE_TYPES_ALLOWED as usize)
                 .context("Failed to deserialize input into ViewRequest")
                 .map_err(|err| {
@@ -11,7 +11,7 @@ use aptos_cached_packages::aptos_stdlib;
 use aptos_config::{
     config::{
         NodeConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
     },
     keys::ConfigKey,
 };
@@ -124,8 +124,8 @@ pub fn new_test_context(
         DbReaderWriter::wrap(
             AptosDB::open(
                 StorageDirPaths::from_path(&tmp_dir),
-                false,                       /* readonly */
-                NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+                false, /* readonly */
+                node_config.storage.storage_pruner_config,
                 RocksdbConfigs::default(),
                 false, /* indexer */
                 BUFFERED_STATE_TARGET_ITEMS,
@@ -638,7 +638,7 @@ impl TestContext {
 
         self.mempool
             .mempool_notifier
-            .notify_new_commit(txns, timestamp)
+            .notify_new_commit(txns, timestamp, None)
             .await
             .unwrap();
     }
@@ -16,6 +16,9 @@ pub struct AptosError {
     pub error_code: AptosErrorCode,
     /// A code providing VM error details when submitting transactions to the VM
     pub vm_error_code: Option<u64>,
+    /// Whether the same request may succeed if retried later, e.g. once the node has caught up
+    #[serde(default)]
+    pub retriable: bool,
 }
 
 impl std::fmt::Display for AptosError {
@@ -35,6 +38,7 @@ impl AptosError {
             message: format!("{:#}", error),
             error_code,
             vm_error_code: None,
+            retriable: error_code.is_retriable(),
         }
     }
 
@@ -47,6 +51,7 @@ impl AptosError {
             message: format!("{:#}", error),
             error_code,
             vm_error_code: Some(vm_error_code as u64),
+            retriable: error_code.is_retriable(),
         }
     }
 }
@@ -116,6 +121,14 @@ impl AptosErrorCode {
     pub fn as_u32(&self) -> u32 {
         *self as u32
     }
+
+    /// Whether errors with this code are transient, so the same request may succeed later
+    pub fn is_retriable(&self) -> bool {
+        matches!(
+            self,
+            AptosErrorCode::HealthCheckFailed | AptosErrorCode::MempoolIsFull
+        )
+    }
 }
 
 #[test]
@@ -129,7 +129,7 @@ define_integer_type!(U128, u128, "A string encoded U128.");
 define_integer_type!(U256, move_core_types::u256::U256, "A string encoded U256.");
 
 /// Hex encoded bytes to allow for having bytes represented in JSON
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct HexEncodedBytes(pub Vec<u8>);
 
 impl HexEncodedBytes {
@@ -1047,13 +1047,17 @@ impl From<&AbilitySet> for MoveFunctionGenericTypeParam {
 /// Move module bytecode along with it's ABI
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct MoveModuleBytecode {
+    // Left out when only the ABI is requested
+    #[serde(default, skip_serializing_if = "is_empty_bytecode")]
+    #[oai(default, skip_serializing_if = "is_empty_bytecode")]
     pub bytecode: HexEncodedBytes,
-    // We don't need deserialize MoveModule as it should be serialized
-    // from `bytecode`.
-    #[serde(skip_deserializing)]
     pub abi: Option<MoveModule>,
 }
 
+fn is_empty_bytecode(bytecode: &HexEncodedBytes) -> bool {
+    bytecode.0.is_empty()
+}
+
 impl VerifyInput for MoveModuleBytecode {
     fn verify(&self) -> anyhow::Result<()> {
         if self.bytecode.is_empty() {
@@ -1083,6 +1087,12 @@ impl MoveModuleBytecode {
         }
         Ok(self)
     }
+
+    /// Drops the bytecode, keeping only the parsed ABI
+    pub fn into_abi_only(mut self) -> Self {
+        self.bytecode = HexEncodedBytes(vec![]);
+        self
+    }
 }
 
 impl From<Module> for MoveModuleBytecode {
@@ -1217,7 +1227,7 @@ pub fn verify_identifier(identifier: &str) -> anyhow::Result<()> {
 mod tests {
     use super::*;
     use aptos_types::account_address::AccountAddress;
-    use move_binary_format::file_format::AbilitySet;
+    use move_binary_format::file_format::{empty_module, AbilitySet};
     use move_core_types::{
         identifier::Identifier,
         language_storage::{StructTag, TypeTag},
@@ -1339,6 +1349,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_deserialize_abi_only_move_module_bytecode() {
+        let mut bytes = vec![];
+        empty_module().serialize(&mut bytes).unwrap();
+        let module = MoveModuleBytecode::new(bytes)
+            .try_parse_abi()
+            .unwrap()
+            .into_abi_only();
+
+        let val = to_value(&module).unwrap();
+        assert!(val.get("bytecode").is_none());
+        let data: MoveModuleBytecode = serde_json::from_value(val).unwrap();
+        assert_eq!(data, module);
+    }
+
     #[test]
     fn test_parse_invalid_move_module_id_string() {
         assert_eq!(
@@ -28,10 +28,11 @@ use aptos_types::{
     },
 };
 use once_cell::sync::Lazy;
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
+    collections::HashSet,
     convert::{From, Into, TryFrom, TryInto},
     fmt,
     str::FromStr,
@@ -499,11 +500,33 @@ pub struct EncodeSubmissionRequest {
     /// Secondary signer accounts of the request for Multi-agent
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary_signers: Option<Vec<Address>>,
+    /// Fee payer account of the request for a fee payer transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<Address>,
 }
 
 impl VerifyInput for EncodeSubmissionRequest {
     fn verify(&self) -> anyhow::Result<()> {
-        self.transaction.verify()
+        self.transaction.verify()?;
+
+        let mut signers = HashSet::new();
+        for signer in self.secondary_signers.iter().flatten() {
+            if signer == &self.transaction.sender {
+                bail!("Secondary signers must not include the sender {}", signer)
+            }
+            if !signers.insert(signer) {
+                bail!("Secondary signer {} is listed more than once", signer)
+            }
+        }
+        if let Some(fee_payer) = &self.fee_payer {
+            if fee_payer == &self.transaction.sender {
+                bail!("The fee payer must not be the sender {}", fee_payer)
+            }
+            if signers.contains(fee_payer) {
+                bail!("The fee payer {} must not be a secondary signer", fee_payer)
+            }
+        }
+        Ok(())
     }
 }
 
@@ -584,6 +607,17 @@ impl From<(&ContractEvent, serde_json::Value)> for Event {
     }
 }
 
+/// Order in which events are returned
+#[derive(Clone, Copy, Debug, Deserialize, Enum, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum EventOrder {
+    /// Oldest events first
+    Asc,
+    /// Newest events first
+    Desc,
+}
+
 /// An event from a transaction with a version
 #[derive(Clone, Debug, Deserialize, Eq, Object, PartialEq, Serialize)]
 pub struct VersionedEvent {
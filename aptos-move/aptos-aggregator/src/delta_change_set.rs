@@ -227,6 +227,7 @@ mod test {
     use move_binary_format::errors::{PartialVMError, PartialVMResult};
     use move_core_types::{value::MoveTypeLayout, vm_status::StatusCode};
     use once_cell::sync::Lazy;
+    use proptest::{collection::vec, prelude::*};
     use std::{
         collections::{BTreeMap, HashSet},
         sync::Arc,
@@ -473,6 +474,59 @@ mod test {
         assert_eq!(b.update, Negative(1));
     }
 
+    #[test]
+    fn test_merged_delta_fails_on_intermediate_overflow() {
+        // +20 then -20 is a no-op overall, but +20 has to succeed first.
+        let merged = assert_ok!(DeltaOp::create_merged_delta(
+            &delta_add(20, 100),
+            &delta_sub(20, 100)
+        ));
+        assert_eq!(merged.update, SignedU128::Positive(0));
+        assert_ok_eq!(merged.apply_to(80), 80);
+        assert_err!(merged.apply_to(81));
+
+        // Similarly, -20 then +20 has to succeed at subtracting first.
+        let merged = assert_ok!(DeltaOp::create_merged_delta(
+            &delta_sub(20, 100),
+            &delta_add(20, 100)
+        ));
+        assert_ok_eq!(merged.apply_to(20), 20);
+        assert_err!(merged.apply_to(19));
+    }
+
+    fn arb_delta(max_value: u128) -> impl Strategy<Value = DeltaOp> {
+        prop_oneof![
+            (0..=max_value).prop_map(move |v| delta_add(v, max_value)),
+            (0..=max_value).prop_map(move |v| delta_sub(v, max_value)),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_merge_then_apply_matches_sequential_apply(
+            (max_value, base, deltas) in (1..1000u128).prop_flat_map(|max_value| (
+                Just(max_value),
+                0..=max_value,
+                vec(arb_delta(max_value), 1..10),
+            )),
+        ) {
+            // Apply the deltas one by one, failing at the first step that fails.
+            let sequential = deltas
+                .iter()
+                .try_fold(base, |value, delta| delta.apply_to(value).ok());
+
+            // Merge all the deltas first, and then apply them at once. Merging fails
+            // only if no base value could make all the steps succeed.
+            let merged = deltas[1..].iter().try_fold(deltas[0], |merged, delta| {
+                DeltaOp::create_merged_delta(&merged, delta)
+            });
+            match merged {
+                Ok(merged) => prop_assert_eq!(merged.apply_to(base).ok(), sequential),
+                Err(_) => prop_assert_eq!(sequential, None),
+            }
+        }
+    }
+
     static KEY: Lazy<StateKey> = Lazy::new(|| StateKey::raw(String::from("test-key").into_bytes()));
 
     #[test]
@@ -9,8 +9,8 @@ use crate::{
     errors::{discarded_output, expect_only_successful_execution},
     gas::{check_gas, get_gas_parameters},
     move_vm_ext::{
-        get_max_binary_format_version, AptosMoveResolver, MoveVmExt, RespawnedSession, SessionExt,
-        SessionId,
+        get_max_binary_format_version, AccessSet, AptosMoveResolver, MoveVmExt,
+        ReadSetTrackingResolver, RespawnedSession, SessionExt, SessionId,
     },
     sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor},
     system_module_names::*,
@@ -444,7 +444,7 @@ impl AptosVM {
                 (error_code, txn_output)
             },
             TransactionStatus::Discard(status_code) => (
-                VMStatus::error(status_code, None),
+                VMStatus::error(status_code, error_code.message().cloned()),
                 discarded_output(status_code),
             ),
             TransactionStatus::Retry => unreachable!(),
@@ -2214,11 +2214,43 @@ impl AptosSimulationVM {
         let resolver = state_view.as_move_resolver();
         let vm = Self::new(&resolver);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        vm.simulate_signed_transaction(&resolver, transaction, &log_context)
+    }
+
+    /// Same as `create_vm_and_simulate_signed_transaction`, but also tracks
+    /// the state keys read by the transaction, e.g., to give the block
+    /// partitioner real read sets. Reads made while creating the VM itself
+    /// are not included.
+    pub fn create_vm_and_simulate_signed_transaction_with_access_set(
+        transaction: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutput, AccessSet) {
+        assert_err!(
+            transaction.verify_signature(),
+            "Simulated transaction should not have a valid signature"
+        );
 
+        let resolver = state_view.as_move_resolver();
+        let vm = Self::new(&resolver);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+
+        let tracking_resolver = ReadSetTrackingResolver::new(&resolver);
+        let (vm_status, txn_output) =
+            vm.simulate_signed_transaction(&tracking_resolver, transaction, &log_context);
+        (vm_status, txn_output, tracking_resolver.into_access_set())
+    }
+
+    fn simulate_signed_transaction(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        transaction: &SignedTransaction,
+        log_context: &AdapterLogSchema,
+    ) -> (VMStatus, TransactionOutput) {
         let (vm_status, vm_output) =
-            vm.0.execute_user_transaction(&resolver, transaction, &log_context);
+            self.0
+                .execute_user_transaction(resolver, transaction, log_context);
         let txn_output = vm_output
-            .try_materialize_into_transaction_output(&resolver)
+            .try_materialize_into_transaction_output(resolver)
             .expect("Materializing aggregator V1 deltas should never fail");
         (vm_status, txn_output)
     }
@@ -11,7 +11,10 @@ mod warm_vm_cache;
 pub(crate) mod write_op_converter;
 
 pub use crate::move_vm_ext::{
-    resolver::{AptosMoveResolver, AsExecutorView, AsResourceGroupView, ResourceGroupResolver},
+    resolver::{
+        AccessSet, AptosMoveResolver, AsExecutorView, AsResourceGroupView, ReadSetTrackingResolver,
+        ResourceGroupResolver,
+    },
     respawned_session::RespawnedSession,
     session::{SessionExt, SessionId},
     vm::{get_max_binary_format_version, get_max_identifier_size, verifier_config, MoveVmExt},
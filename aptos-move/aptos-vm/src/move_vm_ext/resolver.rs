@@ -1,16 +1,40 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_aggregator::resolver::{AggregatorV1Resolver, DelayedFieldResolver};
-use aptos_table_natives::TableResolver;
-use aptos_types::{on_chain_config::ConfigStorage, state_store::state_key::StateKey};
+use crate::{data_cache::get_resource_group_from_metadata, move_vm_ext::resource_state_key};
+use aptos_aggregator::{
+    bounded_math::SignedU128,
+    resolver::{AggregatorV1Resolver, DelayedFieldResolver, TAggregatorV1View, TDelayedFieldView},
+    types::{DelayedFieldID, DelayedFieldValue, DelayedFieldsSpeculativeError, PanicOr},
+};
+use aptos_table_natives::{TableHandle, TableResolver};
+use aptos_types::{
+    access_path::AccessPath,
+    aggregator::PanicError,
+    on_chain_config::ConfigStorage,
+    state_store::{
+        errors::StateviewError, state_key::StateKey, state_storage_usage::StateStorageUsage,
+        state_value::StateValue, StateViewId,
+    },
+    write_set::WriteOp,
+};
 use aptos_vm_types::resolver::{
     ExecutorView, ResourceGroupSize, ResourceGroupView, StateStorageView,
 };
 use bytes::Bytes;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
-use move_core_types::{language_storage::StructTag, resolver::MoveResolver};
-use std::collections::{BTreeMap, HashMap};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag},
+    metadata::Metadata,
+    resolver::{ModuleResolver, MoveResolver, ResourceResolver},
+    value::MoveTypeLayout,
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
 
 /// A general resolver used by AptosVM. Allows to implement custom hooks on
 /// top of storage, e.g. get resources from resource groups, etc.
@@ -26,6 +50,11 @@ pub trait AptosMoveResolver:
     + AsExecutorView
     + AsResourceGroupView
 {
+    /// Returns the state keys accessed through this resolver so far, if it
+    /// tracks them (see `ReadSetTrackingResolver`).
+    fn access_set(&self) -> Option<AccessSet> {
+        None
+    }
 }
 
 pub trait ResourceGroupResolver {
@@ -54,3 +83,354 @@ pub trait AsExecutorView {
 pub trait AsResourceGroupView {
     fn as_resource_group_view(&self) -> &dyn ResourceGroupView;
 }
+
+/// The set of state keys (resources, resource groups, modules, table items
+/// and aggregators) read while executing a transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessSet {
+    keys: BTreeSet<StateKey>,
+}
+
+impl AccessSet {
+    pub fn insert(&mut self, state_key: StateKey) {
+        self.keys.insert(state_key);
+    }
+
+    pub fn contains(&self, state_key: &StateKey) -> bool {
+        self.keys.contains(state_key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StateKey> {
+        self.keys.iter()
+    }
+
+    pub fn into_keys(self) -> BTreeSet<StateKey> {
+        self.keys
+    }
+}
+
+/// Wraps a resolver and records the state key of every access made through
+/// it, so that the real read set of a transaction can be fed to the block
+/// partitioner instead of a heuristic one. Only installed when tracking is
+/// requested, so regular execution does not pay for it.
+///
+/// Note: reads made directly through the executor or resource group views
+/// (e.g., by respawned sessions) bypass the wrapper and are not recorded.
+pub struct ReadSetTrackingResolver<'r> {
+    inner: &'r dyn AptosMoveResolver,
+    access_set: RefCell<AccessSet>,
+}
+
+impl<'r> ReadSetTrackingResolver<'r> {
+    pub fn new(inner: &'r dyn AptosMoveResolver) -> Self {
+        Self {
+            inner,
+            access_set: RefCell::new(AccessSet::default()),
+        }
+    }
+
+    pub fn into_access_set(self) -> AccessSet {
+        self.access_set.into_inner()
+    }
+
+    fn record(&self, state_key: StateKey) {
+        self.access_set.borrow_mut().insert(state_key);
+    }
+
+    fn record_module(&self, module_id: &ModuleId) {
+        self.record(StateKey::access_path(AccessPath::from(module_id)));
+    }
+}
+
+impl<'r> AptosMoveResolver for ReadSetTrackingResolver<'r> {
+    fn access_set(&self) -> Option<AccessSet> {
+        Some(self.access_set.borrow().clone())
+    }
+}
+
+impl<'r> ResourceResolver for ReadSetTrackingResolver<'r> {
+    type Error = PartialVMError;
+
+    fn get_resource_bytes_with_metadata_and_layout(
+        &self,
+        address: &AccountAddress,
+        struct_tag: &StructTag,
+        metadata: &[Metadata],
+        maybe_layout: Option<&MoveTypeLayout>,
+    ) -> PartialVMResult<(Option<Bytes>, usize)> {
+        let state_key = match get_resource_group_from_metadata(struct_tag, metadata) {
+            Some(resource_group) => StateKey::access_path(AccessPath::resource_group_access_path(
+                *address,
+                resource_group,
+            )),
+            None => resource_state_key(*address, struct_tag.clone())?,
+        };
+        self.record(state_key);
+        self.inner.get_resource_bytes_with_metadata_and_layout(
+            address,
+            struct_tag,
+            metadata,
+            maybe_layout,
+        )
+    }
+}
+
+impl<'r> ModuleResolver for ReadSetTrackingResolver<'r> {
+    type Error = PartialVMError;
+
+    fn get_module_metadata(&self, module_id: &ModuleId) -> Vec<Metadata> {
+        self.record_module(module_id);
+        self.inner.get_module_metadata(module_id)
+    }
+
+    fn get_module(&self, module_id: &ModuleId) -> PartialVMResult<Option<Bytes>> {
+        self.record_module(module_id);
+        self.inner.get_module(module_id)
+    }
+}
+
+impl<'r> TableResolver for ReadSetTrackingResolver<'r> {
+    fn resolve_table_entry_bytes_with_layout(
+        &self,
+        handle: &TableHandle,
+        key: &[u8],
+        maybe_layout: Option<&MoveTypeLayout>,
+    ) -> PartialVMResult<Option<Bytes>> {
+        self.record(StateKey::table_item((*handle).into(), key.to_vec()));
+        self.inner
+            .resolve_table_entry_bytes_with_layout(handle, key, maybe_layout)
+    }
+}
+
+impl<'r> TAggregatorV1View for ReadSetTrackingResolver<'r> {
+    type Identifier = StateKey;
+
+    fn get_aggregator_v1_state_value(
+        &self,
+        id: &Self::Identifier,
+    ) -> PartialVMResult<Option<StateValue>> {
+        self.record(id.clone());
+        self.inner.get_aggregator_v1_state_value(id)
+    }
+}
+
+impl<'r> TDelayedFieldView for ReadSetTrackingResolver<'r> {
+    type Identifier = DelayedFieldID;
+    type ResourceGroupTag = StructTag;
+    type ResourceKey = StateKey;
+    type ResourceValue = WriteOp;
+
+    fn is_delayed_field_optimization_capable(&self) -> bool {
+        self.inner.is_delayed_field_optimization_capable()
+    }
+
+    fn get_delayed_field_value(
+        &self,
+        id: &Self::Identifier,
+    ) -> Result<DelayedFieldValue, PanicOr<DelayedFieldsSpeculativeError>> {
+        self.inner.get_delayed_field_value(id)
+    }
+
+    fn delayed_field_try_add_delta_outcome(
+        &self,
+        id: &Self::Identifier,
+        base_delta: &SignedU128,
+        delta: &SignedU128,
+        max_value: u128,
+    ) -> Result<bool, PanicOr<DelayedFieldsSpeculativeError>> {
+        self.inner
+            .delayed_field_try_add_delta_outcome(id, base_delta, delta, max_value)
+    }
+
+    fn generate_delayed_field_id(&self) -> Self::Identifier {
+        self.inner.generate_delayed_field_id()
+    }
+
+    fn validate_and_convert_delayed_field_id(
+        &self,
+        id: u64,
+    ) -> Result<Self::Identifier, PanicError> {
+        self.inner.validate_and_convert_delayed_field_id(id)
+    }
+
+    fn get_reads_needing_exchange(
+        &self,
+        delayed_write_set_keys: &HashSet<Self::Identifier>,
+        skip: &HashSet<Self::ResourceKey>,
+    ) -> Result<BTreeMap<Self::ResourceKey, (Self::ResourceValue, Arc<MoveTypeLayout>)>, PanicError>
+    {
+        self.inner
+            .get_reads_needing_exchange(delayed_write_set_keys, skip)
+    }
+
+    fn get_group_reads_needing_exchange(
+        &self,
+        delayed_write_set_keys: &HashSet<Self::Identifier>,
+        skip: &HashSet<Self::ResourceKey>,
+    ) -> Result<BTreeMap<Self::ResourceKey, (Self::ResourceValue, u64)>, PanicError> {
+        self.inner
+            .get_group_reads_needing_exchange(delayed_write_set_keys, skip)
+    }
+}
+
+impl<'r> ResourceGroupResolver for ReadSetTrackingResolver<'r> {
+    fn release_resource_group_cache(
+        &self,
+    ) -> Option<HashMap<StateKey, BTreeMap<StructTag, Bytes>>> {
+        self.inner.release_resource_group_cache()
+    }
+
+    fn resource_group_size(&self, group_key: &StateKey) -> PartialVMResult<ResourceGroupSize> {
+        self.record(group_key.clone());
+        self.inner.resource_group_size(group_key)
+    }
+
+    fn resource_size_in_group(
+        &self,
+        group_key: &StateKey,
+        resource_tag: &StructTag,
+    ) -> PartialVMResult<usize> {
+        self.record(group_key.clone());
+        self.inner.resource_size_in_group(group_key, resource_tag)
+    }
+
+    fn resource_exists_in_group(
+        &self,
+        group_key: &StateKey,
+        resource_tag: &StructTag,
+    ) -> PartialVMResult<bool> {
+        self.record(group_key.clone());
+        self.inner.resource_exists_in_group(group_key, resource_tag)
+    }
+}
+
+impl<'r> ConfigStorage for ReadSetTrackingResolver<'r> {
+    fn fetch_config(&self, access_path: AccessPath) -> Option<Bytes> {
+        self.record(StateKey::access_path(access_path.clone()));
+        self.inner.fetch_config(access_path)
+    }
+}
+
+impl<'r> StateStorageView for ReadSetTrackingResolver<'r> {
+    fn id(&self) -> StateViewId {
+        self.inner.id()
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage, StateviewError> {
+        self.inner.get_usage()
+    }
+}
+
+impl<'r> AsExecutorView for ReadSetTrackingResolver<'r> {
+    fn as_executor_view(&self) -> &dyn ExecutorView {
+        self.inner.as_executor_view()
+    }
+}
+
+impl<'r> AsResourceGroupView for ReadSetTrackingResolver<'r> {
+    fn as_resource_group_view(&self) -> &dyn ResourceGroupView {
+        self.inner.as_resource_group_view()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data_cache::AsMoveResolver, AptosSimulationVM};
+    use aptos_crypto::ed25519::Ed25519Signature;
+    use aptos_language_e2e_tests::{common_transactions::peer_to_peer_txn, executor::FakeExecutor};
+    use aptos_types::{
+        account_config::{AccountResource, CoinStoreResource, CORE_CODE_ADDRESS},
+        chain_id::ChainId,
+        move_resource::MoveStructType,
+        on_chain_config::OnChainConfig,
+        transaction::{ExecutionStatus, SignedTransaction, TransactionStatus},
+    };
+
+    fn resource_key(address: AccountAddress, struct_tag: StructTag) -> StateKey {
+        resource_state_key(address, struct_tag).unwrap()
+    }
+
+    #[test]
+    fn test_tracking_resolver_records_accessed_keys() {
+        let executor = FakeExecutor::from_head_genesis();
+        let resolver = executor.data_store().as_move_resolver();
+        let tracking_resolver = ReadSetTrackingResolver::new(&resolver);
+
+        let chain_id_tag = ChainId::struct_tag();
+        assert!(tracking_resolver
+            .get_resource(&CORE_CODE_ADDRESS, &chain_id_tag)
+            .unwrap()
+            .is_some());
+        let handle = TableHandle(AccountAddress::random());
+        assert!(tracking_resolver
+            .resolve_table_entry_bytes_with_layout(&handle, b"key", None)
+            .unwrap()
+            .is_none());
+
+        let expected_keys = BTreeSet::from([
+            StateKey::access_path(AccessPath::from(&chain_id_tag.module_id())),
+            resource_key(CORE_CODE_ADDRESS, chain_id_tag),
+            StateKey::table_item(handle.into(), b"key".to_vec()),
+        ]);
+        assert_eq!(
+            tracking_resolver.access_set(),
+            Some(AccessSet {
+                keys: expected_keys
+            })
+        );
+        assert_eq!(resolver.access_set(), None);
+    }
+
+    #[test]
+    fn test_simulation_access_set() {
+        let mut executor = FakeExecutor::from_head_genesis();
+        let sender = executor.create_raw_account_data(1_000_000, 10);
+        let receiver = executor.create_raw_account_data(100_000, 10);
+        let unrelated = executor.create_raw_account_data(100_000, 10);
+        executor.add_account_data(&sender);
+        executor.add_account_data(&receiver);
+        executor.add_account_data(&unrelated);
+
+        // Simulated transactions must not carry a valid signature.
+        let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000, 0);
+        let txn = SignedTransaction::new(
+            txn.into_raw_transaction(),
+            sender.account().pubkey.clone(),
+            Ed25519Signature::dummy_signature(),
+        );
+
+        let (_, output, access_set) =
+            AptosSimulationVM::create_vm_and_simulate_signed_transaction_with_access_set(
+                &txn,
+                executor.get_state_view(),
+            );
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+
+        for address in [sender.address(), receiver.address()] {
+            assert!(access_set.contains(&resource_key(*address, CoinStoreResource::struct_tag())));
+        }
+        assert!(access_set.contains(&resource_key(
+            *sender.address(),
+            AccountResource::struct_tag()
+        )));
+
+        // Nothing about accounts the transaction does not touch is recorded.
+        let unrelated_keys = [
+            resource_key(*unrelated.address(), AccountResource::struct_tag()),
+            resource_key(*unrelated.address(), CoinStoreResource::struct_tag()),
+        ];
+        assert!(unrelated_keys.iter().all(|key| !access_set.contains(key)));
+    }
+}
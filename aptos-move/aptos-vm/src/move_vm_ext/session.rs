@@ -3,7 +3,9 @@
 
 use crate::{
     data_cache::get_resource_group_from_metadata,
-    move_vm_ext::{resource_state_key, write_op_converter::WriteOpConverter, AptosMoveResolver},
+    move_vm_ext::{
+        resource_state_key, write_op_converter::WriteOpConverter, AccessSet, AptosMoveResolver,
+    },
     transaction_metadata::TransactionMetadata,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -208,6 +210,18 @@ impl<'r, 'l> SessionExt<'r, 'l> {
         Ok(change_set)
     }
 
+    /// Same as `finish`, but also returns the state keys read through the
+    /// session's resolver. The access set is `None` unless the session was
+    /// created on top of a `ReadSetTrackingResolver`.
+    pub fn finish_with_access_set(
+        self,
+        configs: &ChangeSetConfigs,
+    ) -> VMResult<(VMChangeSet, Option<AccessSet>)> {
+        let remote = self.remote;
+        let change_set = self.finish(configs)?;
+        Ok((change_set, remote.access_set()))
+    }
+
     pub fn extract_publish_request(&mut self) -> Option<PublishRequest> {
         let ctx = self.get_native_extensions().get_mut::<NativeCodeContext>();
         ctx.requested_module_bundle.take()
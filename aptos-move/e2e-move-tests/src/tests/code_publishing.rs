@@ -10,7 +10,10 @@ use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
     on_chain_config::FeatureFlag,
 };
-use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, parser::parse_struct_tag,
+    vm_status::StatusCode,
+};
 use rstest::rstest;
 use serde::{Deserialize, Serialize};
 
@@ -353,3 +356,44 @@ fn code_publishing_friend_as_private(enabled: Vec<FeatureFlag>, disabled: Vec<Fe
         assert_vm_status!(result, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
     }
 }
+
+#[test]
+fn code_publishing_module_sizes() {
+    let mut pack = PackageBuilder::new("Package");
+    pack.add_source("a", "module 0xcafe::a { public fun f() {} }");
+    pack.add_source(
+        "b",
+        "module 0xcafe::b { use 0xcafe::a; public fun g(x: u64): u64 { a::f(); x + 1 } }",
+    );
+    let pack_dir = pack.write_to_temp().unwrap();
+    let package = build_package(
+        pack_dir.path().to_owned(),
+        aptos_framework::BuildOptions::default(),
+    )
+    .unwrap();
+
+    let module_sizes = package.module_sizes();
+    let module_id = |name: &str| {
+        ModuleId::new(
+            AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            Identifier::new(name).unwrap(),
+        )
+    };
+    let mut module_ids: Vec<_> = module_sizes.iter().map(|(id, _)| id.clone()).collect();
+    module_ids.sort();
+    assert_eq!(module_ids, vec![module_id("a"), module_id("b")]);
+    assert!(module_sizes.iter().all(|(_, size)| *size > 0));
+
+    let code = package.extract_code();
+    assert_eq!(
+        module_sizes
+            .iter()
+            .map(|(_, size)| *size)
+            .collect::<Vec<_>>(),
+        code.iter().map(|bytes| bytes.len()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        package.total_code_size(),
+        code.iter().map(|bytes| bytes.len()).sum::<usize>()
+    );
+}
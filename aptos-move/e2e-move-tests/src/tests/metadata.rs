@@ -4,12 +4,12 @@
 use crate::{assert_vm_status, build_package, MoveHarness};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_framework::{
-    BuildOptions, RuntimeModuleMetadata, RuntimeModuleMetadataV1, APTOS_METADATA_KEY,
-    APTOS_METADATA_KEY_V1,
+    dependency_digest, BuildOptions, BuiltPackage, RuntimeModuleMetadata, RuntimeModuleMetadataV1,
+    APTOS_METADATA_KEY, APTOS_METADATA_KEY_V1,
 };
 use aptos_package_builder::PackageBuilder;
 use aptos_types::transaction::TransactionStatus;
-use move_binary_format::CompiledModule;
+use move_binary_format::{file_format_common::VERSION_DEFAULT, CompiledModule};
 use move_core_types::{account_address::AccountAddress, metadata::Metadata, vm_status::StatusCode};
 use std::collections::BTreeMap;
 
@@ -129,3 +129,53 @@ fn test_metadata_with_changes(f: impl Fn() -> Vec<Metadata>) -> TransactionStatu
         ),
     )
 }
+
+/// Builds a package depending on a package with the given source.
+fn build_with_dependency(dependency_source: &str) -> BuiltPackage {
+    let mut dependency = PackageBuilder::new("Dependency");
+    dependency.add_source("dep.move", dependency_source);
+    let dependency_dir = dependency.write_to_temp().unwrap();
+
+    let mut builder = PackageBuilder::new("Package");
+    builder.add_local_dep("Dependency", &dependency_dir.path().to_string_lossy());
+    builder.add_source(
+        "m.move",
+        r#"
+        module 0xf00d::M {
+            public fun foo(): u64 { 0xcafe::dep::value() }
+        }
+        "#,
+    );
+    let path = builder.write_to_temp().unwrap();
+
+    build_package(path.path().to_path_buf(), BuildOptions::default())
+        .expect("building package must succeed")
+}
+
+#[test]
+fn test_build_info() {
+    let dependency_v1 = "module 0xcafe::dep { public fun value(): u64 { 1 } }";
+    let dependency_v2 = "module 0xcafe::dep { public fun value(): u64 { 2 } }";
+    let build_info = build_with_dependency(dependency_v1)
+        .extract_build_info()
+        .unwrap();
+    let rebuilt_info = build_with_dependency(dependency_v1)
+        .extract_build_info()
+        .unwrap();
+    let upgraded_package = build_with_dependency(dependency_v2);
+    let upgraded_info = upgraded_package.extract_build_info().unwrap();
+
+    assert_eq!(rebuilt_info, build_info);
+    assert_ne!(
+        upgraded_info.dependency_digest,
+        build_info.dependency_digest
+    );
+    assert_eq!(
+        dependency_digest(&upgraded_package.dependency_code()),
+        upgraded_info.dependency_digest
+    );
+    assert_eq!(
+        build_info.bytecode_versions,
+        BTreeMap::from([("M".to_string(), VERSION_DEFAULT as u8)])
+    );
+}
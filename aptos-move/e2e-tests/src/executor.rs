@@ -22,7 +22,8 @@ use aptos_gas_algebra::DynamicExpression;
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
 use aptos_gas_schedule::{
-    InitialGasSchedule, MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION,
+    AptosGasParameters, InitialGasSchedule, MiscGasParameters, NativeGasParameters,
+    LATEST_GAS_FEATURE_VERSION,
 };
 use aptos_keygen::KeyGen;
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
@@ -39,8 +40,8 @@ use aptos_types::{
     chain_id::ChainId,
     contract_event::ContractEvent,
     on_chain_config::{
-        FeatureFlag, Features, OnChainConfig, TimedFeatureOverride, TimedFeaturesBuilder,
-        ValidatorSet, Version,
+        FeatureFlag, Features, GasScheduleV2, OnChainConfig, TimedFeatureOverride,
+        TimedFeaturesBuilder, ValidatorSet, Version,
     },
     state_store::{state_key::StateKey, state_value::StateValue, TStateView},
     transaction::{
@@ -376,13 +377,14 @@ impl FakeExecutor {
         self.read_account_resource_at_address(account.address())
     }
 
+    /// Reads the resource `T` under the given address from this executor's data store, or
+    /// `None` if there's no such resource.
     pub fn read_resource<T: MoveResource>(&self, addr: &AccountAddress) -> Option<T> {
         let ap =
             AccessPath::resource_access_path(*addr, T::struct_tag()).expect("access path in test");
         let data_blob =
             TStateView::get_state_value_bytes(&self.data_store, &StateKey::access_path(ap))
-                .expect("account must exist in data store")
-                .unwrap_or_else(|| panic!("Can't fetch {} resource for {}", T::STRUCT_NAME, addr));
+                .expect("account must exist in data store")?;
         bcs::from_bytes(&data_blob).ok()
     }
 
@@ -674,6 +676,38 @@ impl FakeExecutor {
             .set(state_key, StateValue::new_legacy(data_blob.into()));
     }
 
+    /// Reads the gas parameters from the on-chain gas schedule in this executor's data store.
+    pub fn gas_parameters(&self) -> AptosGasParameters {
+        let gas_schedule = GasScheduleV2::fetch_config(&self.data_store)
+            .expect("gas schedule must exist in data store");
+        AptosGasParameters::from_on_chain_gas_schedule(
+            &gas_schedule.to_btree_map(),
+            gas_schedule.feature_version,
+        )
+        .expect("gas schedule must contain all gas parameters")
+    }
+
+    /// Overrides the gas parameters used by all subsequent transactions.
+    ///
+    /// Until this is called, transactions run under the gas schedule installed by genesis. The
+    /// override replaces the on-chain gas schedule, keeping its feature version, so it also
+    /// stays in effect across new blocks. To change a single parameter, start from
+    /// `gas_parameters()`.
+    pub fn set_gas_parameters(&mut self, gas_params: AptosGasParameters) {
+        let feature_version = GasScheduleV2::fetch_config(&self.data_store)
+            .expect("gas schedule must exist in data store")
+            .feature_version;
+        let gas_schedule = GasScheduleV2 {
+            feature_version,
+            entries: gas_params.to_on_chain_gas_schedule(feature_version),
+        };
+        let access_path = GasScheduleV2::access_path().expect("access path in test");
+        self.write_state_value(
+            StateKey::access_path(access_path),
+            bcs::to_bytes(&gas_schedule).expect("gas schedule must serialize"),
+        );
+    }
+
     /// Verifies the given transaction by running it through the VM verifier.
     pub fn verify_transaction(&self, txn: SignedTransaction) -> VMValidatorResult {
         let vm = AptosVM::new(&self.get_state_view().as_move_resolver());
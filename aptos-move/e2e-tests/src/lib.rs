@@ -8,7 +8,10 @@
 //!
 //! This crate contains helpers for executing tests against the Aptos VM.
 
-use aptos_types::{transaction::TransactionStatus, vm_status::KeptVMStatus};
+use aptos_types::{
+    transaction::TransactionStatus,
+    vm_status::{KeptVMStatus, StatusCode},
+};
 
 pub mod account;
 pub mod account_universe;
@@ -41,11 +44,35 @@ pub fn transaction_status_eq(t1: &TransactionStatus, t2: &TransactionStatus) ->
     }
 }
 
+/// Checks that the prologue rejects a transaction the same way during validation and execution:
+/// validation fails with `expected`, and execution discards the transaction with `expected`.
+pub fn check_prologue_parity(
+    verify_status: Option<StatusCode>,
+    execute_status: &TransactionStatus,
+    expected: StatusCode,
+) -> Result<(), String> {
+    if verify_status != Some(expected) {
+        return Err(format!(
+            "Validation status {:?} doesn't match expected {:?}",
+            verify_status, expected
+        ));
+    }
+    if execute_status != &TransactionStatus::Discard(expected) {
+        return Err(format!(
+            "Execution status {:?} doesn't match expected {:?}",
+            execute_status,
+            TransactionStatus::Discard(expected)
+        ));
+    }
+    Ok(())
+}
+
 #[macro_export]
 macro_rules! assert_prologue_parity {
     ($e1:expr, $e2:expr, $e3:expr) => {
-        assert_eq!($e1.unwrap(), $e3);
-        assert!(transaction_status_eq($e2, &TransactionStatus::Discard($e3)));
+        if let Err(err) = $crate::check_prologue_parity($e1, $e2, $e3) {
+            panic!("{}", err);
+        }
     };
 }
 
@@ -6,7 +6,8 @@ use aptos_language_e2e_tests::{
     account::Account, common_transactions::peer_to_peer_txn, executor::FakeExecutor,
 };
 use aptos_types::{
-    account_config::{DepositEvent, WithdrawEvent},
+    account_address::AccountAddress,
+    account_config::{CoinStoreResource, DepositEvent, WithdrawEvent},
     transaction::{ExecutionStatus, SignedTransaction, TransactionOutput, TransactionStatus},
 };
 use std::{convert::TryFrom, time::Instant};
@@ -61,6 +62,34 @@ fn single_peer_to_peer_with_event() {
     }
 }
 
+#[test]
+fn peer_to_peer_read_receiver_resource() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let sender = executor.create_raw_account_data(1_000_000, 10);
+    let receiver = executor.create_raw_account_data(100_000, 10);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let txn = peer_to_peer_txn(sender.account(), receiver.account(), 10, 1_000, 0);
+    let output = executor.execute_transaction(txn);
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    executor.apply_write_set(output.write_set());
+
+    let coin_store = executor
+        .read_resource::<CoinStoreResource>(receiver.address())
+        .expect("receiver coin store must exist");
+    assert_eq!(coin_store.coin(), 101_000);
+    assert_eq!(coin_store.deposit_events().count(), 1);
+
+    // Accounts that were never created have no resources.
+    assert!(executor
+        .read_resource::<CoinStoreResource>(&AccountAddress::random())
+        .is_none());
+}
+
 #[test]
 fn few_peer_to_peer_with_event() {
     let mut executor = FakeExecutor::from_head_genesis();
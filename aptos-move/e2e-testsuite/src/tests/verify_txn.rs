@@ -7,8 +7,9 @@ use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
 use aptos_gas_algebra::Gas;
 use aptos_gas_schedule::{InitialGasSchedule, TransactionGasParameters};
 use aptos_language_e2e_tests::{
-    assert_prologue_disparity, assert_prologue_parity, common_transactions::EMPTY_SCRIPT,
-    compile::compile_module, current_function_name, executor::FakeExecutor, transaction_status_eq,
+    assert_prologue_disparity, assert_prologue_parity, check_prologue_parity,
+    common_transactions::EMPTY_SCRIPT, compile::compile_module, current_function_name,
+    executor::FakeExecutor, transaction_status_eq,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -29,6 +30,48 @@ use move_ir_compiler::Compiler;
 
 pub const MAX_TRANSACTION_SIZE_IN_BYTES: u64 = 6 * 1024 * 1024;
 
+#[test]
+fn prologue_parity_matching_statuses() {
+    check_prologue_parity(
+        Some(StatusCode::INVALID_SIGNATURE),
+        &TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE),
+        StatusCode::INVALID_SIGNATURE,
+    )
+    .unwrap();
+}
+
+#[test]
+fn prologue_parity_mismatching_statuses() {
+    // Validation disagrees with the expected status.
+    check_prologue_parity(
+        Some(StatusCode::SEQUENCE_NUMBER_TOO_OLD),
+        &TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE),
+        StatusCode::INVALID_SIGNATURE,
+    )
+    .unwrap_err();
+    check_prologue_parity(
+        None,
+        &TransactionStatus::Discard(StatusCode::INVALID_SIGNATURE),
+        StatusCode::INVALID_SIGNATURE,
+    )
+    .unwrap_err();
+
+    // Execution disagrees with the expected status.
+    check_prologue_parity(
+        Some(StatusCode::INVALID_SIGNATURE),
+        &TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD),
+        StatusCode::INVALID_SIGNATURE,
+    )
+    .unwrap_err();
+    let err = check_prologue_parity(
+        Some(StatusCode::INVALID_SIGNATURE),
+        &TransactionStatus::Keep(ExecutionStatus::Success),
+        StatusCode::INVALID_SIGNATURE,
+    )
+    .unwrap_err();
+    assert!(err.contains("Execution status"), "{}", err);
+}
+
 #[test]
 fn verify_signature() {
     let mut executor = FakeExecutor::from_head_genesis();
@@ -992,3 +1035,39 @@ fn test_type_tag_transitive_dependency_fails_verification() {
         _ => panic!("Kept transaction with an invariant violation!"),
     }
 }
+
+#[test]
+fn verify_max_gas_units_with_overridden_gas_schedule() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let sender = executor.create_raw_account_data(1_000_000_000, 10);
+    executor.add_account_data(&sender);
+
+    let mut gas_params = executor.gas_parameters();
+    gas_params.vm.txn.maximum_number_of_gas_units = Gas::new(10_000);
+    executor.set_gas_parameters(gas_params);
+
+    let txn = sender
+        .account()
+        .transaction()
+        .script(Script::new(EMPTY_SCRIPT.clone(), vec![], vec![]))
+        .sequence_number(10)
+        .max_gas_amount(10_001)
+        .gas_unit_price(100)
+        .sign();
+    assert_prologue_parity!(
+        executor.verify_transaction(txn.clone()).status(),
+        executor.execute_transaction(txn).status(),
+        StatusCode::MAX_GAS_UNITS_EXCEEDS_MAX_GAS_UNITS_BOUND
+    );
+
+    // The same transaction is accepted within the lowered bound.
+    let txn = sender
+        .account()
+        .transaction()
+        .script(Script::new(EMPTY_SCRIPT.clone(), vec![], vec![]))
+        .sequence_number(10)
+        .max_gas_amount(10_000)
+        .gas_unit_price(100)
+        .sign();
+    assert_eq!(executor.verify_transaction(txn).status(), None);
+}
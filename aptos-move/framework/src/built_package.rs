@@ -9,6 +9,7 @@ use crate::{
     APTOS_METADATA_KEY_V1, METADATA_V1_MIN_FILE_FORMAT_VERSION,
 };
 use anyhow::bail;
+use aptos_crypto::HashValue;
 use aptos_types::{account_address::AccountAddress, transaction::EntryABI};
 use clap::Parser;
 use codespan_reporting::{
@@ -156,6 +157,29 @@ pub fn build_model(
     })
 }
 
+/// Information about how a package was built which is not part of its on-chain metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PackageBuildInfo {
+    /// The bytecode version each module is compiled to, by module name
+    pub bytecode_versions: BTreeMap<String, u8>,
+    /// Digest of the dependencies the package is built against, see `dependency_digest`
+    pub dependency_digest: [u8; 32],
+}
+
+/// Computes a digest of the dependencies of a package, given the code of the modules of each
+/// dependency package as returned by `BuiltPackage::dependency_code`. Building against a
+/// different revision of any dependency changes the digest.
+pub fn dependency_digest(deps: &BTreeMap<String, BTreeMap<ModuleId, Vec<u8>>>) -> [u8; 32] {
+    let deps: Vec<(&String, HashValue)> = deps
+        .iter()
+        .map(|(package_name, modules)| {
+            let code = bcs::to_bytes(modules).expect("Module code has BCS");
+            (package_name, HashValue::sha3_256_of(&code))
+        })
+        .collect();
+    *HashValue::sha3_256_of(&bcs::to_bytes(&deps).expect("Digests have BCS")).as_ref()
+}
+
 impl BuiltPackage {
     /// Builds the package and on success delivers a `BuiltPackage`.
     ///
@@ -275,6 +299,29 @@ impl BuiltPackage {
             .collect()
     }
 
+    /// Returns the serialized size in bytes of each module of the built package, in the same
+    /// order as `extract_code`.
+    pub fn module_sizes(&self) -> Vec<(ModuleId, usize)> {
+        self.package
+            .root_modules()
+            .filter_map(|unit_with_source| match &unit_with_source.unit {
+                CompiledUnit::Module(NamedCompiledModule { module, .. }) => Some((
+                    module.self_id(),
+                    unit_with_source
+                        .unit
+                        .serialize(self.options.bytecode_version)
+                        .len(),
+                )),
+                CompiledUnit::Script(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the total serialized size in bytes of the modules of the built package.
+    pub fn total_code_size(&self) -> usize {
+        self.module_sizes().iter().map(|(_, size)| size).sum()
+    }
+
     /// Returns the abis for this package, if available.
     pub fn extract_abis(&self) -> Option<Vec<EntryABI>> {
         self.package.compiled_abis.as_ref().map(|abis| {
@@ -389,6 +436,45 @@ impl BuiltPackage {
         })
     }
 
+    /// Extracts information about how the package was built which is not part of its on-chain
+    /// metadata.
+    pub fn extract_build_info(&self) -> anyhow::Result<PackageBuildInfo> {
+        Ok(PackageBuildInfo {
+            bytecode_versions: self.module_bytecode_versions()?,
+            dependency_digest: dependency_digest(&self.dependency_code()),
+        })
+    }
+
+    /// Returns the bytecode version each module of the built package is compiled to, by module
+    /// name.
+    pub fn module_bytecode_versions(&self) -> anyhow::Result<BTreeMap<String, u8>> {
+        self.package
+            .root_modules()
+            .map(|u| {
+                let code = u.unit.serialize(self.options.bytecode_version);
+                let version = CompiledModule::deserialize(&code)?.version;
+                Ok((u.unit.name().to_string(), u8::try_from(version)?))
+            })
+            .collect()
+    }
+
+    /// Returns the code of the modules of the dependency packages the package is built against,
+    /// by package name and module id.
+    pub fn dependency_code(&self) -> BTreeMap<String, BTreeMap<ModuleId, Vec<u8>>> {
+        let mut deps: BTreeMap<String, BTreeMap<ModuleId, Vec<u8>>> = BTreeMap::new();
+        for (package_name, u) in &self.package.deps_compiled_units {
+            if let CompiledUnit::Module(NamedCompiledModule { module, .. }) = &u.unit {
+                deps.entry(package_name.as_str().to_string())
+                    .or_default()
+                    .insert(
+                        module.self_id(),
+                        u.unit.serialize(self.options.bytecode_version),
+                    );
+            }
+        }
+        deps
+    }
+
     pub fn extract_metadata_and_save(&self) -> anyhow::Result<()> {
         let data = self.extract_metadata()?;
         let path = self.package_artifacts_path();
@@ -70,6 +70,8 @@ fn native_read(
     let mut aggregator_data = aggregator_context.aggregator_v1_data.borrow_mut();
     let aggregator = aggregator_data.get_aggregator(id.clone(), max_value)?;
 
+    // Only the aggregator being read is materialized: materializing the others
+    // would turn their deltas into writes, which conflict in parallel execution.
     let value = aggregator.read_and_materialize(aggregator_context.aggregator_v1_resolver, &id)?;
 
     Ok(smallvec![Value::u128(value)])
@@ -164,11 +164,14 @@ impl FromStr for UpgradePolicy {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        match s.to_ascii_lowercase().as_str() {
             "arbitrary" => Ok(UpgradePolicy::arbitrary()),
             "compatible" => Ok(UpgradePolicy::compat()),
             "immutable" => Ok(UpgradePolicy::immutable()),
-            _ => bail!("unknown policy"),
+            _ => bail!(
+                "unknown upgrade policy `{}`, expected one of: arbitrary, compatible, immutable",
+                s
+            ),
         }
     }
 }
@@ -347,3 +350,32 @@ pub fn make_all(
 
     builder.make_named_natives(natives)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_policy_from_str() {
+        for policy in [
+            UpgradePolicy::arbitrary(),
+            UpgradePolicy::compat(),
+            UpgradePolicy::immutable(),
+        ] {
+            assert_eq!(
+                UpgradePolicy::from_str(&policy.to_string()).unwrap(),
+                policy
+            );
+        }
+        assert_eq!(
+            UpgradePolicy::from_str("Compatible").unwrap(),
+            UpgradePolicy::compat()
+        );
+
+        let err = UpgradePolicy::from_str("upgradable")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("upgradable"));
+        assert!(err.contains("arbitrary, compatible, immutable"));
+    }
+}
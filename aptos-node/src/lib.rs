@@ -30,7 +30,7 @@ use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
 use aptos_types::{chain_id::ChainId, validator_txn::Topic};
 use aptos_validator_transaction_pool as vtxn_pool;
 use clap::Parser;
-use futures::channel::mpsc;
+use futures::channel::{mpsc, oneshot};
 use hex::{FromHex, FromHexError};
 use rand::{rngs::StdRng, SeedableRng};
 use std::{
@@ -194,6 +194,7 @@ pub struct AptosHandle {
     _indexer_grpc_runtime: Option<Runtime>,
     _indexer_runtime: Option<Runtime>,
     _indexer_table_info_runtime: Option<Runtime>,
+    _jwk_consensus_close_tx: Option<oneshot::Sender<oneshot::Sender<()>>>,
     _jwk_consensus_runtime: Option<Runtime>,
     _mempool_runtime: Runtime,
     _network_runtimes: Vec<Runtime>,
@@ -698,25 +699,29 @@ pub fn setup_environment_and_start_node(
         _ => None,
     };
 
-    let jwk_consensus_runtime = if let Some(obj) = jwk_consensus_network_interfaces {
-        let ApplicationNetworkInterfaces {
-            network_client,
-            network_service_events,
-        } = obj;
-        let (reconfig_events, onchain_jwk_updated_events) = jwk_consensus_subscriptions.expect(
-            "JWK consensus needs to listen to NewEpochEvents and OnChainJWKMapUpdated events.",
-        );
-        let jwk_consensus_runtime = start_jwk_consensus_runtime(
-            network_client,
-            network_service_events,
-            vtxn_pool_writer_for_jwk,
-            reconfig_events,
-            onchain_jwk_updated_events,
-        );
-        Some(jwk_consensus_runtime)
-    } else {
-        None
-    };
+    let (jwk_consensus_runtime, jwk_consensus_close_tx) =
+        if let Some(obj) = jwk_consensus_network_interfaces {
+            let ApplicationNetworkInterfaces {
+                network_client,
+                network_service_events,
+            } = obj;
+            let (reconfig_events, onchain_jwk_updated_events) = jwk_consensus_subscriptions.expect(
+                "JWK consensus needs to listen to NewEpochEvents and OnChainJWKMapUpdated events.",
+            );
+            let (close_tx, close_rx) = oneshot::channel();
+            let jwk_consensus_runtime = start_jwk_consensus_runtime(
+                &node_config.jwk_consensus,
+                network_client,
+                network_service_events,
+                vtxn_pool_writer_for_jwk,
+                reconfig_events,
+                onchain_jwk_updated_events,
+                close_rx,
+            );
+            (Some(jwk_consensus_runtime), Some(close_tx))
+        } else {
+            (None, None)
+        };
 
     // Create the consensus runtime (this blocks on state sync first)
     let consensus_runtime = consensus_network_interfaces.map(|consensus_network_interfaces| {
@@ -748,6 +753,7 @@ pub fn setup_environment_and_start_node(
         _indexer_grpc_runtime: indexer_grpc_runtime,
         _indexer_runtime: indexer_runtime,
         _indexer_table_info_runtime: indexer_table_info_runtime,
+        _jwk_consensus_close_tx: jwk_consensus_close_tx,
         _jwk_consensus_runtime: jwk_consensus_runtime,
         _mempool_runtime: mempool_runtime,
         _network_runtimes: network_runtimes,
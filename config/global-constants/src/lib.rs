@@ -39,8 +39,23 @@ pub const DEFAULT_BUCKETS: &[u64] = &[0, 150, 300, 500, 1000, 3000, 5000, 10000,
 /// Gas costs are dynamic based on storage, so the simulation values need some headroom applied by
 /// the user if using it to estimate gas
 pub fn adjust_gas_headroom(gas_used: u64, max_possible_gas: u64) -> u64 {
+    adjust_gas_headroom_by_ratio(
+        gas_used,
+        max_possible_gas,
+        GAS_HEADROOM_NUMERATOR,
+        GAS_HEADROOM_DENOMINATOR,
+    )
+}
+
+/// Same as `adjust_gas_headroom`, but with the headroom given as `numerator / denominator`
+pub fn adjust_gas_headroom_by_ratio(
+    gas_used: u64,
+    max_possible_gas: u64,
+    numerator: u64,
+    denominator: u64,
+) -> u64 {
     std::cmp::min(
         max_possible_gas,
-        (gas_used.saturating_mul(GAS_HEADROOM_NUMERATOR)).saturating_div(GAS_HEADROOM_DENOMINATOR),
+        (gas_used.saturating_mul(numerator)).saturating_div(denominator),
     )
 }
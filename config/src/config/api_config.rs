@@ -11,7 +11,7 @@ use crate::{
 };
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -30,6 +30,10 @@ pub struct ApiConfig {
     /// A maximum limit to the body of a POST request in bytes
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub content_length_limit: Option<u64>,
+    /// Per-route overrides of `content_length_limit`, keyed by path prefix (e.g. `/v1/view`).
+    /// When several prefixes match a request, the longest one wins.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub route_content_length_limits: BTreeMap<String, u64>,
     /// Enables failpoints for error testing
     #[serde(default = "default_disabled")]
     pub failpoints_enabled: bool,
@@ -45,6 +49,10 @@ pub struct ApiConfig {
     /// Enables transaction submission APIs
     #[serde(default = "default_enabled")]
     pub transaction_submission_enabled: bool,
+    /// Rejects submission of transactions whose payload is not an entry function (or a multisig
+    /// entry function), i.e. scripts and module bundles
+    #[serde(default = "default_disabled")]
+    pub entry_function_payloads_only: bool,
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
@@ -54,6 +62,8 @@ pub struct ApiConfig {
     pub max_transactions_page_size: u16,
     /// Maximum page size for event paginated APIs
     pub max_events_page_size: u16,
+    /// Maximum number of events read from storage by a single request filtering events by type
+    pub max_events_scanned_by_type_filter: u64,
     /// Maximum page size for resource paginated APIs
     pub max_account_resources_page_size: u16,
     /// Maximum page size for module paginated APIs
@@ -74,6 +84,77 @@ pub struct ApiConfig {
     pub gas_estimation: GasEstimationConfig,
     /// Periodically call gas estimation
     pub periodic_gas_estimation_ms: Option<u64>,
+    /// Maximum number of seconds a transaction lookup by hash can wait for the transaction
+    /// to be committed, via `wait_for_seconds`
+    pub max_wait_for_transaction_secs: u64,
+    /// How often storage is checked for newly committed versions, to notify the transaction
+    /// lookups by hash waiting on them
+    pub committed_version_check_interval_ms: u64,
+    /// Maximum number of transaction lookups by hash that can wait at the same time
+    ///
+    /// Lookups beyond this limit return immediately, as if no wait was requested
+    pub max_wait_for_transaction_connections: usize,
+    /// Configs for caching frequently requested resources and gas estimations
+    pub resource_cache: ResourceCacheConfig,
+    /// Configs for compressed request and response bodies
+    pub compression: CompressionConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResourceCacheConfig {
+    /// A gate for caching resources and gas estimations per ledger version. If false, all
+    /// resources are read from storage and gas estimations are only cached per epoch.
+    pub enabled: bool,
+    /// Maximum number of resources kept in the cache
+    pub max_entries: usize,
+    /// Time after write when a cached resource or gas estimation is no longer returned, even at
+    /// the same version
+    pub ttl_ms: u64,
+    /// Resource types to cache (e.g. `0x1::chain_id::ChainId`). Other resources are always
+    /// read from storage.
+    pub hot_resources: Vec<String>,
+}
+
+impl Default for ResourceCacheConfig {
+    fn default() -> ResourceCacheConfig {
+        ResourceCacheConfig {
+            enabled: true,
+            max_entries: 1_000,
+            ttl_ms: 1_000,
+            hot_resources: vec!["0x1::chain_id::ChainId".to_string()],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompressionConfig {
+    /// Accepts request bodies with a `Content-Encoding` of gzip or zstd, and compresses
+    /// responses for clients that send a matching `Accept-Encoding`
+    pub enabled: bool,
+    /// Compression level for gzip responses, from 0 (none) to 9 (best)
+    pub gzip_level: u32,
+    /// Compression level for zstd responses, from 1 (fastest) to 22 (best)
+    pub zstd_level: i32,
+    /// A maximum limit to the body of a compressed request in bytes, after decompression.
+    /// Unlike `content_length_limit`, which applies to the compressed body, this guards
+    /// against decompression bombs.
+    pub max_decompressed_content_length: u64,
+    /// Responses with smaller bodies (in bytes) are sent uncompressed
+    pub min_response_size: usize,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> CompressionConfig {
+        CompressionConfig {
+            enabled: false,
+            gzip_level: 6,
+            zstd_level: 3,
+            max_decompressed_content_length: DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT,
+            min_response_size: 1024,
+        }
+    }
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -81,8 +162,12 @@ const DEFAULT_PORT: u16 = 8080;
 const DEFAULT_REQUEST_CONTENT_LENGTH_LIMIT: u64 = 8 * 1024 * 1024; // 8 MB
 pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 10;
 pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
+const DEFAULT_MAX_EVENTS_SCANNED_BY_TYPE_FILTER: u64 = 10_000;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
+const DEFAULT_MAX_WAIT_FOR_TRANSACTION_SECS: u64 = 30;
+const DEFAULT_COMMITTED_VERSION_CHECK_INTERVAL_MS: u64 = 100;
+const DEFAULT_MAX_WAIT_FOR_TRANSACTION_CONNECTIONS: usize = 100;
 const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
 
 fn default_enabled() -> bool {
@@ -103,15 +188,18 @@ impl Default for ApiConfig {
             tls_cert_path: None,
             tls_key_path: None,
             content_length_limit: None,
+            route_content_length_limits: BTreeMap::new(),
             failpoints_enabled: default_disabled(),
             bcs_output_enabled: default_enabled(),
             json_output_enabled: default_enabled(),
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
+            entry_function_payloads_only: default_disabled(),
             transaction_simulation_enabled: default_enabled(),
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
+            max_events_scanned_by_type_filter: DEFAULT_MAX_EVENTS_SCANNED_BY_TYPE_FILTER,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
             max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
@@ -119,6 +207,11 @@ impl Default for ApiConfig {
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
             periodic_gas_estimation_ms: Some(30_000),
+            max_wait_for_transaction_secs: DEFAULT_MAX_WAIT_FOR_TRANSACTION_SECS,
+            committed_version_check_interval_ms: DEFAULT_COMMITTED_VERSION_CHECK_INTERVAL_MS,
+            max_wait_for_transaction_connections: DEFAULT_MAX_WAIT_FOR_TRANSACTION_CONNECTIONS,
+            resource_cache: ResourceCacheConfig::default(),
+            compression: CompressionConfig::default(),
         }
     }
 }
@@ -168,6 +261,43 @@ impl ConfigSanitizer for ApiConfig {
             ));
         }
 
+        // Verify that checking for committed versions doesn't busy loop
+        if api_config.max_wait_for_transaction_secs > 0
+            && api_config.committed_version_check_interval_ms == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "committed_version_check_interval_ms must be greater than 0!".into(),
+            ));
+        }
+
+        // Verify that an enabled resource cache can hold entries
+        if api_config.resource_cache.enabled && api_config.resource_cache.max_entries == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "resource_cache.max_entries must be greater than 0!".into(),
+            ));
+        }
+
+        // Verify that filtering events by type can make progress
+        if api_config.max_events_scanned_by_type_filter == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "max_events_scanned_by_type_filter must be greater than 0!".into(),
+            ));
+        }
+
+        // Verify that the compression levels are supported
+        let compression = &api_config.compression;
+        if compression.enabled
+            && (compression.gzip_level > 9 || !(1..=22).contains(&compression.zstd_level))
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "compression.gzip_level must be at most 9, and compression.zstd_level must be between 1 and 22!".into(),
+            ));
+        }
+
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
@@ -241,4 +371,72 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_empty_resource_cache() {
+        // Create a node config with an enabled resource cache that can't hold any entries
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                resource_cache: ResourceCacheConfig {
+                    enabled: true,
+                    max_entries: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because the cache is empty
+        let error =
+            ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_no_events_scanned_by_type_filter() {
+        // Create a node config that doesn't scan any events when filtering by type
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                enabled: true,
+                max_events_scanned_by_type_filter: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails because no progress can be made
+        let error =
+            ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_invalid_compression_levels() {
+        for (gzip_level, zstd_level) in [(10, 3), (6, 0), (6, 23)] {
+            // Create a node config with an unsupported compression level
+            let node_config = NodeConfig {
+                api: ApiConfig {
+                    enabled: true,
+                    compression: CompressionConfig {
+                        enabled: true,
+                        gzip_level,
+                        zstd_level,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            // Sanitize the config and verify that it fails because of the level
+            let error =
+                ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                    .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+    }
 }
@@ -5,8 +5,9 @@ use crate::config::{
     node_config_loader::NodeType,
     utils::{are_failpoints_enabled, get_config_name},
     AdminServiceConfig, ApiConfig, BaseConfig, ConsensusConfig, DagConsensusConfig, Error,
-    ExecutionConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
-    NetbenchConfig, NodeConfig, PeerMonitoringServiceConfig, StateSyncConfig, StorageConfig,
+    ExecutionConfig, IndexerGrpcConfig, InspectionServiceConfig, JWKConsensusConfig, LoggerConfig,
+    MempoolConfig, NetbenchConfig, NodeConfig, PeerMonitoringServiceConfig, StateSyncConfig,
+    StorageConfig,
 };
 use aptos_types::chain_id::ChainId;
 use std::collections::HashSet;
@@ -57,6 +58,7 @@ impl ConfigSanitizer for NodeConfig {
         sanitize_fullnode_network_configs(node_config, node_type, chain_id)?;
         IndexerGrpcConfig::sanitize(node_config, node_type, chain_id)?;
         InspectionServiceConfig::sanitize(node_config, node_type, chain_id)?;
+        JWKConsensusConfig::sanitize(node_config, node_type, chain_id)?;
         LoggerConfig::sanitize(node_config, node_type, chain_id)?;
         MempoolConfig::sanitize(node_config, node_type, chain_id)?;
         NetbenchConfig::sanitize(node_config, node_type, chain_id)?;
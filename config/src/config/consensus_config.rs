@@ -58,6 +58,11 @@ pub struct ConsensusConfig {
     pub wait_for_full_blocks_above_pending_blocks: usize,
     pub wait_for_full_blocks_above_recent_fill_threshold: f32,
     pub intra_consensus_channel_buffer_size: usize,
+    // How long committed transactions are remembered so they can be dropped from pulled payloads
+    // (in milliseconds), covering the window before mempool learns about the commit
+    pub recently_committed_txn_filter_window_ms: u64,
+    // Max number of committed transactions remembered, regardless of the window
+    pub recently_committed_txn_filter_max_txns: usize,
     pub quorum_store: QuorumStoreConfig,
     pub vote_back_pressure_limit: u64,
     pub pipeline_backpressure: Vec<PipelineBackpressureValues>,
@@ -175,6 +180,8 @@ impl Default for ConsensusConfig {
             // Max is 1, so 1.1 disables it.
             wait_for_full_blocks_above_recent_fill_threshold: 1.1,
             intra_consensus_channel_buffer_size: 10,
+            recently_committed_txn_filter_window_ms: 10_000,
+            recently_committed_txn_filter_max_txns: 100_000,
             quorum_store: QuorumStoreConfig::default(),
 
             // Voting backpressure is only used as a backup, to make sure pending rounds don't
@@ -1,17 +1,75 @@
 // Copyright © Aptos Foundation
 
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct JWKConsensusConfig {
     pub max_network_channel_size: usize,
+    /// Number of worker threads of the JWK consensus runtime
+    pub runtime_worker_threads: usize,
 }
 
 impl Default for JWKConsensusConfig {
     fn default() -> Self {
         Self {
             max_network_channel_size: 256,
+            runtime_worker_threads: 4,
         }
     }
 }
+
+impl ConfigSanitizer for JWKConsensusConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+
+        // Verify that the runtime has worker threads (tokio panics otherwise)
+        if node_config.jwk_consensus.runtime_worker_threads == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "runtime_worker_threads must be greater than 0!".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_no_runtime_worker_threads() {
+        // Create a node config with no worker threads for the JWK consensus runtime
+        let node_config = NodeConfig {
+            jwk_consensus: JWKConsensusConfig {
+                runtime_worker_threads: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error =
+            JWKConsensusConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::test()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the default config passes sanitization
+        JWKConsensusConfig::sanitize(
+            &NodeConfig::default(),
+            NodeType::Validator,
+            Some(ChainId::test()),
+        )
+        .unwrap();
+    }
+}
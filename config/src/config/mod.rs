@@ -52,6 +52,7 @@ pub use indexer_config::*;
 pub use indexer_grpc_config::*;
 pub use indexer_table_info_config::*;
 pub use inspection_service_config::*;
+pub use jwk_consensus_config::*;
 pub use logger_config::*;
 pub use mempool_config::*;
 pub use netbench_config::*;
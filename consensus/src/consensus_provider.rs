@@ -7,6 +7,7 @@ use crate::{
     epoch_manager::EpochManager,
     network::NetworkTask,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
+    payload_client::user::recently_committed_filter::RecentlyCommittedFilter,
     persistent_liveness_storage::StorageWriteProxy,
     quorum_store::quorum_store_db::QuorumStoreDB,
     state_computer::ExecutionProxy,
@@ -26,7 +27,7 @@ use aptos_storage_interface::DbReaderWriter;
 use aptos_validator_transaction_pool as vtxn_pool;
 use aptos_vm::AptosVM;
 use futures::channel::mpsc;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
 
 /// Helper function to start consensus based on configuration and return the runtime
@@ -49,12 +50,29 @@ pub fn start_consensus(
         node_config.consensus.mempool_executed_txn_timeout_ms,
     ));
 
+    let recently_committed_filter = Arc::new(RecentlyCommittedFilter::new(
+        Duration::from_millis(
+            node_config
+                .consensus
+                .recently_committed_txn_filter_window_ms,
+        ),
+        node_config.consensus.recently_committed_txn_filter_max_txns,
+    ));
+    let (committed_txns_sender, committed_txns_receiver) =
+        mpsc::channel(node_config.consensus.intra_consensus_channel_buffer_size);
+    runtime.spawn(
+        recently_committed_filter
+            .clone()
+            .start(committed_txns_receiver),
+    );
+
     let state_computer = Arc::new(ExecutionProxy::new(
         Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
         TransactionFilter::new(node_config.execution.transaction_filter.clone()),
+        Some(committed_txns_sender),
     ));
 
     let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
@@ -79,6 +97,7 @@ pub fn start_consensus(
         bounded_executor,
         aptos_time_service::TimeService::real(),
         validator_txn_pool_client,
+        recently_committed_filter,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
@@ -256,6 +256,15 @@ pub static WAIT_FOR_FULL_BLOCKS_TRIGGERED: Lazy<Histogram> = Lazy::new(|| {
     )
 });
 
+/// Count of the pulled user transactions dropped because they were recently committed
+pub static RECENTLY_COMMITTED_TXNS_FILTERED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_recently_committed_txns_filtered",
+        "Count of the pulled user transactions dropped because they were recently committed"
+    )
+    .unwrap()
+});
+
 /// Counts when chain_health backoff is triggered
 pub static PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED: Lazy<Histogram> = Lazy::new(|| {
     register_avg_counter(
@@ -33,8 +33,15 @@ use crate::{
     },
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     payload_client::{
-        mixed::MixedPayloadClient, user::quorum_store_client::QuorumStoreClient,
-        validator::ValidatorTxnPayloadClient, PayloadClient,
+        mixed::MixedPayloadClient,
+        user::{
+            quorum_store_client::QuorumStoreClient,
+            recently_committed_filter::{
+                RecentlyCommittedFilter, RecentlyCommittedFilteringClient,
+            },
+        },
+        validator::ValidatorTxnPayloadClient,
+        PayloadClient,
     },
     payload_manager::PayloadManager,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
@@ -163,6 +170,7 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     dag_shutdown_tx: Option<oneshot::Sender<oneshot::Sender<()>>>,
     dag_config: DagConsensusConfig,
     payload_manager: Arc<PayloadManager>,
+    recently_committed_filter: Arc<RecentlyCommittedFilter>,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
@@ -180,6 +188,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         bounded_executor: BoundedExecutor,
         aptos_time_service: aptos_time_service::TimeService,
         validator_txn_pool_client: vtxn_pool::ReadClient,
+        recently_committed_filter: Arc<RecentlyCommittedFilter>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -221,6 +230,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             aptos_time_service,
             dag_config,
             payload_manager: Arc::new(PayloadManager::DirectMempool),
+            recently_committed_filter,
         }
     }
 
@@ -1028,7 +1038,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let mixed_payload_client = MixedPayloadClient::new(
             consensus_config.validator_txn_enabled(),
             self.validator_txn_pool_client.clone(),
-            Arc::new(quorum_store_client),
+            Arc::new(RecentlyCommittedFilteringClient::new(
+                Arc::new(quorum_store_client),
+                self.recently_committed_filter.clone(),
+            )),
         );
         self.init_commit_state_computer(epoch_state, payload_manager.clone(), execution_config);
         self.start_quorum_store(quorum_store_builder);
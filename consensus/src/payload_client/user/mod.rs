@@ -77,3 +77,4 @@ impl UserPayloadClient for DummyClient {
 }
 
 pub mod quorum_store_client;
+pub mod recently_committed_filter;
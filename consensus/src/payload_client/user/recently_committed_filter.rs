@@ -0,0 +1,311 @@
+// Copyright © Aptos Foundation
+
+use crate::{
+    counters::RECENTLY_COMMITTED_TXNS_FILTERED, error::QuorumStoreError,
+    payload_client::user::UserPayloadClient,
+};
+use aptos_consensus_types::common::{Payload, PayloadFilter, TransactionSummary};
+use aptos_infallible::Mutex;
+use aptos_logger::debug;
+use futures::{channel::mpsc, future::BoxFuture, StreamExt};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Remembers the user transactions committed within a recent time window, so they can be dropped
+/// from pulled payloads. After a block is committed there is a window in which mempool hasn't
+/// been notified yet, and the proposer could otherwise pull the committed transactions again.
+///
+/// Memory is bounded: transactions are forgotten once they fall out of the window, or once more
+/// than `max_txns` newer transactions have been recorded.
+pub struct RecentlyCommittedFilter {
+    window: Duration,
+    max_txns: usize,
+    committed: Mutex<RecentlyCommitted>,
+}
+
+#[derive(Default)]
+struct RecentlyCommitted {
+    // The committed transactions in the order they were recorded, with a record counter
+    queue: VecDeque<(u64, Instant, TransactionSummary)>,
+    // The counter of the latest record of each transaction in the queue
+    latest_record: HashMap<TransactionSummary, u64>,
+    next_record: u64,
+}
+
+impl RecentlyCommitted {
+    fn push(&mut self, txn: TransactionSummary, now: Instant) {
+        self.queue.push_back((self.next_record, now, txn));
+        self.latest_record.insert(txn, self.next_record);
+        self.next_record += 1;
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some((record, _, txn)) = self.queue.pop_front() {
+            // Keep transactions that were recorded again later, they are still in the queue
+            if self.latest_record.get(&txn) == Some(&record) {
+                self.latest_record.remove(&txn);
+            }
+        }
+    }
+
+    fn prune(&mut self, window: Duration, max_txns: usize, now: Instant) {
+        while self.queue.len() > max_txns {
+            self.pop_oldest();
+        }
+        while let Some((_, time, _)) = self.queue.front() {
+            if now.saturating_duration_since(*time) < window {
+                break;
+            }
+            self.pop_oldest();
+        }
+    }
+}
+
+impl RecentlyCommittedFilter {
+    pub fn new(window: Duration, max_txns: usize) -> Self {
+        Self {
+            window,
+            max_txns,
+            committed: Mutex::new(RecentlyCommitted::default()),
+        }
+    }
+
+    /// Records newly committed transactions, returning once the sender is dropped.
+    pub async fn start(
+        self: Arc<Self>,
+        mut committed_txns_rx: mpsc::Receiver<Vec<TransactionSummary>>,
+    ) {
+        while let Some(txns) = committed_txns_rx.next().await {
+            self.record(txns);
+        }
+        debug!("Recently committed filter stopped");
+    }
+
+    pub fn record(&self, txns: Vec<TransactionSummary>) {
+        self.record_at(txns, Instant::now());
+    }
+
+    fn record_at(&self, txns: Vec<TransactionSummary>, now: Instant) {
+        let mut committed = self.committed.lock();
+        for txn in txns {
+            committed.push(txn, now);
+        }
+        committed.prune(self.window, self.max_txns, now);
+    }
+
+    /// Drops the recently committed transactions from the payload. Quorum store payloads only
+    /// reference batches, so they are returned as is.
+    pub fn filter(&self, payload: Payload) -> Payload {
+        self.filter_at(payload, Instant::now())
+    }
+
+    fn filter_at(&self, payload: Payload, now: Instant) -> Payload {
+        match payload {
+            Payload::DirectMempool(mut txns) => {
+                let mut committed = self.committed.lock();
+                committed.prune(self.window, self.max_txns, now);
+                if committed.latest_record.is_empty() {
+                    return Payload::DirectMempool(txns);
+                }
+
+                let num_pulled = txns.len();
+                txns.retain(|txn| {
+                    !committed
+                        .latest_record
+                        .contains_key(&TransactionSummary::new(
+                            txn.sender(),
+                            txn.sequence_number(),
+                        ))
+                });
+                RECENTLY_COMMITTED_TXNS_FILTERED.inc_by((num_pulled - txns.len()) as u64);
+                Payload::DirectMempool(txns)
+            },
+            payload @ Payload::InQuorumStore(_) => payload,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.committed.lock().latest_record.len()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A user payload client that drops recently committed transactions from the payloads pulled
+/// by the inner client.
+pub struct RecentlyCommittedFilteringClient {
+    inner: Arc<dyn UserPayloadClient>,
+    filter: Arc<RecentlyCommittedFilter>,
+}
+
+impl RecentlyCommittedFilteringClient {
+    pub fn new(inner: Arc<dyn UserPayloadClient>, filter: Arc<RecentlyCommittedFilter>) -> Self {
+        Self { inner, filter }
+    }
+}
+
+#[async_trait::async_trait]
+impl UserPayloadClient for RecentlyCommittedFilteringClient {
+    async fn pull(
+        &self,
+        max_poll_time: Duration,
+        max_items: u64,
+        max_bytes: u64,
+        exclude: PayloadFilter,
+        wait_callback: BoxFuture<'static, ()>,
+        pending_ordering: bool,
+        pending_uncommitted_blocks: usize,
+        recent_max_fill_fraction: f32,
+    ) -> anyhow::Result<Payload, QuorumStoreError> {
+        let payload = self
+            .inner
+            .pull(
+                max_poll_time,
+                max_items,
+                max_bytes,
+                exclude,
+                wait_callback,
+                pending_ordering,
+                pending_uncommitted_blocks,
+                recent_max_fill_fraction,
+            )
+            .await?;
+        Ok(self.filter.filter(payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{payload_client::user::DummyClient, test_utils::create_vec_signed_transactions};
+    use aptos_consensus_types::common::ProofWithData;
+    use aptos_types::transaction::SignedTransaction;
+    use futures::SinkExt;
+
+    fn summary(txn: &SignedTransaction) -> TransactionSummary {
+        TransactionSummary::new(txn.sender(), txn.sequence_number())
+    }
+
+    async fn pull(client: &RecentlyCommittedFilteringClient) -> Vec<SignedTransaction> {
+        let payload = client
+            .pull(
+                Duration::from_millis(50), // max_poll_time
+                99,                        // max_items
+                1048576,                   // size limit: 1MB
+                PayloadFilter::Empty,
+                Box::pin(async {}),
+                false,
+                0,
+                0.,
+            )
+            .await
+            .unwrap();
+        match payload {
+            Payload::DirectMempool(txns) => txns,
+            Payload::InQuorumStore(_) => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_filter_removes_committed_txns() {
+        let all_txns = create_vec_signed_transactions(10);
+        let filter = Arc::new(RecentlyCommittedFilter::new(Duration::from_secs(10), 100));
+        let client = RecentlyCommittedFilteringClient::new(
+            Arc::new(DummyClient::new(all_txns.clone())),
+            filter.clone(),
+        );
+        assert_eq!(pull(&client).await, all_txns);
+
+        // Commit every other transaction through the channel
+        let (mut committed_txns_tx, committed_txns_rx) = mpsc::channel(10);
+        let listener = tokio::spawn(filter.clone().start(committed_txns_rx));
+        let committed: Vec<_> = all_txns.iter().step_by(2).map(summary).collect();
+        committed_txns_tx.send(committed).await.unwrap();
+        drop(committed_txns_tx);
+        listener.await.unwrap();
+        assert_eq!(filter.len(), 5);
+
+        let expected: Vec<_> = all_txns.iter().skip(1).step_by(2).cloned().collect();
+        assert_eq!(pull(&client).await, expected);
+    }
+
+    #[test]
+    fn test_filter_forgets_txns_outside_window() {
+        let txns = create_vec_signed_transactions(4);
+        let filter = RecentlyCommittedFilter::new(Duration::from_secs(10), 100);
+        let start = Instant::now();
+        filter.record_at(vec![summary(&txns[0])], start);
+        filter.record_at(vec![summary(&txns[1])], start + Duration::from_secs(5));
+
+        let Payload::DirectMempool(pulled) = filter.filter_at(
+            Payload::DirectMempool(txns.clone()),
+            start + Duration::from_secs(9),
+        ) else {
+            unreachable!()
+        };
+        assert_eq!(pulled, txns[2..]);
+
+        // The first transaction has fallen out of the window
+        let Payload::DirectMempool(pulled) = filter.filter_at(
+            Payload::DirectMempool(txns.clone()),
+            start + Duration::from_secs(10),
+        ) else {
+            unreachable!()
+        };
+        assert_eq!(pulled, [&txns[..1], &txns[2..]].concat());
+        assert_eq!(filter.len(), 1);
+
+        let Payload::DirectMempool(pulled) = filter.filter_at(
+            Payload::DirectMempool(txns.clone()),
+            start + Duration::from_secs(15),
+        ) else {
+            unreachable!()
+        };
+        assert_eq!(pulled, txns);
+        assert!(filter.is_empty());
+    }
+
+    #[test]
+    fn test_filter_is_bounded() {
+        let txns = create_vec_signed_transactions(10);
+        let filter = RecentlyCommittedFilter::new(Duration::from_secs(10), 3);
+        let now = Instant::now();
+        filter.record_at(txns.iter().map(summary).collect(), now);
+        assert_eq!(filter.len(), 3);
+
+        // Only the most recently recorded transactions are remembered
+        let Payload::DirectMempool(pulled) =
+            filter.filter_at(Payload::DirectMempool(txns.clone()), now)
+        else {
+            unreachable!()
+        };
+        assert_eq!(pulled, txns[..7]);
+
+        // Recording a transaction again keeps it around until its latest entry is evicted
+        filter.record_at(vec![summary(&txns[7])], now);
+        filter.record_at(vec![summary(&txns[0])], now);
+        assert_eq!(filter.len(), 3);
+        let Payload::DirectMempool(pulled) =
+            filter.filter_at(Payload::DirectMempool(txns.clone()), now)
+        else {
+            unreachable!()
+        };
+        assert_eq!(pulled, [&txns[1..7], &txns[8..9]].concat());
+    }
+
+    #[test]
+    fn test_filter_ignores_quorum_store_payloads() {
+        let txns = create_vec_signed_transactions(1);
+        let filter = RecentlyCommittedFilter::new(Duration::from_secs(10), 100);
+        filter.record(vec![summary(&txns[0])]);
+        let payload = Payload::InQuorumStore(ProofWithData::new(vec![]));
+        assert_eq!(filter.filter(payload.clone()), payload);
+    }
+}
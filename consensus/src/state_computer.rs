@@ -18,7 +18,11 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_consensus_notifications::ConsensusNotificationSender;
-use aptos_consensus_types::{block::Block, common::Round, executed_block::ExecutedBlock};
+use aptos_consensus_types::{
+    block::Block,
+    common::{Round, TransactionSummary},
+    executed_block::ExecutedBlock,
+};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{BlockExecutorTrait, ExecutorResult, StateComputeResult};
 use aptos_infallible::Mutex;
@@ -33,7 +37,7 @@ use aptos_types::{
     transaction::{SignedTransaction, Transaction},
 };
 use fail::fail_point;
-use futures::{future::BoxFuture, SinkExt, StreamExt};
+use futures::{channel::mpsc, future::BoxFuture, SinkExt, StreamExt};
 use std::{boxed::Box, sync::Arc};
 use tokio::sync::Mutex as AsyncMutex;
 
@@ -100,12 +104,16 @@ impl ExecutionProxy {
         state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
         handle: &tokio::runtime::Handle,
         txn_filter: TransactionFilter,
+        mut committed_txns_sender: Option<mpsc::Sender<Vec<TransactionSummary>>>,
     ) -> Self {
         let (tx, mut rx) =
             aptos_channels::new::<NotificationType>(10, &counters::PENDING_STATE_SYNC_NOTIFICATION);
         let notifier = state_sync_notifier.clone();
         handle.spawn(async move {
             while let Some((callback, txns, subscribable_events)) = rx.next().await {
+                if let Some(committed_txns_sender) = committed_txns_sender.as_mut() {
+                    notify_committed_txns(committed_txns_sender, &txns);
+                }
                 if let Err(e) = monitor!(
                     "notify_state_sync",
                     notifier.notify_new_commit(txns, subscribable_events).await
@@ -136,6 +144,30 @@ impl ExecutionProxy {
     }
 }
 
+/// Lets the recently committed filter know about the committed user transactions. This is best
+/// effort: the notification is dropped rather than holding up state sync if the filter lags.
+pub(crate) fn notify_committed_txns(
+    committed_txns_sender: &mut mpsc::Sender<Vec<TransactionSummary>>,
+    txns: &[Transaction],
+) {
+    let committed_txns: Vec<_> = txns
+        .iter()
+        .filter_map(|txn| match txn {
+            Transaction::UserTransaction(signed_txn) => Some(TransactionSummary::new(
+                signed_txn.sender(),
+                signed_txn.sequence_number(),
+            )),
+            _ => None,
+        })
+        .collect();
+    if committed_txns.is_empty() {
+        return;
+    }
+    if let Err(e) = committed_txns_sender.try_send(committed_txns) {
+        warn!(error = ?e, "Failed to notify the recently committed filter");
+    }
+}
+
 #[async_trait::async_trait]
 impl StateComputer for ExecutionProxy {
     async fn schedule_compute(
@@ -469,6 +501,7 @@ async fn test_commit_sync_race() {
         recorded_commit.clone(),
         &tokio::runtime::Handle::current(),
         TransactionFilter::new(Filter::empty()),
+        None,
     );
 
     executor.new_epoch(
@@ -1,14 +1,21 @@
 // Copyright © Aptos Foundation
 
 use crate::{
-    error::MempoolError, payload_manager::PayloadManager, state_computer::ExecutionProxy,
-    state_replication::StateComputer, transaction_deduper::NoOpDeduper,
-    transaction_filter::TransactionFilter, transaction_shuffler::NoOpShuffler,
+    error::MempoolError,
+    payload_manager::PayloadManager,
+    state_computer::{notify_committed_txns, ExecutionProxy},
+    state_replication::StateComputer,
+    test_utils::create_vec_signed_transactions,
+    transaction_deduper::NoOpDeduper,
+    transaction_filter::TransactionFilter,
+    transaction_shuffler::NoOpShuffler,
     txn_notifier::TxnNotifier,
 };
 use aptos_config::config::transaction_filter_type::Filter;
 use aptos_consensus_notifications::{ConsensusNotificationSender, Error};
-use aptos_consensus_types::{block::Block, block_data::BlockData, executed_block::ExecutedBlock};
+use aptos_consensus_types::{
+    block::Block, block_data::BlockData, common::TransactionSummary, executed_block::ExecutedBlock,
+};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{
     state_checkpoint_output::StateCheckpointOutput, BlockExecutorTrait, ExecutorResult,
@@ -24,6 +31,7 @@ use aptos_types::{
     transaction::{ExecutionStatus, SignedTransaction, Transaction, TransactionStatus},
     validator_txn::ValidatorTransaction,
 };
+use futures::channel::mpsc;
 use futures_channel::oneshot;
 use std::sync::Arc;
 use tokio::runtime::Handle;
@@ -143,6 +151,7 @@ async fn schedule_compute_should_discover_validator_txns() {
         Arc::new(DummyStateSyncNotifier::new()),
         &Handle::current(),
         TransactionFilter::new(Filter::empty()),
+        None,
     );
 
     let validator_txn_0 = ValidatorTransaction::dummy1(vec![0xFF; 99]);
@@ -195,6 +204,7 @@ async fn commit_should_discover_validator_txns() {
         state_sync_notifier.clone(),
         &tokio::runtime::Handle::current(),
         TransactionFilter::new(Filter::empty()),
+        None,
     );
 
     let validator_txn_0 = ValidatorTransaction::dummy1(vec![0xFF; 99]);
@@ -259,3 +269,27 @@ async fn commit_should_discover_validator_txns() {
     assert_eq!(&validator_txn_0, supposed_validator_txn_0);
     assert_eq!(&validator_txn_1, supposed_validator_txn_1);
 }
+
+#[test]
+fn notify_committed_txns_should_drop_when_channel_is_full() {
+    // The channel holds one message for its buffer, and one for the single sender
+    let (mut committed_txns_sender, mut committed_txns_receiver) = mpsc::channel(1);
+    let txns = create_vec_signed_transactions(3);
+    for txn in &txns {
+        notify_committed_txns(&mut committed_txns_sender, &[Transaction::UserTransaction(
+            txn.clone(),
+        )]);
+    }
+
+    for txn in &txns[..2] {
+        assert_eq!(
+            committed_txns_receiver.try_next().unwrap(),
+            Some(vec![TransactionSummary::new(
+                txn.sender(),
+                txn.sequence_number()
+            )])
+        );
+    }
+    // The last notification was dropped rather than queued
+    assert!(committed_txns_receiver.try_next().is_err());
+}
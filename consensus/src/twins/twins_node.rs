@@ -8,6 +8,7 @@ use crate::{
     network::NetworkTask,
     network_interface::{ConsensusNetworkClient, DIRECT_SEND, RPC},
     network_tests::{NetworkPlayground, TwinId},
+    payload_client::user::recently_committed_filter::RecentlyCommittedFilter,
     payload_manager::PayloadManager,
     pipeline::buffer_manager::OrderedBlocks,
     quorum_store::quorum_store_db::MockQuorumStoreDB,
@@ -50,7 +51,7 @@ use aptos_types::{
 use aptos_validator_transaction_pool as vtxn_pool;
 use futures::{channel::mpsc, StreamExt};
 use maplit::hashmap;
-use std::{collections::HashMap, iter::FromIterator, sync::Arc};
+use std::{collections::HashMap, iter::FromIterator, sync::Arc, time::Duration};
 use tokio::runtime::Runtime;
 
 /// Auxiliary struct that is preparing SMR for the test
@@ -161,6 +162,10 @@ impl SMRNode {
             bounded_executor,
             aptos_time_service::TimeService::real(),
             validator_txn_pool_client,
+            Arc::new(RecentlyCommittedFilter::new(
+                Duration::from_millis(config.consensus.recently_committed_txn_filter_window_ms),
+                config.consensus.recently_committed_txn_filter_max_txns,
+            )),
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
@@ -0,0 +1,318 @@
+// Copyright © Aptos Foundation
+
+// Latency tracking turns the API tester into a latency canary. Every REST call made by the flows
+// is timed and recorded per operation, so that p50 and p95 latencies can be reported at the end
+// of the run. Optionally, each operation can be given a latency budget, in which case a call that
+// takes longer than its budget fails the step it belongs to.
+
+use crate::{strings::FAIL_OVER_LATENCY_BUDGET, utils::TestFailure};
+use anyhow::{anyhow, bail, Error, Result};
+use aptos_logger::{error, info};
+use futures::Future;
+use once_cell::sync::{Lazy, OnceCell};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+    sync::Mutex,
+    time::Duration,
+};
+use tokio::time::Instant;
+
+// Operation
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Operation {
+    CreateAccount,
+    Fund,
+    GetAccount,
+    GetAccountBalance,
+    GetAccountBalanceAtVersion,
+    GetAccountModule,
+    View,
+    WaitForTransaction,
+}
+
+impl Operation {
+    const ALL: [Operation; 8] = [
+        Operation::CreateAccount,
+        Operation::Fund,
+        Operation::GetAccount,
+        Operation::GetAccountBalance,
+        Operation::GetAccountBalanceAtVersion,
+        Operation::GetAccountModule,
+        Operation::View,
+        Operation::WaitForTransaction,
+    ];
+}
+
+impl ToString for Operation {
+    fn to_string(&self) -> String {
+        match &self {
+            Operation::CreateAccount => "create_account".to_string(),
+            Operation::Fund => "fund".to_string(),
+            Operation::GetAccount => "get_account".to_string(),
+            Operation::GetAccountBalance => "get_account_balance".to_string(),
+            Operation::GetAccountBalanceAtVersion => "get_account_balance_at_version".to_string(),
+            Operation::GetAccountModule => "get_account_module".to_string(),
+            Operation::View => "view".to_string(),
+            Operation::WaitForTransaction => "wait_for_transaction".to_string(),
+        }
+    }
+}
+
+impl FromStr for Operation {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Operation::ALL
+            .into_iter()
+            .find(|operation| operation.to_string() == s)
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown operation '{}', expected one of: {}",
+                    s,
+                    Operation::ALL
+                        .iter()
+                        .map(|operation| operation.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+// Latency budgets
+
+/// Per operation latency budgets, parsed from e.g. `get_account=500ms,wait_for_transaction=30s`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LatencyBudgets(HashMap<Operation, Duration>);
+
+impl LatencyBudgets {
+    /// Fail with the measured value if `elapsed` exceeds the budget of `operation`.
+    pub fn check(&self, operation: Operation, elapsed: Duration) -> Result<(), TestFailure> {
+        match self.0.get(&operation) {
+            Some(budget) if elapsed > *budget => {
+                let message = format!(
+                    "{}: {} took {:?}, budget is {:?}",
+                    FAIL_OVER_LATENCY_BUDGET,
+                    operation.to_string(),
+                    elapsed,
+                    budget
+                );
+                error!("{}", message);
+                Err(TestFailure::Fail(message))
+            },
+            _ => Ok(()),
+        }
+    }
+}
+
+impl FromStr for LatencyBudgets {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut budgets = HashMap::new();
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (operation, budget) = entry.split_once('=').ok_or_else(|| {
+                anyhow!(
+                    "invalid budget '{}', expected <operation>=<duration>",
+                    entry
+                )
+            })?;
+            let operation: Operation = operation.trim().parse()?;
+            let budget = parse_duration(budget.trim())?;
+            if budgets.insert(operation, budget).is_some() {
+                bail!(
+                    "budget for operation '{}' is set more than once",
+                    operation.to_string()
+                );
+            }
+        }
+        Ok(LatencyBudgets(budgets))
+    }
+}
+
+/// Parse a duration with a unit suffix, e.g. `500ms`, `30s` or `2m`.
+fn parse_duration(s: &str) -> Result<Duration> {
+    let (value, to_duration): (&str, fn(u64) -> Duration) = if let Some(ms) = s.strip_suffix("ms") {
+        (ms, Duration::from_millis)
+    } else if let Some(secs) = s.strip_suffix('s') {
+        (secs, Duration::from_secs)
+    } else if let Some(mins) = s.strip_suffix('m') {
+        (mins, |mins| Duration::from_secs(mins * 60))
+    } else {
+        bail!("invalid duration '{}', expected a unit of ms, s or m", s);
+    };
+    let value = value
+        .parse()
+        .map_err(|e| anyhow!("invalid duration '{}': {}", s, e))?;
+    Ok(to_duration(value))
+}
+
+// The budgets in effect for this run, set once at startup.
+pub static LATENCY_BUDGETS: OnceCell<LatencyBudgets> = OnceCell::new();
+
+// Latency tracking
+
+// Latencies of every timed call made during this run, by operation.
+static LATENCIES: Lazy<Mutex<HashMap<Operation, Vec<Duration>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Time a REST call, record its latency and check it against the budget for `operation`. The
+/// output of the call is returned as is, so callers can handle API errors as before.
+pub async fn timed<Fut: Future>(
+    operation: Operation,
+    future: Fut,
+) -> Result<Fut::Output, TestFailure> {
+    let start = Instant::now();
+    let output = future.await;
+    let elapsed = start.elapsed();
+
+    LATENCIES
+        .lock()
+        .unwrap()
+        .entry(operation)
+        .or_default()
+        .push(elapsed);
+    if let Some(budgets) = LATENCY_BUDGETS.get() {
+        budgets.check(operation, elapsed)?;
+    }
+
+    Ok(output)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+}
+
+/// Summarize latency samples using the nearest rank percentiles. Returns None if there are none.
+fn summarize(samples: &[Duration]) -> Option<LatencySummary> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let percentile = |p: usize| sorted[((sorted.len() * p + 99) / 100).max(1) - 1];
+
+    Some(LatencySummary {
+        count: sorted.len(),
+        p50: percentile(50),
+        p95: percentile(95),
+    })
+}
+
+/// Log p50 and p95 latencies for every operation called during the run.
+pub fn report() {
+    let summaries: BTreeMap<Operation, LatencySummary> = LATENCIES
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|(operation, samples)| Some((*operation, summarize(samples)?)))
+        .collect();
+
+    info!("----- LATENCY REPORT -----");
+    for (operation, summary) in summaries {
+        info!(
+            "operation: {} calls: {} p50: {:?} p95: {:?}",
+            operation.to_string(),
+            summary.count,
+            summary.p50,
+            summary.p95,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_budgets() {
+        let budgets: LatencyBudgets = "get_account=500ms, wait_for_transaction=30s,view=2m"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            budgets,
+            LatencyBudgets(HashMap::from([
+                (Operation::GetAccount, Duration::from_millis(500)),
+                (Operation::WaitForTransaction, Duration::from_secs(30)),
+                (Operation::View, Duration::from_secs(120)),
+            ]))
+        );
+        assert_eq!(
+            "".parse::<LatencyBudgets>().unwrap(),
+            LatencyBudgets::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_budgets_errors() {
+        let err = "get_acount=500ms".parse::<LatencyBudgets>().unwrap_err();
+        assert!(err.to_string().contains("unknown operation 'get_acount'"));
+        assert!(err.to_string().contains("get_account"));
+
+        for invalid in [
+            "get_account",
+            "get_account=",
+            "get_account=500",
+            "get_account=500h",
+            "get_account=-1s",
+            "get_account=1s,get_account=2s",
+        ] {
+            assert!(
+                invalid.parse::<LatencyBudgets>().is_err(),
+                "{} should not parse",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_budget() {
+        let budgets: LatencyBudgets = "get_account=500ms".parse().unwrap();
+
+        assert!(budgets
+            .check(Operation::GetAccount, Duration::from_millis(500))
+            .is_ok());
+        assert!(budgets
+            .check(Operation::GetAccountBalance, Duration::from_secs(60))
+            .is_ok());
+        match budgets.check(Operation::GetAccount, Duration::from_millis(501)) {
+            Err(TestFailure::Fail(message)) => {
+                assert!(message.contains("get_account took 501ms"), "{}", message)
+            },
+            result => panic!("Expected a budget failure, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(summarize(&[]), None);
+
+        let samples: Vec<_> = (1..=100).rev().map(Duration::from_millis).collect();
+        assert_eq!(
+            summarize(&samples),
+            Some(LatencySummary {
+                count: 100,
+                p50: Duration::from_millis(50),
+                p95: Duration::from_millis(95),
+            })
+        );
+
+        assert_eq!(
+            summarize(&[Duration::from_secs(1)]),
+            Some(LatencySummary {
+                count: 1,
+                p50: Duration::from_secs(1),
+                p95: Duration::from_secs(1),
+            })
+        );
+    }
+}
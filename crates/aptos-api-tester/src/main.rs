@@ -5,6 +5,7 @@
 
 mod consts;
 mod counters;
+mod latency;
 mod persistent_check;
 mod strings;
 mod tests;
@@ -13,10 +14,14 @@ mod utils;
 #[macro_use]
 mod macros;
 
-use crate::utils::{NetworkName, TestName};
-use anyhow::Result;
+use crate::{
+    latency::{LatencyBudgets, LATENCY_BUDGETS},
+    utils::{NetworkName, TestName},
+};
+use anyhow::{anyhow, Result};
 use aptos_logger::{info, Level, Logger};
 use aptos_push_metrics::MetricsPusher;
+use clap::Parser;
 use consts::{NETWORK_NAME, NUM_THREADS, STACK_SIZE};
 use futures::future::join_all;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -76,7 +81,22 @@ async fn test_flows(runtime: &Runtime, network_name: NetworkName) -> Result<()>
     Ok(())
 }
 
+#[derive(Debug, Parser)]
+#[clap(name = "aptos-api-tester", author, version)]
+struct Args {
+    /// Latency budgets per REST operation, e.g. `get_account=500ms,wait_for_transaction=30s`.
+    /// A call that takes longer than its budget fails the step it belongs to.
+    #[clap(long)]
+    budget: Option<LatencyBudgets>,
+}
+
 fn main() -> Result<()> {
+    // parse arguments
+    let args = Args::parse();
+    LATENCY_BUDGETS
+        .set(args.budget.unwrap_or_default())
+        .map_err(|_| anyhow!("latency budgets are already set"))?;
+
     // create runtime
     let runtime = Builder::new_multi_thread()
         .worker_threads(*NUM_THREADS)
@@ -93,5 +113,8 @@ fn main() -> Result<()> {
         let _ = test_flows(&runtime, *NETWORK_NAME).await;
     });
 
+    // report latencies
+    latency::report();
+
     Ok(())
 }
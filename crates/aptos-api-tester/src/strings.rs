@@ -2,6 +2,7 @@
 
 // Fail messages
 
+pub const FAIL_OVER_LATENCY_BUDGET: &str = "over latency budget";
 pub const FAIL_WRONG_ACCOUNT_DATA: &str = "wrong account data";
 pub const FAIL_WRONG_BALANCE: &str = "wrong balance";
 pub const FAIL_WRONG_BALANCE_AT_VERSION: &str = "wrong balance at version";
@@ -2,6 +2,7 @@
 
 use crate::{
     consts::FUND_AMOUNT,
+    latency::{timed, Operation},
     persistent_check,
     strings::{
         CHECK_ACCOUNT_BALANCE, CHECK_ACCOUNT_BALANCE_AT_VERSION, CHECK_ACCOUNT_DATA,
@@ -120,7 +121,7 @@ async fn setup(
                 "test: coin_transfer part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_FUND_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -132,7 +133,7 @@ async fn setup(
                 "test: coin_transfer part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_CREATE_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -172,7 +173,12 @@ async fn transfer_coins(
     };
 
     // wait and get version
-    let response = match client.wait_for_transaction(&pending_txn).await {
+    let response = match timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         Ok(response) => response,
         Err(e) => {
             error!(
@@ -206,7 +212,12 @@ async fn check_account_balance(
     let expected = U64(TRANSFER_AMOUNT);
 
     // actual
-    let actual = match client.get_account_balance(address).await {
+    let actual = match timed(
+        Operation::GetAccountBalance,
+        client.get_account_balance(address),
+    )
+    .await?
+    {
         Ok(response) => response.into_inner().coin.value,
         Err(e) => {
             error!(
@@ -223,7 +234,7 @@ async fn check_account_balance(
             "test: coin_transfer part: check_account_balance FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_BALANCE, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE));
+        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE.to_string()));
     }
 
     Ok(())
@@ -238,9 +249,11 @@ async fn check_account_balance_at_version(
     let expected = U64(0);
 
     // actual
-    let actual = match client
-        .get_account_balance_at_version(address, transaction_version - 1)
-        .await
+    let actual = match timed(
+        Operation::GetAccountBalanceAtVersion,
+        client.get_account_balance_at_version(address, transaction_version - 1),
+    )
+    .await?
     {
         Ok(response) => response.into_inner().coin.value,
         Err(e) => {
@@ -258,7 +271,7 @@ async fn check_account_balance_at_version(
             "test: coin_transfer part: check_account_balance_at_version FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_BALANCE_AT_VERSION, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE_AT_VERSION));
+        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE_AT_VERSION.to_string()));
     }
 
     Ok(())
@@ -2,6 +2,7 @@
 
 use crate::{
     consts::FUND_AMOUNT,
+    latency::{timed, Operation},
     persistent_check,
     strings::{
         CHECK_ACCOUNT_BALANCE, CHECK_ACCOUNT_DATA, ERROR_COULD_NOT_CREATE_ACCOUNT,
@@ -88,7 +89,7 @@ async fn setup(
                 "test: new_account part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_CREATE_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -97,7 +98,7 @@ async fn setup(
 
 async fn fund(faucet_client: &FaucetClient, address: AccountAddress) -> Result<(), TestFailure> {
     // fund account
-    if let Err(e) = faucet_client.fund(address, FUND_AMOUNT).await {
+    if let Err(e) = timed(Operation::Fund, faucet_client.fund(address, FUND_AMOUNT)).await? {
         error!(
             "test: new_account part: fund ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FUND_ACCOUNT, e
@@ -116,7 +117,7 @@ async fn check_account_data(client: &Client, account: &LocalAccount) -> Result<(
     };
 
     // actual
-    let actual = match client.get_account(account.address()).await {
+    let actual = match timed(Operation::GetAccount, client.get_account(account.address())).await? {
         Ok(response) => response.into_inner(),
         Err(e) => {
             error!(
@@ -133,7 +134,7 @@ async fn check_account_data(client: &Client, account: &LocalAccount) -> Result<(
             "test: new_account part: check_account_data FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_ACCOUNT_DATA, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_ACCOUNT_DATA));
+        return Err(TestFailure::Fail(FAIL_WRONG_ACCOUNT_DATA.to_string()));
     }
 
     Ok(())
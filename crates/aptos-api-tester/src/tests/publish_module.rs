@@ -2,6 +2,7 @@
 
 use crate::{
     consts::FUND_AMOUNT,
+    latency::{timed, Operation},
     persistent_check,
     strings::{
         BUILD_MODULE, CHECK_ACCOUNT_DATA, CHECK_MESSAGE, CHECK_MODULE_DATA,
@@ -140,7 +141,7 @@ async fn setup(network_name: NetworkName) -> Result<(Client, LocalAccount), Test
                 "test: publish_module part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_FUND_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -238,7 +239,12 @@ async fn publish_module(
         };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: publish_module part: publish_module ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -267,7 +273,12 @@ async fn check_module_data(
     expected: &HexEncodedBytes,
 ) -> Result<(), TestFailure> {
     // actual
-    let response = match client.get_account_module(address, MODULE_NAME).await {
+    let response = match timed(
+        Operation::GetAccountModule,
+        client.get_account_module(address, MODULE_NAME),
+    )
+    .await?
+    {
         Ok(response) => response,
         Err(e) => {
             error!(
@@ -285,7 +296,7 @@ async fn check_module_data(
             "test: publish_module part: check_module_data FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_MODULE, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_MODULE));
+        return Err(TestFailure::Fail(FAIL_WRONG_MODULE.to_string()));
     }
 
     Ok(())
@@ -328,7 +339,12 @@ async fn set_message(client: &Client, account: &mut LocalAccount) -> Result<(),
         };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: publish_module part: set_message ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -361,7 +377,7 @@ async fn check_message(client: &Client, address: AccountAddress) -> Result<(), T
             "test: publish_module part: check_message FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_MESSAGE, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_MESSAGE));
+        return Err(TestFailure::Fail(FAIL_WRONG_MESSAGE.to_string()));
     }
 
     Ok(())
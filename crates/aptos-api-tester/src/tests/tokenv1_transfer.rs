@@ -2,6 +2,7 @@
 
 use crate::{
     consts::FUND_AMOUNT,
+    latency::{timed, Operation},
     persistent_check,
     strings::{
         CHECK_ACCOUNT_DATA, CHECK_COLLECTION_METADATA, CHECK_RECEIVER_BALANCE,
@@ -192,7 +193,7 @@ async fn setup(
                 "test: nft_transfer part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_FUND_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -204,7 +205,7 @@ async fn setup(
                 "test: nft_transfer part: setup ERROR: {}, with error {:?}",
                 ERROR_COULD_NOT_FUND_ACCOUNT, e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -259,7 +260,12 @@ async fn create_collection(
     };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: nft_transfer part: create_collection ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -301,7 +307,7 @@ async fn check_collection_metadata(
             "test: nft_transfer part: check_collection_metadata FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_COLLECTION_DATA, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_COLLECTION_DATA));
+        return Err(TestFailure::Fail(FAIL_WRONG_COLLECTION_DATA.to_string()));
     }
 
     Ok(())
@@ -341,7 +347,12 @@ async fn create_token(
     };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: nft_transfer part: create_token ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -383,7 +394,7 @@ async fn check_token_metadata(
             "test: nft_transfer part: check_token_metadata FAIL: {}, expected {:?}, got {:?}",
             FAIL_WRONG_TOKEN_DATA, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_TOKEN_DATA));
+        return Err(TestFailure::Fail(FAIL_WRONG_TOKEN_DATA.to_string()));
     }
 
     Ok(())
@@ -420,7 +431,12 @@ async fn offer_token(
     };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: nft_transfer part: offer_token ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -475,7 +491,12 @@ async fn claim_token(
     };
 
     // wait for transaction to finish
-    if let Err(e) = client.wait_for_transaction(&pending_txn).await {
+    if let Err(e) = timed(
+        Operation::WaitForTransaction,
+        client.wait_for_transaction(&pending_txn),
+    )
+    .await?
+    {
         error!(
             "test: nft_transfer part: claim_token ERROR: {}, with error {:?}",
             ERROR_COULD_NOT_FINISH_TRANSACTION, e
@@ -569,7 +590,7 @@ async fn check_token_balance(
             "test: nft_transfer part: {} FAIL: {}, expected {:?}, got {:?}",
             part, FAIL_WRONG_TOKEN_BALANCE, expected, actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_TOKEN_BALANCE));
+        return Err(TestFailure::Fail(FAIL_WRONG_TOKEN_BALANCE.to_string()));
     }
 
     Ok(())
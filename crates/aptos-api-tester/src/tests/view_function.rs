@@ -2,6 +2,7 @@
 
 use crate::{
     consts::FUND_AMOUNT,
+    latency::{timed, Operation},
     persistent_check,
     strings::{
         CHECK_ACCOUNT_DATA, CHECK_VIEW_ACCOUNT_BALANCE, ERROR_BAD_BALANCE_STRING,
@@ -84,7 +85,7 @@ async fn setup(network_name: NetworkName) -> Result<(Client, LocalAccount), Test
                 ERROR_COULD_NOT_FUND_ACCOUNT,
                 e
             );
-            return Err(e.into());
+            return Err(e);
         },
     };
 
@@ -107,16 +108,18 @@ async fn check_view_account_balance(
     // actual
 
     // get client response
-    let response = match client
-        .view(
+    let response = match timed(
+        Operation::View,
+        client.view(
             &ViewRequest {
                 function: "0x1::coin::balance".parse()?,
                 type_arguments: vec!["0x1::aptos_coin::AptosCoin".parse()?],
                 arguments: vec![serde_json::Value::String(address.to_hex_literal())],
             },
             None,
-        )
-        .await
+        ),
+    )
+    .await?
     {
         Ok(response) => response,
         Err(e) => {
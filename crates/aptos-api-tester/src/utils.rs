@@ -5,6 +5,7 @@ use crate::{
         DEVNET_FAUCET_URL, DEVNET_NODE_URL, FUND_AMOUNT, TESTNET_FAUCET_URL, TESTNET_NODE_URL,
     },
     counters::{test_error, test_fail, test_latency, test_step_latency, test_success},
+    latency::{timed, Operation},
     strings::{ERROR_NO_BALANCE, FAIL_WRONG_BALANCE},
     tests::{coin_transfer, new_account, publish_module, tokenv1_transfer, view_function},
     time_fn,
@@ -22,7 +23,7 @@ use std::{env, num::ParseIntError, str::FromStr};
 #[derive(Debug)]
 pub enum TestFailure {
     // Variant for failed checks, e.g. wrong balance
-    Fail(&'static str),
+    Fail(String),
     // Variant for test failures, e.g. client returns an error
     Error(anyhow::Error),
 }
@@ -144,9 +145,13 @@ impl NetworkName {
 pub async fn create_account(
     faucet_client: &FaucetClient,
     test_name: TestName,
-) -> Result<LocalAccount> {
+) -> Result<LocalAccount, TestFailure> {
     let account = LocalAccount::generate(&mut rand::rngs::OsRng);
-    faucet_client.create_account(account.address()).await?;
+    timed(
+        Operation::CreateAccount,
+        faucet_client.create_account(account.address()),
+    )
+    .await??;
 
     info!(
         "CREATED ACCOUNT {} for test: {}",
@@ -160,9 +165,13 @@ pub async fn create_account(
 pub async fn create_and_fund_account(
     faucet_client: &FaucetClient,
     test_name: TestName,
-) -> Result<LocalAccount> {
+) -> Result<LocalAccount, TestFailure> {
     let account = LocalAccount::generate(&mut rand::rngs::OsRng);
-    faucet_client.fund(account.address(), FUND_AMOUNT).await?;
+    timed(
+        Operation::Fund,
+        faucet_client.fund(account.address(), FUND_AMOUNT),
+    )
+    .await??;
 
     info!(
         "CREATED ACCOUNT {} for test: {}",
@@ -180,7 +189,12 @@ pub async fn check_balance(
     expected: U64,
 ) -> Result<(), TestFailure> {
     // actual
-    let actual = match client.get_account_balance(address).await {
+    let actual = match timed(
+        Operation::GetAccountBalance,
+        client.get_account_balance(address),
+    )
+    .await?
+    {
         Ok(response) => response.into_inner().coin.value,
         Err(e) => {
             error!(
@@ -202,7 +216,7 @@ pub async fn check_balance(
             expected,
             actual
         );
-        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE));
+        return Err(TestFailure::Fail(FAIL_WRONG_BALANCE.to_string()));
     }
 
     Ok(())
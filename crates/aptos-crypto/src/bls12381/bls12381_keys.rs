@@ -26,9 +26,10 @@ use crate::{
     ValidCryptoMaterialStringExt, VerifyingKey,
 };
 use anyhow::{anyhow, Result};
-use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use serde::Serialize;
 use std::{convert::TryFrom, fmt};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 #[derive(Clone, Eq, SerializeKey, DeserializeKey)]
 /// A BLS12381 public key
@@ -38,8 +39,11 @@ pub struct PublicKey {
     // One reason for this is these PKs are stored in the root of the Merkle accumulator.
 }
 
-#[derive(SerializeKey, DeserializeKey, SilentDebug, SilentDisplay)]
+#[derive(SerializeKey, DeserializeKey)]
 /// A BLS12381 private key
+///
+/// The key material is zeroized on drop, and `Debug`/`Display` only print a prefix of the
+/// public key so the secret can't end up in logs.
 pub struct PrivateKey {
     pub(crate) privkey: blst::min_pk::SecretKey,
 }
@@ -87,14 +91,62 @@ impl PublicKey {
 }
 
 impl PrivateKey {
+    /// The number of bytes of the public key printed to identify a PrivateKey.
+    const FINGERPRINT_LENGTH: usize = 4;
     /// The length of a serialized PrivateKey struct.
     // NOTE: We have to hardcode this here because there is no library-defined constant
     pub const LENGTH: usize = 32;
 
-    /// Serialize a PrivateKey.
+    /// Serialize a PrivateKey. Same as `leak_to_bytes`.
     pub fn to_bytes(&self) -> [u8; Self::LENGTH] {
+        self.leak_to_bytes()
+    }
+
+    /// Returns the raw secret key bytes.
+    ///
+    /// WARNING: The returned bytes are not zeroized on drop, so this should only be used where
+    /// the raw key is truly needed (e.g., serialization), and the caller should zeroize them.
+    pub fn leak_to_bytes(&self) -> [u8; Self::LENGTH] {
         self.privkey.to_bytes()
     }
+
+    /// Prints a prefix of the public key, which identifies the key without revealing it.
+    fn fmt_fingerprint(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let public_key = PublicKey::from(self).to_bytes();
+        write!(
+            f,
+            "<elided secret for PrivateKey with public key 0x{}..>",
+            hex::encode(&public_key[..Self::FINGERPRINT_LENGTH])
+        )
+    }
+}
+
+impl Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        // Overwrites the scalar held by blst in place.
+        self.privkey.zeroize();
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PrivateKey {}
+
+// In order to ensure that secrets are never leaked, Debug and Display only print a fingerprint
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_fingerprint(f)
+    }
+}
+
+impl fmt::Display for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_fingerprint(f)
+    }
 }
 
 ///////////////////////
@@ -130,7 +182,10 @@ impl traits::SigningKey for PrivateKey {
 
 impl traits::ValidCryptoMaterial for PrivateKey {
     fn to_bytes(&self) -> Vec<u8> {
-        self.to_bytes().to_vec()
+        let mut bytes = self.leak_to_bytes();
+        let serialized = bytes.to_vec();
+        bytes.zeroize();
+        serialized
     }
 }
 
@@ -164,6 +219,7 @@ impl Uniform for PrivateKey {
         rng.fill_bytes(&mut ikm);
         let privkey =
             blst::min_pk::SecretKey::key_gen(&ikm, &[]).expect("ikm length should be higher");
+        ikm.zeroize();
         Self { privkey }
     }
 }
@@ -182,8 +238,10 @@ static_assertions::assert_not_impl_any!(PrivateKey: Clone);
 #[cfg(any(test, feature = "cloneable-private-keys"))]
 impl Clone for PrivateKey {
     fn clone(&self) -> Self {
-        let serialized: &[u8] = &(self.to_bytes());
-        PrivateKey::try_from(serialized).unwrap()
+        let mut serialized = self.leak_to_bytes();
+        let cloned = PrivateKey::try_from(serialized.as_ref()).unwrap();
+        serialized.zeroize();
+        cloned
     }
 }
 
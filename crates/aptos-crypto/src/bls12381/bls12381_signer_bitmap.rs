@@ -0,0 +1,75 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides the `SignerBitmap` struct, which records which signers out of a fixed,
+//! ordered set (e.g., the validator set) contributed a signature share to a multisignature.
+//!
+//! Bit `i` is set if and only if the `i`th signer contributed. The bitmap has exactly one bit per
+//! signer, so it can be checked against the signer set when verifying the multisignature via
+//! `Signature::verify_multisig`.
+
+use anyhow::{ensure, Result};
+use bitvec::{order::Lsb0, vec::BitVec};
+
+/// A bitmap of the signers, out of a fixed and ordered set of signers, that contributed to a
+/// multisignature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerBitmap {
+    bits: BitVec<u8, Lsb0>,
+}
+
+impl SignerBitmap {
+    /// Creates a bitmap for `num_signers` signers, with none of them set.
+    pub fn new(num_signers: usize) -> Self {
+        Self {
+            bits: BitVec::repeat(false, num_signers),
+        }
+    }
+
+    /// Creates a bitmap for `num_signers` signers, with the signers in `indices` set. Fails if any
+    /// of the indices is out of bounds.
+    pub fn from_indices(num_signers: usize, indices: &[usize]) -> Result<Self> {
+        let mut bitmap = Self::new(num_signers);
+        for &index in indices {
+            bitmap.set(index)?;
+        }
+        Ok(bitmap)
+    }
+
+    /// Marks the signer at `index` as having contributed. Fails if `index` is out of bounds.
+    pub fn set(&mut self, index: usize) -> Result<()> {
+        ensure!(
+            index < self.bits.len(),
+            "Signer index {} is out of bounds for {} signers",
+            index,
+            self.bits.len()
+        );
+        self.bits.set(index, true);
+        Ok(())
+    }
+
+    /// Returns true if the signer at `index` contributed. Out of bounds indices are never set.
+    pub fn is_set(&self, index: usize) -> bool {
+        self.bits.get(index).map_or(false, |bit| *bit)
+    }
+
+    /// The number of signers the bitmap was created for.
+    pub fn num_signers(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// The number of signers that contributed.
+    pub fn count_ones(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    /// Returns true if no signer contributed.
+    pub fn is_empty(&self) -> bool {
+        self.bits.not_any()
+    }
+
+    /// Iterates over the indices of the signers that contributed, in increasing order.
+    pub fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter_ones()
+    }
+}
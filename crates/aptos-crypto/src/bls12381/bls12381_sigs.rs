@@ -19,13 +19,14 @@
 use crate::{
     bls12381::{
         bls12381_keys::{PrivateKey, PublicKey},
+        bls12381_signer_bitmap::SignerBitmap,
         DST_BLS_SIG_IN_G2_WITH_POP,
     },
     hash::CryptoHash,
     signing_message, traits, CryptoMaterialError, Length, ValidCryptoMaterial,
     ValidCryptoMaterialStringExt,
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use blst::BLST_ERROR;
 use serde::Serialize;
@@ -118,6 +119,33 @@ impl Signature {
         self.verify_aggregate_arbitrary_msg(&msgs_refs, pks)
     }
 
+    /// Verifies a multisignature on `message` from the signers in `bitmap`, where bit `i` of the
+    /// bitmap stands for the signer with public key `pubkeys[i]`. Only the public keys of the set
+    /// signers are aggregated.
+    ///
+    /// Fails if the bitmap is not exactly as long as `pubkeys`, or if no signer is set.
+    ///
+    /// WARNING: This function assumes that the public keys have been subgroup-checked by the caller
+    /// implicitly when verifying their proof-of-possession (PoP) in `ProofOfPossession::verify`.
+    pub fn verify_multisig<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        pubkeys: &[PublicKey],
+        bitmap: &SignerBitmap,
+    ) -> Result<()> {
+        ensure!(
+            bitmap.num_signers() == pubkeys.len(),
+            "Signer bitmap has {} bits, but there are {} public keys",
+            bitmap.num_signers(),
+            pubkeys.len()
+        );
+        ensure!(!bitmap.is_empty(), "Signer bitmap has no signers set");
+
+        let signer_pubkeys = bitmap.iter_ones().map(|index| &pubkeys[index]).collect();
+        let aggpk = PublicKey::aggregate(signer_pubkeys)?;
+        traits::Signature::verify(self, message, &aggpk)
+    }
+
     /// Return a dummy signature for testing.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn dummy_signature() -> Self {
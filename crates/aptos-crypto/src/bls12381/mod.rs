@@ -421,10 +421,12 @@ pub const DST_BLS_SIG_IN_G2_WITH_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_S
 
 pub mod bls12381_keys;
 pub mod bls12381_pop;
+pub mod bls12381_signer_bitmap;
 pub mod bls12381_sigs;
 pub mod bls12381_validatable;
 
 pub use bls12381_keys::{PrivateKey, PublicKey};
 pub use bls12381_pop::ProofOfPossession;
+pub use bls12381_signer_bitmap::SignerBitmap;
 pub use bls12381_sigs::Signature;
 pub use bls12381_validatable::UnvalidatedPublicKey;
@@ -3,14 +3,15 @@
 
 use crate::{
     bls12381,
-    bls12381::{PrivateKey, ProofOfPossession, PublicKey},
+    bls12381::{PrivateKey, ProofOfPossession, PublicKey, SignerBitmap},
     test_utils::{random_subset, KeyPair, TestAptosCrypto},
     validatable::{Validatable, Validate},
-    Signature, SigningKey, Uniform,
+    Signature, SigningKey, Uniform, ValidCryptoMaterialStringExt,
 };
 use rand::{distributions::Alphanumeric, Rng};
 use rand_core::OsRng;
 use std::{convert::TryFrom, iter::zip};
+use zeroize::Zeroize;
 
 /// Tests that an individual signature share computed correctly on a message m passes verification on m.
 /// Tests that a signature share computed on a different message m' fails verification on m.
@@ -142,6 +143,90 @@ fn bls12381_multisig_should_verify() {
     assert!(multisig.verify(&message_wrong, &aggpk).is_err());
 }
 
+/// Tests that a multisignature verifies against the bitmap of its signers, for several subsets of
+/// signers, but not against the bitmap of a different subset.
+#[test]
+fn bls12381_multisig_with_bitmap_should_verify() {
+    let mut rng = OsRng;
+
+    let message = random_message_for_signing(&mut rng);
+    let num_signers = 20;
+    let key_pairs = bls12381_keygen(num_signers, &mut rng);
+    let pubkeys: Vec<PublicKey> = key_pairs.iter().map(|kp| kp.public_key.clone()).collect();
+
+    for subset_size in [1, 2, num_signers / 2, num_signers - 1, num_signers] {
+        let signers = random_subset(&mut rng, num_signers, subset_size);
+        let signatures = signers
+            .iter()
+            .map(|&i| key_pairs[i].private_key.sign(&message).unwrap())
+            .collect();
+        let multisig = bls12381::Signature::aggregate(signatures).unwrap();
+
+        let bitmap = SignerBitmap::from_indices(num_signers, &signers).unwrap();
+        assert_eq!(bitmap.count_ones(), subset_size);
+        assert!(multisig
+            .verify_multisig(&message, &pubkeys, &bitmap)
+            .is_ok());
+
+        // Flipping a single signer in or out of the bitmap breaks verification
+        let mut wrong_signers = signers.clone();
+        match (0..num_signers).find(|i| !signers.contains(i)) {
+            Some(missing) => wrong_signers.push(missing),
+            None => {
+                wrong_signers.pop();
+            },
+        }
+        let wrong_bitmap = SignerBitmap::from_indices(num_signers, &wrong_signers).unwrap();
+        assert!(multisig
+            .verify_multisig(&message, &pubkeys, &wrong_bitmap)
+            .is_err());
+    }
+}
+
+/// Tests that empty bitmaps, bitmaps that don't match the number of public keys and out of bounds
+/// signer indices are rejected.
+#[test]
+fn bls12381_multisig_with_bad_bitmap_does_not_verify() {
+    let mut rng = OsRng;
+
+    let message = random_message_for_signing(&mut rng);
+    let num_signers = 5;
+    let key_pairs = bls12381_keygen(num_signers, &mut rng);
+    let pubkeys: Vec<PublicKey> = key_pairs.iter().map(|kp| kp.public_key.clone()).collect();
+    let signatures = key_pairs
+        .iter()
+        .map(|kp| kp.private_key.sign(&message).unwrap())
+        .collect();
+    let multisig = bls12381::Signature::aggregate(signatures).unwrap();
+
+    // An empty bitmap is rejected
+    let empty_bitmap = SignerBitmap::new(num_signers);
+    assert!(empty_bitmap.is_empty());
+    assert!(multisig
+        .verify_multisig(&message, &pubkeys, &empty_bitmap)
+        .is_err());
+
+    // A bitmap longer than the public keys is rejected, even if the signers match
+    let all_signers: Vec<usize> = (0..num_signers).collect();
+    let long_bitmap = SignerBitmap::from_indices(num_signers + 1, &all_signers).unwrap();
+    assert!(multisig
+        .verify_multisig(&message, &pubkeys, &long_bitmap)
+        .is_err());
+    let full_bitmap = SignerBitmap::from_indices(num_signers, &all_signers).unwrap();
+    assert!(multisig
+        .verify_multisig(&message, &pubkeys, &full_bitmap)
+        .is_ok());
+    assert!(multisig
+        .verify_multisig(&message, &pubkeys[..num_signers - 1], &full_bitmap)
+        .is_err());
+
+    // Signer indices have to be within the bitmap
+    let mut bitmap = SignerBitmap::new(num_signers);
+    assert!(bitmap.set(num_signers).is_err());
+    assert!(!bitmap.is_set(num_signers));
+    assert!(SignerBitmap::from_indices(num_signers, &[0, num_signers]).is_err());
+}
+
 /// Tests signature (de)serialization
 #[test]
 fn bls12381_serialize_sig() {
@@ -614,3 +699,37 @@ fn bls12381_sample_aggregate_sigs() {
     }
     println!("];");
 }
+
+/// Tests that formatting a private key only reveals a prefix of its public key.
+#[test]
+fn bls12381_private_key_debug_is_redacted() {
+    let mut rng = OsRng;
+    let sk = PrivateKey::generate(&mut rng);
+    let pk = PublicKey::from(&sk);
+    let sk_hex = hex::encode(sk.leak_to_bytes());
+    let pk_prefix = hex::encode(&pk.to_bytes()[..4]);
+
+    for formatted in [format!("{:?}", sk), format!("{}", sk)] {
+        assert!(!formatted.contains(&sk_hex));
+        assert!(!formatted.contains(&sk_hex[..8]));
+        assert!(formatted.contains(&pk_prefix));
+    }
+}
+
+/// Tests that private keys still serialize and deserialize the same, and that zeroizing clears
+/// the key material.
+#[test]
+fn bls12381_private_key_serialization_and_zeroize() {
+    let mut rng = OsRng;
+    let sk = PrivateKey::generate(&mut rng);
+
+    let sk_deserialized: PrivateKey = bcs::from_bytes(&bcs::to_bytes(&sk).unwrap()).unwrap();
+    assert_eq!(sk_deserialized.leak_to_bytes(), sk.leak_to_bytes());
+    let sk_decoded = PrivateKey::from_encoded_string(&sk.to_encoded_string().unwrap()).unwrap();
+    assert_eq!(sk_decoded.leak_to_bytes(), sk.leak_to_bytes());
+
+    let mut sk_zeroized = sk.clone();
+    assert_eq!(sk_zeroized.leak_to_bytes(), sk.leak_to_bytes());
+    sk_zeroized.zeroize();
+    assert_eq!(sk_zeroized.leak_to_bytes(), [0u8; PrivateKey::LENGTH]);
+}
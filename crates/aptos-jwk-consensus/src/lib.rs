@@ -1,6 +1,9 @@
 // Copyright © Aptos Foundation
 
-use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_config::{
+    config::JWKConsensusConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_event_notifications::{
     DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
 };
@@ -10,25 +13,42 @@ use aptos_network::application::{
 };
 use aptos_types::PeerId;
 use aptos_validator_transaction_pool as vtxn_pool;
+use futures_channel::oneshot;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use tokio::runtime::Runtime;
 
+/// Starts the JWK consensus runtime. Sending an ack sender through `close_rx`
+/// (or dropping its sender) stops the main loop after the pending validator
+/// transaction is withdrawn from the pool.
 #[allow(clippy::let_and_return)]
 pub fn start_jwk_consensus_runtime(
+    config: &JWKConsensusConfig,
     _network_client: NetworkClient<JWKConsensusMsg>,
     _network_service_events: NetworkServiceEvents<JWKConsensusMsg>,
-    _vtxn_pool_writer: vtxn_pool::SingleTopicWriteClient,
+    vtxn_pool_writer: vtxn_pool::SingleTopicWriteClient,
     mut reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     mut onchain_jwk_updated_events: EventNotificationListener,
+    mut close_rx: oneshot::Receiver<oneshot::Sender<()>>,
 ) -> Runtime {
-    let runtime = aptos_runtimes::spawn_named_runtime("jwk".into(), Some(4));
+    let runtime =
+        aptos_runtimes::spawn_named_runtime("jwk".into(), Some(config.runtime_worker_threads));
     runtime.spawn(async move {
         loop {
             tokio::select! {
-                _ = reconfig_events.select_next_some() => {},
+                _ = reconfig_events.select_next_some() => {
+                    // Updates certified in the previous epoch are no longer valid.
+                    vtxn_pool_writer.put(None);
+                },
                 _ = onchain_jwk_updated_events.select_next_some() => {},
+                close_req = &mut close_rx => {
+                    vtxn_pool_writer.put(None);
+                    if let Ok(ack_sender) = close_req {
+                        let _ = ack_sender.send(());
+                    }
+                    break;
+                },
             }
         }
     });
@@ -62,3 +82,5 @@ impl<NetworkClient: NetworkClientInterface<JWKConsensusMsg>> JWKNetworkClient<Ne
 }
 
 pub mod network_interface;
+#[cfg(test)]
+mod tests;
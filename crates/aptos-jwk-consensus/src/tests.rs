@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+
+use crate::start_jwk_consensus_runtime;
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::config::JWKConsensusConfig;
+use aptos_event_notifications::{
+    DbBackedOnChainConfig, EventNotification, NotificationListener, ReconfigNotification,
+};
+use aptos_network::application::{
+    interface::{NetworkClient, NetworkServiceEvents},
+    storage::PeersAndMetadata,
+};
+use aptos_types::validator_txn::Topic;
+use aptos_validator_transaction_pool as vtxn_pool;
+use futures_channel::oneshot;
+use std::{collections::HashMap, time::Duration};
+use tokio::runtime::Runtime;
+
+type NotificationSenders = (
+    aptos_channel::Sender<(), ReconfigNotification<DbBackedOnChainConfig>>,
+    aptos_channel::Sender<(), EventNotification>,
+);
+
+/// Starts the runtime with stub dependencies. The returned notification
+/// senders have to be kept alive for the runtime's listeners to stay open.
+fn start_test_runtime(
+    config: &JWKConsensusConfig,
+    close_rx: oneshot::Receiver<oneshot::Sender<()>>,
+) -> (Runtime, NotificationSenders) {
+    let network_client =
+        NetworkClient::new(vec![], vec![], HashMap::new(), PeersAndMetadata::new(&[]));
+    let network_service_events = NetworkServiceEvents::new(HashMap::new());
+    let (_, mut vtxn_pool_writers) = vtxn_pool::new(vec![(Topic::JWK_CONSENSUS, None)]);
+
+    let (reconfig_sender, reconfig_receiver) = aptos_channel::new(QueueStyle::KLAST, 1, None);
+    let (event_sender, event_receiver) = aptos_channel::new(QueueStyle::KLAST, 1, None);
+
+    let runtime = start_jwk_consensus_runtime(
+        config,
+        network_client,
+        network_service_events,
+        vtxn_pool_writers.pop().unwrap(),
+        NotificationListener {
+            notification_receiver: reconfig_receiver,
+        },
+        NotificationListener {
+            notification_receiver: event_receiver,
+        },
+        close_rx,
+    );
+    (runtime, (reconfig_sender, event_sender))
+}
+
+#[test]
+fn test_start_runtime_with_custom_config() {
+    let config = JWKConsensusConfig {
+        runtime_worker_threads: 2,
+        ..Default::default()
+    };
+    let (_close_tx, close_rx) = oneshot::channel();
+    let (runtime, _senders) = start_test_runtime(&config, close_rx);
+
+    assert_eq!(runtime.metrics().num_workers(), 2);
+    assert_eq!(runtime.block_on(runtime.spawn(async { 42 })).unwrap(), 42);
+}
+
+#[test]
+fn test_shutdown_runtime() {
+    let (close_tx, close_rx) = oneshot::channel();
+    let (runtime, _senders) = start_test_runtime(&JWKConsensusConfig::default(), close_rx);
+
+    // The main loop acks the close request right before exiting
+    let (ack_tx, ack_rx) = oneshot::channel();
+    close_tx.send(ack_tx).unwrap();
+    runtime.block_on(async {
+        tokio::time::timeout(Duration::from_secs(5), ack_rx)
+            .await
+            .expect("JWK consensus did not shut down in time")
+            .unwrap();
+    });
+}
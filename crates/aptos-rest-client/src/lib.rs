@@ -1138,6 +1138,20 @@ impl Client {
         self.get(url).await
     }
 
+    pub async fn get_account_module_abi(
+        &self,
+        address: AccountAddress,
+        module_name: &str,
+    ) -> AptosResult<Response<MoveModuleBytecode>> {
+        let mut url = self.build_path(&format!(
+            "accounts/{}/module/{}",
+            address.to_hex(),
+            module_name
+        ))?;
+        url.set_query(Some("abi_only=true"));
+        self.get(url).await
+    }
+
     pub async fn get_account_module_bcs(
         &self,
         address: AccountAddress,
@@ -7,15 +7,15 @@ use crate::{
         init::Network,
         utils::{
             check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
-            get_account_with_state, get_auth_key, get_sequence_number, parse_json_file,
-            prompt_yes_with_override, read_from_file, start_logger, to_common_result,
-            to_common_success_result, write_to_file, write_to_file_with_opts,
+            explain_failed_execution, get_account_with_state, get_auth_key, get_sequence_number,
+            parse_json_file, prompt_yes_with_override, read_from_file, start_logger,
+            to_common_result, to_common_success_result, write_to_file, write_to_file_with_opts,
             write_to_user_only_file,
         },
     },
     config::GlobalConfig,
     genesis::git::from_yaml,
-    move_tool::{ArgWithType, FunctionArgType, MemberId},
+    move_tool::{parse_member_id_with_named_addresses, ArgWithType, FunctionArgType, MemberId},
 };
 use anyhow::Context;
 use aptos_crypto::{
@@ -24,7 +24,10 @@ use aptos_crypto::{
     x25519, PrivateKey, ValidCryptoMaterialStringExt,
 };
 use aptos_gas_profiling::FrameName;
-use aptos_global_constants::adjust_gas_headroom;
+use aptos_global_constants::{
+    adjust_gas_headroom_by_ratio, GAS_HEADROOM_DENOMINATOR, GAS_HEADROOM_NUMERATOR, GAS_UNIT_PRICE,
+    MAX_GAS_AMOUNT,
+};
 use aptos_keygen::KeyGen;
 use aptos_logger::Level;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
@@ -34,7 +37,7 @@ use aptos_rest_client::{
     AptosBaseUrl, Client, Transaction,
 };
 use aptos_sdk::{
-    transaction_builder::TransactionFactory,
+    transaction_builder::{TransactionBuilder, TransactionFactory},
     types::{HardwareWalletAccount, HardwareWalletType, LocalAccount, TransactionSigner},
 };
 use aptos_types::{
@@ -45,10 +48,16 @@ use aptos_types::{
     },
 };
 use async_trait::async_trait;
-use clap::{Parser, ValueEnum};
+use clap::{ArgAction, Parser, ValueEnum};
 use hex::FromHexError;
 use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
-use move_package::CompilerVersion;
+use move_package::{
+    source_package::{
+        manifest_parser::parse_move_manifest_from_file, parsed_manifest::SourceManifest,
+    },
+    CompilerVersion,
+};
+use move_symbol_pool::Symbol;
 use serde::{Deserialize, Serialize};
 #[cfg(unix)]
 use std::os::unix::fs::OpenOptionsExt;
@@ -67,6 +76,8 @@ pub const USER_AGENT: &str = concat!("aptos-cli/", env!("CARGO_PKG_VERSION"));
 const US_IN_SECS: u64 = 1_000_000;
 const ACCEPTED_CLOCK_SKEW_US: u64 = 5 * US_IN_SECS;
 pub const DEFAULT_EXPIRATION_SECS: u64 = 30;
+pub const DEFAULT_GAS_HEADROOM_PERCENT: u64 =
+    GAS_HEADROOM_NUMERATOR * 100 / GAS_HEADROOM_DENOMINATOR;
 pub const DEFAULT_PROFILE: &str = "default";
 
 // Custom header value to identify the client
@@ -1044,6 +1055,17 @@ pub struct MovePackageDir {
     #[clap(long, value_parser = crate::common::utils::parse_map::<String, AccountAddressWrapper>, default_value = "")]
     pub(crate) named_addresses: BTreeMap<String, AccountAddressWrapper>,
 
+    /// Named address to set to the account of the current profile
+    ///
+    /// The profile is the one given by --profile for commands that take it, and the default
+    /// profile otherwise.  The named address must be declared in the [addresses] of the
+    /// Move.toml file.  If the profile has no account, dev mode falls back to the preset
+    /// [dev-addresses] value.
+    ///
+    /// Example: --use-profile-address alice
+    #[clap(long)]
+    pub use_profile_address: Option<String>,
+
     /// Skip pulling the latest git dependencies
     ///
     /// If you don't have a network connection, the compiler may fail due
@@ -1080,6 +1102,7 @@ impl MovePackageDir {
             package_dir: Some(package_dir),
             output_dir: None,
             named_addresses: Default::default(),
+            use_profile_address: None,
             skip_fetch_latest_git_deps: true,
             bytecode_version: None,
             compiler_version: None,
@@ -1105,6 +1128,92 @@ impl MovePackageDir {
         self.named_addresses
             .insert(key, AccountAddressWrapper::from_str(&value).unwrap());
     }
+
+    /// Fails if `--use-profile-address` was given, for commands that don't build the package
+    /// for a profile
+    pub fn reject_profile_address(&self, command_name: &str) -> CliTypedResult<()> {
+        if self.use_profile_address.is_some() {
+            return Err(CliError::CommandArgumentError(format!(
+                "--use-profile-address is not supported by {}",
+                command_name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Adds the account of the profile as the `--use-profile-address` named address, so that
+    /// it's included in the NamedAddresses
+    pub fn resolve_profile_address(&mut self, profile: Option<&str>) -> CliTypedResult<()> {
+        let name = if let Some(name) = self.use_profile_address.clone() {
+            name
+        } else {
+            return Ok(());
+        };
+        let manifest = parse_move_manifest_from_file(&self.get_package_path()?)
+            .map_err(|err| CliError::UnableToParse("Move.toml", format!("{:#}", err)))?;
+        let account = match CliConfig::load_profile(profile, ConfigSearchMode::CurrentDirAndParents)
+        {
+            Ok(profile) => profile.and_then(|profile| profile.account),
+            Err(CliError::ConfigNotFoundError(_)) => None,
+            Err(err) => return Err(err),
+        };
+        self.add_profile_address(&name, &manifest, account)
+    }
+
+    pub(crate) fn add_profile_address(
+        &mut self,
+        name: &str,
+        manifest: &SourceManifest,
+        account: Option<AccountAddress>,
+    ) -> CliTypedResult<()> {
+        let symbol = Symbol::from(name);
+        let is_declared = manifest
+            .addresses
+            .as_ref()
+            .map_or(false, |addresses| addresses.contains_key(&symbol));
+        if !is_declared {
+            return Err(CliError::CommandArgumentError(format!(
+                "Named address '{}' is not declared in the [addresses] of package {}",
+                name, manifest.package.name
+            )));
+        }
+
+        let account = if let Some(account) = account {
+            account
+        } else {
+            let has_dev_address = manifest
+                .dev_address_assignments
+                .as_ref()
+                .map_or(false, |dev_addresses| dev_addresses.contains_key(&symbol));
+            if self.dev && has_dev_address {
+                // Leave it to the [dev-addresses] of the package
+                return Ok(());
+            }
+            return Err(CliError::CommandArgumentError(format!(
+                "Unable to set named address '{}', the profile has no account",
+                name
+            )));
+        };
+
+        match self.named_addresses.get(name) {
+            Some(existing) if existing.account_address != account => {
+                Err(CliError::CommandArgumentError(format!(
+                    "Named address '{}' is set to {} by --named-addresses, \
+                    but the profile account is {}",
+                    name,
+                    existing.account_address.to_hex_literal(),
+                    account.to_hex_literal()
+                )))
+            },
+            _ => {
+                self.named_addresses
+                    .insert(name.to_string(), AccountAddressWrapper {
+                        account_address: account,
+                    });
+                Ok(())
+            },
+        }
+    }
 }
 
 /// A wrapper around `AccountAddress` to be more flexible from strings than AccountAddress
@@ -1254,11 +1363,18 @@ pub struct TransactionSummary {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas_unit_price: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_gas: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pending: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sender: Option<AccountAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sequence_number: Option<u64>,
+    /// Hex encoded BCS signed transaction, when signing only without a file to write it to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_transaction: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_transaction_file: Option<PathBuf>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1282,8 +1398,11 @@ impl From<&Transaction> for TransactionSummary {
                 pending: Some(true),
                 sender: Some(*txn.request.sender.inner()),
                 sequence_number: Some(txn.request.sequence_number.0),
+                signed_transaction: None,
+                signed_transaction_file: None,
                 gas_used: None,
                 gas_unit_price: None,
+                max_gas: None,
                 success: None,
                 version: None,
                 vm_status: None,
@@ -1294,10 +1413,13 @@ impl From<&Transaction> for TransactionSummary {
                 sender: Some(*txn.request.sender.inner()),
                 gas_used: Some(txn.info.gas_used.0),
                 gas_unit_price: Some(txn.request.gas_unit_price.0),
+                max_gas: Some(txn.request.max_gas_amount.0),
                 success: Some(txn.info.success),
                 version: Some(txn.info.version.0),
                 vm_status: Some(txn.info.vm_status.clone()),
                 sequence_number: Some(txn.request.sequence_number.0),
+                signed_transaction: None,
+                signed_transaction_file: None,
                 timestamp_us: Some(txn.timestamp.0),
                 pending: None,
             },
@@ -1309,8 +1431,11 @@ impl From<&Transaction> for TransactionSummary {
                 sender: None,
                 gas_used: None,
                 gas_unit_price: None,
+                max_gas: None,
                 pending: None,
                 sequence_number: None,
+                signed_transaction: None,
+                signed_transaction_file: None,
                 timestamp_us: None,
             },
             Transaction::BlockMetadataTransaction(txn) => TransactionSummary {
@@ -1322,8 +1447,11 @@ impl From<&Transaction> for TransactionSummary {
                 sender: None,
                 gas_used: None,
                 gas_unit_price: None,
+                max_gas: None,
                 pending: None,
                 sequence_number: None,
+                signed_transaction: None,
+                signed_transaction_file: None,
             },
             Transaction::StateCheckpointTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
@@ -1334,16 +1462,22 @@ impl From<&Transaction> for TransactionSummary {
                 sender: None,
                 gas_used: None,
                 gas_unit_price: None,
+                max_gas: None,
                 pending: None,
                 sequence_number: None,
+                signed_transaction: None,
+                signed_transaction_file: None,
             },
             Transaction::ValidatorTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
                 gas_used: None,
                 gas_unit_price: None,
+                max_gas: None,
                 pending: None,
                 sender: None,
                 sequence_number: None,
+                signed_transaction: None,
+                signed_transaction_file: None,
                 success: Some(txn.info.success),
                 timestamp_us: Some(txn.timestamp.0),
                 version: Some(txn.info.version.0),
@@ -1460,6 +1594,29 @@ pub struct GasOptions {
     /// This is the number of seconds from the current local computer time.
     #[clap(long, default_value_t = DEFAULT_EXPIRATION_SECS)]
     pub expiration_secs: u64,
+    /// Estimate gas for values not given by `--max-gas` or `--gas-unit-price`
+    ///
+    /// The max gas is estimated by simulating the transaction, and the gas unit price is the
+    /// network's current estimated price.  If disabled, or if estimation fails, the default
+    /// values are used instead.
+    #[clap(long, default_value_t = true, action = ArgAction::Set)]
+    pub estimate_gas: bool,
+    /// Only estimate the max gas, and take the gas unit price from `--gas-unit-price`
+    ///
+    /// If `--gas-unit-price` isn't provided, the default gas unit price is used rather than the
+    /// network's estimated price.
+    #[clap(long)]
+    pub estimate_max_gas_only: bool,
+    /// Percentage applied to the simulated gas used to determine the max gas
+    ///
+    /// Gas costs can change between simulation and execution, so this gives the transaction
+    /// headroom e.g. 150 allows up to 1.5x the simulated gas used.  Must be at least 100.
+    #[clap(
+        long,
+        default_value_t = DEFAULT_GAS_HEADROOM_PERCENT,
+        value_parser = clap::value_parser!(u64).range(100..)
+    )]
+    pub gas_headroom_percent: u64,
 }
 
 impl Default for GasOptions {
@@ -1468,6 +1625,66 @@ impl Default for GasOptions {
             gas_unit_price: None,
             max_gas: None,
             expiration_secs: DEFAULT_EXPIRATION_SECS,
+            estimate_gas: true,
+            estimate_max_gas_only: false,
+            gas_headroom_percent: DEFAULT_GAS_HEADROOM_PERCENT,
+        }
+    }
+}
+
+impl GasOptions {
+    /// Whether the gas unit price should be fetched from the network's estimate
+    pub fn should_estimate_gas_unit_price(&self) -> bool {
+        self.gas_unit_price.is_none() && self.estimate_gas && !self.estimate_max_gas_only
+    }
+
+    /// Whether the max gas should be estimated by simulating the transaction
+    pub fn should_estimate_max_gas(&self) -> bool {
+        self.max_gas.is_none() && (self.estimate_gas || self.estimate_max_gas_only)
+    }
+
+    /// Picks the gas unit price, preferring `--gas-unit-price`, then the network's estimate,
+    /// then the default
+    pub fn resolve_gas_unit_price(&self, estimate: Option<CliTypedResult<u64>>) -> u64 {
+        if let Some(gas_unit_price) = self.gas_unit_price {
+            return gas_unit_price;
+        }
+        match estimate {
+            Some(Ok(gas_unit_price)) => gas_unit_price,
+            Some(Err(err)) => {
+                eprintln!(
+                    "Failed to estimate gas unit price, using the default of {}: {}",
+                    GAS_UNIT_PRICE, err
+                );
+                GAS_UNIT_PRICE
+            },
+            None => GAS_UNIT_PRICE,
+        }
+    }
+
+    /// Picks the max gas, preferring `--max-gas`, then the simulated gas used with headroom
+    /// applied, then the default
+    ///
+    /// The simulation is given as the gas used and the max gas the simulation was allowed.
+    pub fn resolve_max_gas(&self, simulation: Option<CliTypedResult<(u64, u64)>>) -> u64 {
+        if let Some(max_gas) = self.max_gas {
+            return max_gas;
+        }
+        match simulation {
+            Some(Ok((gas_used, max_possible_gas))) => adjust_gas_headroom_by_ratio(
+                gas_used,
+                max_possible_gas,
+                self.gas_headroom_percent,
+                100,
+            ),
+            Some(Err(err)) => {
+                eprintln!(
+                    "Failed to estimate max gas, using the default of {}: {}",
+                    MAX_GAS_AMOUNT, err
+                );
+                MAX_GAS_AMOUNT
+            },
+            None => MAX_GAS_AMOUNT,
         }
     }
 }
@@ -1478,6 +1695,31 @@ pub enum AccountType {
     HardwareWallet,
 }
 
+/// Options for signing a transaction without submitting it
+#[derive(Debug, Default, Parser)]
+pub struct SignOnlyOptions {
+    /// Sign the transaction and output it, rather than submitting it
+    ///
+    /// No requests are made to the network, so `--sender-sequence-number` and `--chain-id`
+    /// must be provided, and gas isn't estimated.  `--gas-unit-price` and `--max-gas` are
+    /// used if provided, otherwise the defaults are used.
+    #[clap(long, requires_all = &["sender_sequence_number", "chain_id"])]
+    pub(crate) sign_only: bool,
+    /// File to write the BCS encoded signed transaction to
+    ///
+    /// If not provided, the BCS encoded signed transaction is returned as hex in the result
+    #[clap(long, requires = "sign_only", value_parser)]
+    pub(crate) signed_transaction_file: Option<PathBuf>,
+    /// Sequence number of the sender to sign the transaction with
+    #[clap(long, requires = "sign_only")]
+    pub(crate) sender_sequence_number: Option<u64>,
+    /// Chain ID of the network the transaction is for
+    ///
+    /// e.g. `2`, `testnet` or `mainnet`
+    #[clap(long, requires = "sign_only")]
+    pub(crate) chain_id: Option<ChainId>,
+}
+
 /// Common options for interacting with an account for a validator
 #[derive(Debug, Default, Parser)]
 pub struct TransactionOptions {
@@ -1500,6 +1742,8 @@ pub struct TransactionOptions {
     pub(crate) gas_options: GasOptions,
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
+    #[clap(flatten)]
+    pub(crate) sign_only_options: SignOnlyOptions,
 
     /// If this option is set, simulate the transaction locally using the debugger and generate
     /// flamegraphs that reflect the gas usage.
@@ -1587,21 +1831,31 @@ impl TransactionOptions {
         &self,
         payload: TransactionPayload,
     ) -> CliTypedResult<Transaction> {
+        if self.sign_only_options.sign_only {
+            return Err(CliError::CommandArgumentError(
+                "--sign-only is not supported by this command".to_string(),
+            ));
+        }
+
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
 
-        // Ask to confirm price if the gas unit price is estimated above the lowest value when
-        // it is automatically estimated
-        let ask_to_confirm_price;
-        let gas_unit_price = if let Some(gas_unit_price) = self.gas_options.gas_unit_price {
-            ask_to_confirm_price = false;
-            gas_unit_price
+        // Ask to confirm price if the gas unit price wasn't explicitly chosen
+        let ask_to_confirm_price = self.gas_options.gas_unit_price.is_none();
+        let gas_unit_price_estimate = if self.gas_options.should_estimate_gas_unit_price() {
+            Some(
+                client
+                    .estimate_gas_price()
+                    .await
+                    .map(|response| response.into_inner().gas_estimate)
+                    .map_err(CliError::from),
+            )
         } else {
-            let gas_unit_price = client.estimate_gas_price().await?.into_inner().gas_estimate;
-
-            ask_to_confirm_price = true;
-            gas_unit_price
+            None
         };
+        let gas_unit_price = self
+            .gas_options
+            .resolve_gas_unit_price(gas_unit_price_estimate);
 
         // Get sequence number for account
         let (account, state) = get_account_with_state(&client, sender_address).await?;
@@ -1624,14 +1878,7 @@ impl TransactionOptions {
         let chain_id = ChainId::new(state.chain_id);
         // TODO: Check auth key against current private key and provide a better message
 
-        let max_gas = if let Some(max_gas) = self.gas_options.max_gas {
-            // If the gas unit price was estimated ask, but otherwise you've chosen hwo much you want to spend
-            if ask_to_confirm_price {
-                let message = format!("Do you want to submit transaction for a maximum of {} Octas at a gas unit price of {} Octas?",  max_gas * gas_unit_price, gas_unit_price);
-                prompt_yes_with_override(&message, self.prompt_options)?;
-            }
-            max_gas
-        } else {
+        let simulation = if self.gas_options.should_estimate_max_gas() {
             let transaction_factory =
                 TransactionFactory::new(chain_id).with_gas_unit_price(gas_unit_price);
 
@@ -1648,26 +1895,39 @@ impl TransactionOptions {
                 Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
             );
 
-            let txns = client
+            match client
                 .simulate_with_gas_estimation(&signed_transaction, true, false)
-                .await?
-                .into_inner();
-            let simulated_txn = txns.first().unwrap();
-
-            // Check if the transaction will pass, if it doesn't then fail
-            if !simulated_txn.info.success {
-                return Err(CliError::SimulationError(
-                    simulated_txn.info.vm_status.clone(),
-                ));
+                .await
+            {
+                Ok(response) => {
+                    let txns = response.into_inner();
+                    let simulated_txn = txns.first().unwrap();
+
+                    // Check if the transaction will pass, if it doesn't then fail
+                    if !simulated_txn.info.success {
+                        return Err(CliError::SimulationError(
+                            simulated_txn.info.vm_status.clone(),
+                        ));
+                    }
+                    Some(Ok((
+                        simulated_txn.info.gas_used.0,
+                        simulated_txn.request.max_gas_amount.0,
+                    )))
+                },
+                Err(err) => Some(Err(CliError::from(err))),
             }
+        } else {
+            None
+        };
+        let gas_used = match &simulation {
+            Some(Ok((gas_used, _))) => Some(*gas_used),
+            _ => None,
+        };
+        let max_gas = self.gas_options.resolve_max_gas(simulation);
 
-            // Take the gas used and use a headroom factor on it
-            let gas_used = simulated_txn.info.gas_used.0;
-            let adjusted_max_gas =
-                adjust_gas_headroom(gas_used, simulated_txn.request.max_gas_amount.0);
-
+        if let Some(gas_used) = gas_used {
             // Ask if you want to accept the estimate amount
-            let upper_cost_bound = adjusted_max_gas * gas_unit_price;
+            let upper_cost_bound = max_gas * gas_unit_price;
             let lower_cost_bound = gas_used * gas_unit_price;
             let message = format!(
                     "Do you want to submit a transaction for a range of [{} - {}] Octas at a gas unit price of {} Octas?",
@@ -1675,8 +1935,11 @@ impl TransactionOptions {
                     upper_cost_bound,
                     gas_unit_price);
             prompt_yes_with_override(&message, self.prompt_options)?;
-            adjusted_max_gas
-        };
+        } else if ask_to_confirm_price || self.gas_options.max_gas.is_none() {
+            // If the gas wasn't fully chosen by the user, confirm the maximum they'd spend
+            let message = format!("Do you want to submit transaction for a maximum of {} Octas at a gas unit price of {} Octas?",  max_gas * gas_unit_price, gas_unit_price);
+            prompt_yes_with_override(&message, self.prompt_options)?;
+        }
 
         // Sign and submit transaction
         let transaction_factory = TransactionFactory::new(chain_id)
@@ -1684,21 +1947,53 @@ impl TransactionOptions {
             .with_max_gas_amount(max_gas)
             .with_transaction_expiration_time(self.gas_options.expiration_secs);
 
-        match self.get_transaction_account_type() {
-            Ok(AccountType::Local) => {
+        // The same payload, to be simulated after the fact if the transaction fails
+        let replay_transaction = SignedTransaction::new(
+            transaction_factory
+                .payload(payload.clone())
+                .sender(sender_address)
+                .sequence_number(sequence_number + 1)
+                .build(),
+            sender_public_key.clone(),
+            Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+        );
+
+        let transaction = self.sign_transaction(
+            sender_address,
+            sender_public_key,
+            sequence_number,
+            transaction_factory.payload(payload),
+        )?;
+        match client.submit_and_wait(&transaction).await {
+            Ok(response) => Ok(response.into_inner()),
+            Err(err) => {
+                let message =
+                    explain_failed_execution(&client, &transaction, &replay_transaction).await;
+                Err(CliError::ApiError(format!(
+                    "{}{}",
+                    err,
+                    message.unwrap_or_default()
+                )))
+            },
+        }
+    }
+
+    /// Signs the transaction with either the local key or the hardware wallet
+    fn sign_transaction(
+        &self,
+        sender_address: AccountAddress,
+        sender_public_key: Ed25519PublicKey,
+        sequence_number: u64,
+        builder: TransactionBuilder,
+    ) -> CliTypedResult<SignedTransaction> {
+        match self.get_transaction_account_type()? {
+            AccountType::Local => {
                 let (private_key, _) = self.get_key_and_address()?;
                 let sender_account =
                     &mut LocalAccount::new(sender_address, private_key, sequence_number);
-                let transaction = sender_account
-                    .sign_with_transaction_builder(transaction_factory.payload(payload));
-                let response = client
-                    .submit_and_wait(&transaction)
-                    .await
-                    .map_err(|err| CliError::ApiError(err.to_string()))?;
-
-                Ok(response.into_inner())
+                Ok(sender_account.sign_with_transaction_builder(builder))
             },
-            Ok(AccountType::HardwareWallet) => {
+            AccountType::HardwareWallet => {
                 let sender_account = &mut HardwareWalletAccount::new(
                     sender_address,
                     sender_public_key,
@@ -1709,19 +2004,66 @@ impl TransactionOptions {
                     HardwareWalletType::Ledger,
                     sequence_number,
                 );
-                let transaction = sender_account
-                    .sign_with_transaction_builder(transaction_factory.payload(payload))?;
-                let response = client
-                    .submit_and_wait(&transaction)
-                    .await
-                    .map_err(|err| CliError::ApiError(err.to_string()))?;
-
-                Ok(response.into_inner())
+                Ok(sender_account.sign_with_transaction_builder(builder)?)
             },
-            Err(err) => Err(err),
         }
     }
 
+    /// Signs the transaction and writes it out as BCS, without any requests to the network
+    pub fn sign_only(&self, payload: TransactionPayload) -> CliTypedResult<TransactionSummary> {
+        let (sequence_number, chain_id) = match (
+            self.sign_only_options.sender_sequence_number,
+            self.sign_only_options.chain_id,
+        ) {
+            (Some(sequence_number), Some(chain_id)) => (sequence_number, chain_id),
+            _ => {
+                return Err(CliError::CommandArgumentError(
+                    "--sender-sequence-number and --chain-id must be provided with --sign-only"
+                        .to_string(),
+                ))
+            },
+        };
+        let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
+        let gas_unit_price = self.gas_options.gas_unit_price.unwrap_or(GAS_UNIT_PRICE);
+        let max_gas = self.gas_options.max_gas.unwrap_or(MAX_GAS_AMOUNT);
+
+        let transaction_factory = TransactionFactory::new(chain_id)
+            .with_gas_unit_price(gas_unit_price)
+            .with_max_gas_amount(max_gas)
+            .with_transaction_expiration_time(self.gas_options.expiration_secs);
+        let transaction = self.sign_transaction(
+            sender_address,
+            sender_public_key,
+            sequence_number,
+            transaction_factory.payload(payload),
+        )?;
+
+        let bytes = bcs::to_bytes(&transaction)?;
+        let signed_transaction = if let Some(path) = &self.sign_only_options.signed_transaction_file
+        {
+            write_to_file(path, "Signed transaction", &bytes)?;
+            None
+        } else {
+            Some(format!("0x{}", hex::encode(bytes)))
+        };
+
+        Ok(TransactionSummary {
+            transaction_hash: transaction.clone().committed_hash().into(),
+            gas_used: None,
+            gas_unit_price: Some(gas_unit_price),
+            max_gas: Some(max_gas),
+            pending: None,
+            sender: Some(sender_address),
+            sequence_number: Some(sequence_number),
+            signed_transaction,
+            signed_transaction_file: self.sign_only_options.signed_transaction_file.clone(),
+            success: None,
+            timestamp_us: None,
+            version: None,
+            vm_status: None,
+        })
+    }
+
     /// Simulate the transaction locally using the debugger, with the gas profiler enabled.
     pub async fn profile_gas(
         &self,
@@ -1813,9 +2155,12 @@ impl TransactionOptions {
             transaction_hash: hash.into(),
             gas_used: Some(output.gas_used()),
             gas_unit_price: Some(gas_unit_price),
+            max_gas: Some(max_gas),
             pending: None,
             sender: Some(sender_address),
             sequence_number: None, // The transaction is not comitted so there is no new sequence number.
+            signed_transaction: None,
+            signed_transaction_file: None,
             success,
             timestamp_us: None,
             version: Some(version), // The transaction is not comitted so there is no new version.
@@ -1985,7 +2330,7 @@ pub struct EntryFunctionArguments {
     ///
     /// Example: `0x842ed41fad9640a2ad08fdd7d3e4f7f505319aac7d67e1c0dd6a7cce8732c7e3::message::set_message`
     #[clap(long, required_unless_present = "json_file")]
-    pub function_id: Option<MemberId>,
+    pub function_id: Option<String>,
 
     #[clap(flatten)]
     pub(crate) type_arg_vec: TypeArgVec,
@@ -2006,14 +2351,29 @@ impl EntryFunctionArguments {
             Ok(self)
         }
     }
-}
 
-impl TryInto<EntryFunction> for EntryFunctionArguments {
-    type Error = CliError;
+    /// Parses the function ID, resolving its address against `named_addresses` if it's named.
+    fn member_id(
+        &self,
+        named_addresses: &BTreeMap<String, AccountAddress>,
+    ) -> CliTypedResult<MemberId> {
+        let function_id = self
+            .function_id
+            .as_ref()
+            .ok_or(CliError::CommandArgumentError(
+                "No function ID provided".to_string(),
+            ))?;
+        parse_member_id_with_named_addresses(function_id, named_addresses)
+    }
 
-    fn try_into(self) -> Result<EntryFunction, Self::Error> {
+    /// Builds the entry function, resolving a named address in the function ID against
+    /// `named_addresses`.
+    pub(crate) fn try_into_entry_function(
+        self,
+        named_addresses: &BTreeMap<String, AccountAddress>,
+    ) -> CliTypedResult<EntryFunction> {
         let entry_function_args = self.check_input_style()?;
-        let function_id: MemberId = (&entry_function_args).try_into()?;
+        let function_id = entry_function_args.member_id(named_addresses)?;
         Ok(EntryFunction::new(
             function_id.module_id,
             function_id.member_id,
@@ -2023,6 +2383,14 @@ impl TryInto<EntryFunction> for EntryFunctionArguments {
     }
 }
 
+impl TryInto<EntryFunction> for EntryFunctionArguments {
+    type Error = CliError;
+
+    fn try_into(self) -> Result<EntryFunction, Self::Error> {
+        self.try_into_entry_function(&BTreeMap::new())
+    }
+}
+
 impl TryInto<MultisigTransactionPayload> for EntryFunctionArguments {
     type Error = CliError;
 
@@ -2035,11 +2403,7 @@ impl TryInto<MemberId> for &EntryFunctionArguments {
     type Error = CliError;
 
     fn try_into(self) -> Result<MemberId, Self::Error> {
-        self.function_id
-            .clone()
-            .ok_or(CliError::CommandArgumentError(
-                "No function ID provided".to_string(),
-            ))
+        self.member_id(&BTreeMap::new())
     }
 }
 
@@ -2114,7 +2478,7 @@ impl TryInto<EntryFunctionArguments> for EntryFunctionArgumentsJSON {
 
     fn try_into(self) -> Result<EntryFunctionArguments, Self::Error> {
         Ok(EntryFunctionArguments {
-            function_id: Some(MemberId::from_str(&self.function_id)?),
+            function_id: Some(self.function_id),
             type_arg_vec: TypeArgVec::try_from(&self.type_args)?,
             arg_vec: ArgWithTypeVec::try_from(&self.args)?,
             json_file: None,
@@ -13,13 +13,15 @@ use aptos_build_info::build_information;
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use aptos_keygen::KeyGen;
 use aptos_logger::{debug, Level};
-use aptos_rest_client::{aptos_api_types::HashValue, Account, Client, FaucetClient, State};
+use aptos_rest_client::{
+    aptos_api_types::HashValue, Account, Client, FaucetClient, State, Transaction,
+};
 use aptos_telemetry::service::telemetry_is_disabled;
 use aptos_types::{
     account_address::create_multisig_account_address,
     chain_id::ChainId,
     on_chain_config::{FeatureFlag, Features},
-    transaction::{authenticator::AuthenticationKey, TransactionPayload},
+    transaction::{authenticator::AuthenticationKey, SignedTransaction, TransactionPayload},
 };
 use itertools::Itertools;
 use move_core_types::{account_address::AccountAddress, language_storage::CORE_CODE_ADDRESS};
@@ -278,6 +280,39 @@ pub async fn get_account_with_state(
     Ok(account_response.into_parts())
 }
 
+/// The on-chain status of a failed transaction only keeps the status code. Simulates the same
+/// payload after the fact to recover the message the VM failed with, e.g. which modules a failed
+/// publish is about. Returns the part of the simulated status the committed one lacks, if the
+/// simulation fails the same way.
+pub async fn explain_failed_execution(
+    client: &aptos_rest_client::Client,
+    transaction: &SignedTransaction,
+    replay_transaction: &SignedTransaction,
+) -> Option<String> {
+    let committed = match client
+        .get_transaction_by_hash(transaction.committed_hash())
+        .await
+        .ok()?
+        .into_inner()
+    {
+        Transaction::UserTransaction(txn) if !txn.info.success => txn,
+        _ => return None,
+    };
+    let simulated = client
+        .simulate(replay_transaction)
+        .await
+        .ok()?
+        .into_inner()
+        .into_iter()
+        .next()?;
+    simulated
+        .info
+        .vm_status
+        .strip_prefix(committed.info.vm_status.as_str())
+        .filter(|message| !message.is_empty())
+        .map(str::to_owned)
+}
+
 /// Retrieves sequence number from the rest client
 pub async fn get_sequence_number(
     client: &aptos_rest_client::Client,
@@ -476,13 +511,15 @@ pub fn start_logger(level: Level) {
     logger.build();
 }
 
-/// For transaction payload and options, either get gas profile or submit for execution.
+/// For transaction payload and options, either sign only, get gas profile or submit for execution.
 pub async fn profile_or_submit(
     payload: TransactionPayload,
     txn_options_ref: &TransactionOptions,
 ) -> CliTypedResult<TransactionSummary> {
-    // Profile gas if needed.
-    if txn_options_ref.profile_gas {
+    // Sign without submitting if needed.
+    if txn_options_ref.sign_only_options.sign_only {
+        txn_options_ref.sign_only(payload)
+    } else if txn_options_ref.profile_gas {
         txn_options_ref.profile_gas(payload).await
     } else {
         // Otherwise submit the transaction.
@@ -982,6 +982,8 @@ impl CliCommand<()> for GenerateUpgradeProposal {
     }
 
     async fn execute(self) -> CliTypedResult<()> {
+        self.move_options
+            .reject_profile_address(self.command_name())?;
         let GenerateUpgradeProposal {
             move_options,
             account,
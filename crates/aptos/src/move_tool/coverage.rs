@@ -143,8 +143,9 @@ impl CliCommand<()> for BytecodeCoverage {
 }
 
 fn compile_coverage(
-    move_options: MovePackageDir,
+    mut move_options: MovePackageDir,
 ) -> CliTypedResult<(CoverageMap, CompiledPackage)> {
+    move_options.resolve_profile_address(None)?;
     let config = BuildConfig {
         dev_mode: move_options.dev,
         additional_named_addresses: move_options.named_addresses(),
@@ -2,17 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::common::{
-    types::{CliCommand, CliError, CliTypedResult, PromptOptions},
+    types::{
+        load_account_arg, CliCommand, CliError, CliTypedResult, ProfileOptions, PromptOptions,
+        RestOptions,
+    },
     utils::{
         check_if_file_exists, create_dir_if_not_exist, dir_default_to_current, read_from_file,
         write_to_user_only_file,
     },
 };
 use anyhow::Context;
+use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
 use clap::Parser;
 use move_binary_format::{
-    binary_views::BinaryIndexedView, file_format::CompiledScript, CompiledModule,
+    access::{ModuleAccess, ScriptAccess},
+    binary_views::BinaryIndexedView,
+    file_format::CompiledScript,
+    CompiledModule,
 };
 use move_bytecode_source_map::{mapping::SourceMapping, utils::source_map_from_file};
 use move_command_line_common::files::{
@@ -21,9 +28,16 @@ use move_command_line_common::files::{
 use move_coverage::coverage_map::CoverageMap;
 use move_disassembler::disassembler::{Disassembler, DisassemblerOptions};
 use move_ir_types::location::Spanned;
-use std::{fs, path::PathBuf};
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 
 const DISASSEMBLED_CODE_FILE: &str = "disassembled-code.move";
+/// Passing this as `--output-path` returns the disassembled code in the result instead of writing
+/// it to a file
+const RESULT_OUTPUT_PATH: &str = "-";
 
 /// Disassemble the Move bytecode pointed to
 ///
@@ -31,18 +45,40 @@ const DISASSEMBLED_CODE_FILE: &str = "disassembled-code.move";
 /// 1. Download the package - aptos move download
 /// 2. Compile the package - aptos move compile
 /// 3. Cd to package and disassemble - aptos move disassemble --bytecode-path ./test.mv
+///
+/// Alternatively, a published module can be fetched and disassembled directly:
+/// aptos move disassemble --account 0x1 --module-name coin
 #[derive(Debug, Parser)]
 pub struct Disassemble {
     /// Treat input file as a script (default is to treat file as a module)
-    #[clap(long)]
+    #[clap(long, conflicts_with = "account")]
     pub is_script: bool,
 
     /// The path to the bytecode file to disassemble;
     ///
     /// let's call it file.mv. We assume that two other files reside under the same directory:
     /// a source map file.mvsm (possibly) and the Move source code file.move.
+    #[clap(long, required_unless_present = "account", conflicts_with = "account")]
+    pub bytecode_path: Option<PathBuf>,
+
+    /// Address of the account the module to disassemble is published under
+    #[clap(long, value_parser = load_account_arg, requires = "module_name")]
+    pub account: Option<AccountAddress>,
+
+    /// Name of the published module to disassemble
+    #[clap(long, requires = "account")]
+    pub module_name: Option<String>,
+
+    /// (Optional) Path to a package that has been built locally
+    ///
+    /// If the package's build directory contains a source map and source file for the module,
+    /// they are used to name locals and show source code in the disassembled output.
+    #[clap(long, value_parser)]
+    pub package_dir: Option<PathBuf>,
+
+    /// Leave out the offset of each instruction within its function
     #[clap(long)]
-    pub bytecode_path: PathBuf,
+    pub no_code_offsets: bool,
 
     /// (Optional) Path to a coverage file for the VM in order to print trace information in the
     /// disassembled output.
@@ -53,18 +89,59 @@ pub struct Disassemble {
     #[clap(long, value_parser)]
     pub(crate) output_dir: Option<PathBuf>,
 
+    /// (Optional) File to write the disassembled code to, or `-` to return it in the result
+    ///
+    /// Defaults to `disassembled-code.move` in the output directory.
+    #[clap(long, value_parser, conflicts_with = "output_dir")]
+    pub(crate) output_path: Option<PathBuf>,
+
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
 }
 
-#[async_trait]
-impl CliCommand<String> for Disassemble {
-    fn command_name(&self) -> &'static str {
-        "Disassemble"
-    }
+/// Result of disassembling a module or script
+#[derive(Debug, Serialize)]
+pub struct DisassembleSummary {
+    /// Where the disassembled code was written, if it was written to a file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+    /// The disassembled code, if it wasn't written to a file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disassembled_code: Option<String>,
+    /// The functions defined in the bytecode, in definition order
+    pub functions: Vec<FunctionCodeSize>,
+}
+
+/// Number of bytecode instructions in a function; native functions have none
+#[derive(Debug, Serialize)]
+pub struct FunctionCodeSize {
+    pub name: String,
+    pub code_size: usize,
+}
+
+impl Disassemble {
+    /// Loads the bytecode to disassemble, along with the path its source map and source file
+    /// would be next to, if any.
+    async fn load_bytecode(&self) -> CliTypedResult<(Vec<u8>, Option<PathBuf>)> {
+        if let (Some(account), Some(module_name)) = (self.account, &self.module_name) {
+            let client = self.rest_options.client(&self.profile_options)?;
+            let bytes = client
+                .get_account_module_bcs(account, module_name)
+                .await?
+                .into_inner();
+            return Ok((bytes.to_vec(), None));
+        }
 
-    async fn execute(self) -> CliTypedResult<String> {
-        let bytecode_path = self.bytecode_path.as_path();
+        let bytecode_path = self.bytecode_path.as_deref().ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "Either --bytecode-path or --account and --module-name must be provided"
+                    .to_string(),
+            )
+        })?;
         let extension = bytecode_path
             .extension()
             .context("Missing file extension for bytecode file")?;
@@ -74,19 +151,59 @@ impl CliCommand<String> for Disassemble {
                 extension, MOVE_COMPILED_EXTENSION
             )));
         }
+        Ok((
+            read_from_file(bytecode_path)?,
+            Some(bytecode_path.to_path_buf()),
+        ))
+    }
+
+    /// Finds the source map and source file of the given module in the package's build
+    /// directory, returning the path they share apart from the extension.
+    fn find_in_package_build(package_dir: &Path, module_name: &str) -> Option<PathBuf> {
+        let build_dir = package_dir.join("build");
+        fs::read_dir(build_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join("source_maps").join(module_name))
+            .find(|path| path.with_extension(SOURCE_MAP_EXTENSION).exists())
+    }
+}
+
+/// Lists the functions defined in the bytecode, with the number of instructions in each.
+fn function_code_sizes(bytecode: &BinaryIndexedView) -> Vec<FunctionCodeSize> {
+    match bytecode {
+        BinaryIndexedView::Module(module) => module
+            .function_defs()
+            .iter()
+            .map(|def| FunctionCodeSize {
+                name: module
+                    .identifier_at(module.function_handle_at(def.function).name)
+                    .to_string(),
+                code_size: def.code.as_ref().map_or(0, |code| code.code.len()),
+            })
+            .collect(),
+        BinaryIndexedView::Script(script) => vec![FunctionCodeSize {
+            name: "main".to_string(),
+            code_size: script.code().code.len(),
+        }],
+    }
+}
 
-        let bytecode_bytes = read_from_file(bytecode_path)?;
-        let move_path = bytecode_path.with_extension(MOVE_EXTENSION);
-        let source_map_path = bytecode_path.with_extension(SOURCE_MAP_EXTENSION);
+#[async_trait]
+impl CliCommand<DisassembleSummary> for Disassemble {
+    fn command_name(&self) -> &'static str {
+        "Disassemble"
+    }
 
-        let source = fs::read_to_string(move_path).ok();
-        let source_map = source_map_from_file(&source_map_path);
+    async fn execute(self) -> CliTypedResult<DisassembleSummary> {
+        let (bytecode_bytes, mut source_path) = self.load_bytecode().await?;
 
         let disassembler_options = DisassemblerOptions {
             print_code: true,
             only_externally_visible: false,
             print_basic_blocks: true,
             print_locals: true,
+            no_code_offsets: self.no_code_offsets,
         };
 
         let no_loc = Spanned::unsafe_no_loc(()).loc;
@@ -102,17 +219,50 @@ impl CliCommand<String> for Disassemble {
             BinaryIndexedView::Module(&module)
         };
 
-        let mut source_mapping = if let Ok(s) = source_map {
+        // A local build of the package takes precedence over files next to the bytecode
+        if let (Some(package_dir), BinaryIndexedView::Module(module)) =
+            (&self.package_dir, &bytecode)
+        {
+            let module_name = module.self_id().name().to_string();
+            source_path = Some(
+                Self::find_in_package_build(package_dir, &module_name).ok_or_else(|| {
+                    CliError::UnexpectedError(format!(
+                        "No source map for module {} in the build of package {}",
+                        module_name,
+                        package_dir.display()
+                    ))
+                })?,
+            );
+        }
+
+        let source_map = source_path
+            .as_ref()
+            .map(|path| source_map_from_file(&path.with_extension(SOURCE_MAP_EXTENSION)));
+        let mut source_mapping = if let Some(Ok(s)) = source_map {
             SourceMapping::new(s, bytecode)
         } else {
             SourceMapping::new_from_view(bytecode, no_loc)
                 .context("Unable to build dummy source mapping")?
         };
 
-        if let Some(source_code) = source {
-            source_mapping.with_source_code((bytecode_path.display().to_string(), source_code));
+        if let Some(path) = &source_path {
+            let source_file = if self.package_dir.is_some() {
+                // Package builds keep the sources next to, not in, the source maps directory
+                let file_name = path.file_name().unwrap_or_default();
+                path.parent()
+                    .and_then(Path::parent)
+                    .map(|dir| dir.join("sources").join(file_name))
+                    .unwrap_or_else(|| path.clone())
+            } else {
+                path.clone()
+            }
+            .with_extension(MOVE_EXTENSION);
+            if let Ok(source_code) = fs::read_to_string(&source_file) {
+                source_mapping.with_source_code((source_file.display().to_string(), source_code));
+            }
         }
 
+        let functions = function_code_sizes(&bytecode);
         let mut disassembler = Disassembler::new(source_mapping, disassembler_options);
 
         if let Some(file_path) = &self.code_coverage_path {
@@ -129,12 +279,25 @@ impl CliCommand<String> for Disassemble {
             .disassemble()
             .map_err(|_err| CliError::UnexpectedError("Unable to disassemble".to_string()))?;
 
-        let output_dir = dir_default_to_current(self.output_dir.clone())?;
-        let disassemble_file = output_dir.join(DISASSEMBLED_CODE_FILE);
+        let disassemble_file = match &self.output_path {
+            Some(path) if path.as_os_str() == RESULT_OUTPUT_PATH => {
+                return Ok(DisassembleSummary {
+                    output_path: None,
+                    disassembled_code: Some(disassemble_string),
+                    functions,
+                });
+            },
+            Some(path) => path.clone(),
+            None => dir_default_to_current(self.output_dir.clone())?.join(DISASSEMBLED_CODE_FILE),
+        };
         check_if_file_exists(disassemble_file.as_path(), self.prompt_options)?;
 
         // Create the directory if it doesn't exist
-        create_dir_if_not_exist(output_dir.as_path())?;
+        if let Some(output_dir) = disassemble_file.parent() {
+            if !output_dir.as_os_str().is_empty() {
+                create_dir_if_not_exist(output_dir)?;
+            }
+        }
 
         // write to file
         write_to_user_only_file(
@@ -143,6 +306,134 @@ impl CliCommand<String> for Disassemble {
             disassemble_string.as_bytes(),
         )?;
 
-        Ok(disassemble_file.as_path().display().to_string())
+        Ok(DisassembleSummary {
+            output_path: Some(disassemble_file.as_path().display().to_string()),
+            disassembled_code: None,
+            functions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_api_types::{
+        X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH, X_APTOS_LEDGER_OLDEST_VERSION,
+        X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION, X_APTOS_OLDEST_BLOCK_HEIGHT,
+    };
+    use httpmock::MockServer;
+    use tempfile::TempDir;
+
+    /// A compiled framework module, used as the bytecode to disassemble.
+    fn coin_module_bytes() -> Vec<u8> {
+        aptos_cached_packages::head_release_bundle()
+            .code_and_compiled_modules()
+            .into_iter()
+            .find(|(_, module)| module.self_id().name().as_str() == "coin")
+            .map(|(code, _)| code.to_vec())
+            .expect("Framework contains the coin module")
+    }
+
+    fn disassemble_command(output_path: PathBuf) -> Disassemble {
+        Disassemble {
+            is_script: false,
+            bytecode_path: None,
+            account: None,
+            module_name: None,
+            package_dir: None,
+            no_code_offsets: false,
+            code_coverage_path: None,
+            output_dir: None,
+            output_path: Some(output_path),
+            prompt_options: PromptOptions::yes(),
+            rest_options: RestOptions::default(),
+            profile_options: ProfileOptions::default(),
+        }
+    }
+
+    fn assert_has_transfer(summary: &DisassembleSummary) {
+        let transfer = summary
+            .functions
+            .iter()
+            .find(|function| function.name == "transfer")
+            .expect("coin::transfer is listed");
+        assert!(transfer.code_size > 0);
+    }
+
+    #[tokio::test]
+    async fn test_disassemble_bytecode_file() {
+        let dir = TempDir::new().unwrap();
+        let bytecode_path = dir.path().join("coin.mv");
+        fs::write(&bytecode_path, coin_module_bytes()).unwrap();
+        let output_path = dir.path().join("coin.asm");
+
+        let mut command = disassemble_command(output_path.clone());
+        command.bytecode_path = Some(bytecode_path);
+        command.no_code_offsets = true;
+        let summary = command.execute().await.unwrap();
+
+        assert_eq!(summary.output_path, Some(output_path.display().to_string()));
+        assert_eq!(summary.disassembled_code, None);
+        assert_has_transfer(&summary);
+        let disassembled = fs::read_to_string(&output_path).unwrap();
+        assert!(disassembled.contains("module 1.coin"));
+        assert!(!disassembled.lines().any(|line| line
+            .strip_prefix('\t')
+            .and_then(|rest| rest.split_once(": "))
+            .map_or(false, |(offset, _)| offset.parse::<u16>().is_ok())));
+    }
+
+    #[tokio::test]
+    async fn test_disassemble_to_result() {
+        let dir = TempDir::new().unwrap();
+        let bytecode_path = dir.path().join("coin.mv");
+        fs::write(&bytecode_path, coin_module_bytes()).unwrap();
+
+        let mut command = disassemble_command(PathBuf::from(RESULT_OUTPUT_PATH));
+        command.bytecode_path = Some(bytecode_path);
+        let summary = command.execute().await.unwrap();
+
+        assert_eq!(summary.output_path, None);
+        assert_has_transfer(&summary);
+        let disassembled = summary.disassembled_code.unwrap();
+        assert!(disassembled.contains("module 1.coin"));
+        assert!(disassembled.lines().any(|line| line
+            .strip_prefix('\t')
+            .and_then(|rest| rest.split_once(": "))
+            .map_or(false, |(offset, _)| offset.parse::<u16>().is_ok())));
+    }
+
+    #[tokio::test]
+    async fn test_disassemble_published_module() {
+        let bytes = coin_module_bytes();
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("GET").path(format!(
+                "/v1/accounts/{}/module/coin",
+                AccountAddress::ONE.to_hex()
+            ));
+            then.status(200)
+                .header(X_APTOS_CHAIN_ID, "4")
+                .header(X_APTOS_LEDGER_VERSION, "1")
+                .header(X_APTOS_LEDGER_TIMESTAMP, "1")
+                .header(X_APTOS_EPOCH, "1")
+                .header(X_APTOS_LEDGER_OLDEST_VERSION, "0")
+                .header(X_APTOS_BLOCK_HEIGHT, "1")
+                .header(X_APTOS_OLDEST_BLOCK_HEIGHT, "0")
+                .body(bytes);
+        });
+
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join("coin.asm");
+        let mut command = disassemble_command(output_path.clone());
+        command.account = Some(AccountAddress::ONE);
+        command.module_name = Some("coin".to_string());
+        command.rest_options = RestOptions::new(Some(server.base_url().parse().unwrap()), None);
+        let summary = command.execute().await.unwrap();
+
+        mock.assert();
+        assert_has_transfer(&summary);
+        let disassembled = fs::read_to_string(&output_path).unwrap();
+        assert!(disassembled.contains("module 1.coin"));
     }
 }
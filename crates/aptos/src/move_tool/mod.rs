@@ -13,8 +13,8 @@ use crate::{
     account::derive_resource_account::ResourceAccountSeed,
     common::{
         types::{
-            load_account_arg, ArgWithTypeJSON, CliConfig, CliError, CliTypedResult,
-            ConfigSearchMode, EntryFunctionArguments, EntryFunctionArgumentsJSON,
+            load_account_arg, AccountAddressWrapper, ArgWithTypeJSON, CliConfig, CliError,
+            CliTypedResult, ConfigSearchMode, EntryFunctionArguments, EntryFunctionArgumentsJSON,
             MoveManifestAccountWrapper, MovePackageDir, ProfileOptions, PromptOptions, RestOptions,
             SaveFile, ScriptFunctionArguments, TransactionOptions, TransactionSummary,
         },
@@ -33,12 +33,13 @@ use crate::{
 };
 use aptos_crypto::HashValue;
 use aptos_framework::{
-    docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy, prover::ProverOptions,
-    BuildOptions, BuiltPackage,
+    dependency_digest, docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy,
+    prover::ProverOptions, BuildOptions, BuiltPackage,
 };
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
-use aptos_rest_client::aptos_api_types::{
-    EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
+use aptos_rest_client::{
+    aptos_api_types::{EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId},
+    Client,
 };
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
@@ -49,7 +50,11 @@ use clap::{Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
 use move_cli::{self, base::test::UnitTestResult};
 use move_command_line_common::env::MOVE_HOME;
-use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::U256};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    u256::U256,
+};
 use move_package::{
     source_package::layout::SourcePackageLayout, BuildConfig, CompilerConfig, CompilerVersion,
 };
@@ -58,7 +63,7 @@ pub use package_hooks::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{Display, Formatter},
     path::{Path, PathBuf},
     str::FromStr,
@@ -300,7 +305,8 @@ impl CliCommand<Vec<String>> for CompilePackage {
         "CompilePackage"
     }
 
-    async fn execute(self) -> CliTypedResult<Vec<String>> {
+    async fn execute(mut self) -> CliTypedResult<Vec<String>> {
+        self.move_options.resolve_profile_address(None)?;
         let build_options = BuildOptions {
             install_dir: self.move_options.output_dir.clone(),
             ..self
@@ -348,7 +354,8 @@ impl CliCommand<CompileScriptOutput> for CompileScript {
         "CompileScript"
     }
 
-    async fn execute(self) -> CliTypedResult<CompileScriptOutput> {
+    async fn execute(mut self) -> CliTypedResult<CompileScriptOutput> {
+        self.move_options.resolve_profile_address(None)?;
         let (bytecode, script_hash) = self.compile_script().await?;
         let script_location = self.output_file.unwrap_or_else(|| {
             self.move_options
@@ -447,7 +454,8 @@ impl CliCommand<&'static str> for TestPackage {
         "TestPackage"
     }
 
-    async fn execute(self) -> CliTypedResult<&'static str> {
+    async fn execute(mut self) -> CliTypedResult<&'static str> {
+        self.move_options.resolve_profile_address(None)?;
         let known_attributes = extended_checks::get_all_attribute_names();
         let mut config = BuildConfig {
             dev_mode: self.move_options.dev,
@@ -530,9 +538,10 @@ impl CliCommand<&'static str> for ProvePackage {
 
     async fn execute(self) -> CliTypedResult<&'static str> {
         let ProvePackage {
-            move_options,
+            mut move_options,
             prover_options,
         } = self;
+        move_options.resolve_profile_address(None)?;
 
         let result = task::spawn_blocking(move || {
             prover_options.prove(
@@ -573,9 +582,10 @@ impl CliCommand<&'static str> for DocumentPackage {
 
     async fn execute(self) -> CliTypedResult<&'static str> {
         let DocumentPackage {
-            move_options,
+            mut move_options,
             docgen_options,
         } = self;
+        move_options.resolve_profile_address(None)?;
         let build_options = BuildOptions {
             dev: move_options.dev,
             with_srcs: false,
@@ -631,9 +641,61 @@ pub struct PublishPackage {
 struct PackagePublicationData {
     metadata_serialized: Vec<u8>,
     compiled_units: Vec<Vec<u8>>,
+    dependency_code: BTreeMap<String, BTreeMap<ModuleId, Vec<u8>>>,
     payload: TransactionPayload,
 }
 
+/// Warns about dependency packages whose code on chain differs from the code the package was built
+/// against, e.g. because a dependency was upgraded after the revision used for the build. This
+/// doesn't fail the publish, since the package may still be compatible with the new code.
+async fn warn_on_changed_dependencies(
+    client: &Client,
+    dependency_code: &BTreeMap<String, BTreeMap<ModuleId, Vec<u8>>>,
+) {
+    let addresses: BTreeSet<AccountAddress> = dependency_code
+        .values()
+        .flat_map(|modules| modules.keys().map(|id| *id.address()))
+        .collect();
+    let mut published_code = BTreeMap::new();
+    for address in addresses {
+        match client.get_account_modules_bcs(address).await {
+            Ok(response) => published_code.extend(
+                response
+                    .into_inner()
+                    .into_iter()
+                    .map(|(id, code)| (ModuleId::from(id), code)),
+            ),
+            // Without the published code there is nothing to compare against
+            Err(_) => return,
+        }
+    }
+    let mut on_chain_code = BTreeMap::new();
+    for (package_name, modules) in dependency_code {
+        let mut package_code = BTreeMap::new();
+        for id in modules.keys() {
+            match published_code.get(id) {
+                Some(code) => package_code.insert(id.clone(), code.clone()),
+                // Dependencies which are not published yet can't have changed
+                None => return,
+            };
+        }
+        on_chain_code.insert(package_name.clone(), package_code);
+    }
+
+    if dependency_digest(&on_chain_code) != dependency_digest(dependency_code) {
+        let changed = dependency_code
+            .iter()
+            .filter(|(package_name, modules)| on_chain_code.get(*package_name) != Some(*modules))
+            .map(|(package_name, _)| package_name.as_str())
+            .join(", ");
+        eprintln!(
+            "Warning: the code of dependencies {} on chain differs from the code the package was \
+            built against. They may have been upgraded since the revision used for the build.",
+            changed
+        );
+    }
+}
+
 /// Build a publication transaction payload and store it in a JSON output file.
 #[derive(Parser)]
 pub struct BuildPublishPayload {
@@ -648,22 +710,25 @@ impl TryInto<PackagePublicationData> for &PublishPackage {
     type Error = CliError;
 
     fn try_into(self) -> Result<PackagePublicationData, Self::Error> {
-        let package_path = self.move_options.get_package_path()?;
+        let mut move_options = self.move_options.clone();
+        move_options.resolve_profile_address(self.txn_options.profile_options.profile_name())?;
+        let package_path = move_options.get_package_path()?;
         let options = self
             .included_artifacts_args
             .included_artifacts
             .build_options(
-                self.move_options.dev,
-                self.move_options.skip_fetch_latest_git_deps,
-                self.move_options.named_addresses(),
-                self.move_options.bytecode_version,
-                self.move_options.compiler_version,
-                self.move_options.skip_attribute_checks,
-                self.move_options.check_test_code,
+                move_options.dev,
+                move_options.skip_fetch_latest_git_deps,
+                move_options.named_addresses(),
+                move_options.bytecode_version,
+                move_options.compiler_version,
+                move_options.skip_attribute_checks,
+                move_options.check_test_code,
             );
         let package = BuiltPackage::build(package_path, options)
             .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
         let compiled_units = package.extract_code();
+        let dependency_code = package.dependency_code();
         let metadata_serialized =
             bcs::to_bytes(&package.extract_metadata()?).expect("PackageMetadata has BCS");
         let payload = aptos_cached_packages::aptos_stdlib::code_publish_package_txn(
@@ -683,6 +748,7 @@ impl TryInto<PackagePublicationData> for &PublishPackage {
         Ok(PackagePublicationData {
             metadata_serialized,
             compiled_units,
+            dependency_code,
             payload,
         })
     }
@@ -793,6 +859,13 @@ impl CliCommand<TransactionSummary> for PublishPackage {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         let package_publication_data: PackagePublicationData = (&self).try_into()?;
+        if !self.txn_options.sign_only_options.sign_only {
+            let client = self
+                .txn_options
+                .rest_options
+                .client(&self.txn_options.profile_options)?;
+            warn_on_changed_dependencies(&client, &package_publication_data.dependency_code).await;
+        }
         profile_or_submit(package_publication_data.payload, &self.txn_options).await
     }
 }
@@ -909,6 +982,7 @@ impl CliCommand<TransactionSummary> for CreateResourceAccountAndPublishPackage {
         };
         let seed = seed_args.seed()?;
 
+        move_options.resolve_profile_address(txn_options.profile_options.profile_name())?;
         let resource_address = create_resource_address(account, &seed);
         move_options.add_named_address(address_name, resource_address.to_string());
 
@@ -1048,7 +1122,9 @@ impl CliCommand<&'static str> for VerifyPackage {
         "VerifyPackage"
     }
 
-    async fn execute(self) -> CliTypedResult<&'static str> {
+    async fn execute(mut self) -> CliTypedResult<&'static str> {
+        self.move_options
+            .resolve_profile_address(self.profile_options.profile_name())?;
         // First build the package locally to get the package metadata
         let build_options = BuildOptions {
             install_dir: self.move_options.output_dir.clone(),
@@ -1175,6 +1251,8 @@ impl CliCommand<&'static str> for CleanPackage {
     }
 
     async fn execute(self) -> CliTypedResult<&'static str> {
+        self.move_options
+            .reject_profile_address(self.command_name())?;
         let path = self.move_options.get_package_path()?;
         let build_dir = path.join("build");
         // Only remove the build dir if it exists, allowing for users to still clean their cache
@@ -1201,6 +1279,9 @@ impl CliCommand<&'static str> for CleanPackage {
     }
 }
 
+/// Default maximum nesting depth of the type arguments given to `RunFunction`
+pub const DEFAULT_MAX_TYPE_ARG_DEPTH: usize = 16;
+
 /// Run a Move function
 #[derive(Parser)]
 pub struct RunFunction {
@@ -1208,6 +1289,47 @@ pub struct RunFunction {
     pub(crate) entry_function_args: EntryFunctionArguments,
     #[clap(flatten)]
     pub(crate) txn_options: TransactionOptions,
+
+    /// Maximum nesting depth allowed for each type argument
+    ///
+    /// For example, `u64` has a depth of 1 and `vector<0x1::option::Option<u64>>` has a depth of 3.
+    #[clap(long, default_value_t = DEFAULT_MAX_TYPE_ARG_DEPTH)]
+    pub(crate) max_type_arg_depth: usize,
+
+    /// Named addresses that can be used as the address of the function ID
+    ///
+    /// Example: alice=0x1234,bob=0x5678
+    #[clap(long, value_parser = crate::common::utils::parse_map::<String, AccountAddressWrapper>, default_value = "")]
+    pub(crate) named_addresses: BTreeMap<String, AccountAddressWrapper>,
+}
+
+fn type_tag_depth(type_tag: &TypeTag) -> usize {
+    match type_tag {
+        TypeTag::Vector(inner) => 1 + type_tag_depth(inner),
+        TypeTag::Struct(struct_tag) => {
+            1 + struct_tag
+                .type_params
+                .iter()
+                .map(type_tag_depth)
+                .max()
+                .unwrap_or(0)
+        },
+        _ => 1,
+    }
+}
+
+/// Ensures none of the type arguments are nested deeper than `max_depth`
+pub(crate) fn check_type_arg_depth(type_args: &[TypeTag], max_depth: usize) -> CliTypedResult<()> {
+    for (index, type_arg) in type_args.iter().enumerate() {
+        let depth = type_tag_depth(type_arg);
+        if depth > max_depth {
+            return Err(CliError::CommandArgumentError(format!(
+                "Type argument {} ({}) has a nesting depth of {}, which exceeds the maximum of {}",
+                index, type_arg, depth, max_depth
+            )));
+        }
+    }
+    Ok(())
 }
 
 #[async_trait]
@@ -1217,8 +1339,17 @@ impl CliCommand<TransactionSummary> for RunFunction {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let named_addresses = self
+            .named_addresses
+            .into_iter()
+            .map(|(key, value)| (key, value.account_address))
+            .collect();
+        let entry_function = self
+            .entry_function_args
+            .try_into_entry_function(&named_addresses)?;
+        check_type_arg_depth(entry_function.ty_args(), self.max_type_arg_depth)?;
         profile_or_submit(
-            TransactionPayload::EntryFunction(self.entry_function_args.try_into()?),
+            TransactionPayload::EntryFunction(entry_function),
             &self.txn_options,
         )
         .await
@@ -1710,6 +1841,15 @@ pub struct MemberId {
 }
 
 fn parse_member_id(function_id: &str) -> CliTypedResult<MemberId> {
+    parse_member_id_with_named_addresses(function_id, &BTreeMap::new())
+}
+
+/// Parses a member ID, looking up the address in `named_addresses` before falling back to
+/// `load_account_arg`.
+pub(crate) fn parse_member_id_with_named_addresses(
+    function_id: &str,
+    named_addresses: &BTreeMap<String, AccountAddress>,
+) -> CliTypedResult<MemberId> {
     let ids: Vec<&str> = function_id.split_terminator("::").collect();
     if ids.len() != 3 {
         return Err(CliError::CommandArgumentError(
@@ -1717,7 +1857,10 @@ fn parse_member_id(function_id: &str) -> CliTypedResult<MemberId> {
                 .to_string(),
         ));
     }
-    let address = load_account_arg(ids.first().unwrap())?;
+    let address = match named_addresses.get(*ids.first().unwrap()) {
+        Some(address) => *address,
+        None => load_account_arg(ids.first().unwrap())?,
+    };
     let module = Identifier::from_str(ids.get(1).unwrap())
         .map_err(|err| CliError::UnableToParse("Module Name", err.to_string()))?;
     let member_id = Identifier::from_str(ids.get(2).unwrap())
@@ -1736,3 +1879,15 @@ impl FromStr for MemberId {
         parse_member_id(s)
     }
 }
+
+impl Display for MemberId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}::{}::{}",
+            self.module_id.address().to_hex_literal(),
+            self.module_id.name(),
+            self.member_id
+        )
+    }
+}
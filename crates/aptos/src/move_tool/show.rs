@@ -52,7 +52,8 @@ impl CliCommand<Vec<EntryABI>> for ShowAbi {
         "ShowAbi"
     }
 
-    async fn execute(self) -> CliTypedResult<Vec<EntryABI>> {
+    async fn execute(mut self) -> CliTypedResult<Vec<EntryABI>> {
+        self.move_options.resolve_profile_address(None)?;
         let build_options = BuildOptions {
             install_dir: self.move_options.output_dir.clone(),
             with_abis: true,
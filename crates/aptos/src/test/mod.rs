@@ -28,7 +28,7 @@ use crate::{
     move_tool::{
         ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
         IncludedArtifactsArgs, InitPackage, MemberId, PublishPackage, RunFunction, RunScript,
-        TestPackage,
+        TestPackage, DEFAULT_MAX_TYPE_ARG_DEPTH,
     },
     node::{
         AnalyzeMode, AnalyzeValidatorPerformance, GetStakePool, InitializeValidator,
@@ -57,7 +57,7 @@ use aptos_rest_client::{
     aptos_api_types::{MoveStructTag, MoveType},
     Transaction,
 };
-use aptos_sdk::move_types::{account_address::AccountAddress, language_storage::ModuleId};
+use aptos_sdk::move_types::account_address::AccountAddress;
 use aptos_temppath::TempPath;
 use aptos_types::on_chain_config::ValidatorSet;
 use move_core_types::ident_str;
@@ -309,10 +309,7 @@ impl CliTestFramework {
     ) -> CliTypedResult<TransactionSummary> {
         RunFunction {
             entry_function_args: EntryFunctionArguments {
-                function_id: Some(MemberId {
-                    module_id: ModuleId::new(AccountAddress::ONE, ident_str!("coin").into()),
-                    member_id: ident_str!("transfer").into(),
-                }),
+                function_id: Some("0x1::coin::transfer".to_string()),
                 arg_vec: ArgWithTypeVec {
                     args: vec![
                         ArgWithType::from_str("address:0xdeadbeefcafebabe").unwrap(),
@@ -330,6 +327,8 @@ impl CliTestFramework {
                 json_file: None,
             },
             txn_options: self.transaction_options(sender_index, gas_options),
+            max_type_arg_depth: DEFAULT_MAX_TYPE_ARG_DEPTH,
+            named_addresses: BTreeMap::new(),
         }
         .execute()
         .await
@@ -592,9 +591,7 @@ impl CliTestFramework {
     ) -> CliTypedResult<TransactionSummary> {
         RunFunction {
             entry_function_args: EntryFunctionArguments {
-                function_id: Some(
-                    MemberId::from_str("0x1::staking_contract::create_staking_contract").unwrap(),
-                ),
+                function_id: Some("0x1::staking_contract::create_staking_contract".to_string()),
                 arg_vec: ArgWithTypeVec {
                     args: vec![
                         ArgWithType::address(self.account_id(operator_index)),
@@ -608,6 +605,8 @@ impl CliTestFramework {
                 json_file: None,
             },
             txn_options: self.transaction_options(owner_index, None),
+            max_type_arg_depth: DEFAULT_MAX_TYPE_ARG_DEPTH,
+            named_addresses: BTreeMap::new(),
         }
         .execute()
         .await
@@ -918,7 +917,7 @@ impl CliTestFramework {
 
         RunFunction {
             entry_function_args: EntryFunctionArguments {
-                function_id: Some(function_id),
+                function_id: Some(function_id.to_string()),
                 arg_vec: ArgWithTypeVec { args: parsed_args },
                 type_arg_vec: TypeArgVec {
                     type_args: parsed_type_args,
@@ -926,6 +925,8 @@ impl CliTestFramework {
                 json_file: None,
             },
             txn_options: self.transaction_options(index, gas_options),
+            max_type_arg_depth: DEFAULT_MAX_TYPE_ARG_DEPTH,
+            named_addresses: BTreeMap::new(),
         }
         .execute()
         .await
@@ -1041,6 +1042,7 @@ impl CliTestFramework {
             package_dir: Some(self.move_dir()),
             output_dir: None,
             named_addresses: Self::named_addresses(account_strs),
+            use_profile_address: None,
             skip_fetch_latest_git_deps: true,
             bytecode_version: None,
             compiler_version: None,
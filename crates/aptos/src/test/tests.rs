@@ -2,11 +2,29 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    move_tool::{ArgWithType, FunctionArgType},
+    common::types::{
+        CliError, GasOptions, MovePackageDir, PrivateKeyInputOptions, RestOptions, SignOnlyOptions,
+        TransactionOptions,
+    },
+    move_tool::{
+        check_type_arg_depth, parse_member_id_with_named_addresses, ArgWithType, FunctionArgType,
+        MemberId, DEFAULT_MAX_TYPE_ARG_DEPTH,
+    },
     CliResult, Tool,
 };
+use aptos_api_types::HashValue;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_global_constants::{GAS_UNIT_PRICE, MAX_GAS_AMOUNT};
+use aptos_keygen::KeyGen;
+use aptos_types::{chain_id::ChainId, transaction::SignedTransaction};
 use clap::Parser;
-use std::str::FromStr;
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
+use move_package::source_package::{
+    manifest_parser::{parse_move_manifest_string, parse_source_manifest},
+    parsed_manifest::SourceManifest,
+};
+use std::{collections::BTreeMap, str::FromStr};
+use tempfile::TempDir;
 
 /// In order to ensure that there aren't duplicate input arguments for untested CLI commands,
 /// we call help on every command to ensure it at least runs
@@ -66,6 +84,7 @@ async fn ensure_every_command_args_work() {
     assert_cmd_not_panic(&["aptos", "move", "clean", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "compile", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "compile-script", "--help"]).await;
+    assert_cmd_not_panic(&["aptos", "move", "disassemble", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "download", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "init", "--help"]).await;
     assert_cmd_not_panic(&["aptos", "move", "list", "--help"]).await;
@@ -119,6 +138,277 @@ async fn ensure_can_parse_args_with_urls() {
     );
 }
 
+#[test]
+fn test_type_arg_depth() {
+    let shallow = vec![
+        TypeTag::from_str("u64").unwrap(),
+        TypeTag::from_str("vector<0x1::option::Option<0x1::aptos_coin::AptosCoin>>").unwrap(),
+    ];
+    check_type_arg_depth(&shallow, DEFAULT_MAX_TYPE_ARG_DEPTH).unwrap();
+    // vector<0x1::option::Option<...>> has a depth of 3
+    check_type_arg_depth(&shallow[1..], 3).unwrap();
+    assert!(check_type_arg_depth(&shallow[1..], 2).is_err());
+
+    let mut deep = TypeTag::U8;
+    for _ in 0..DEFAULT_MAX_TYPE_ARG_DEPTH {
+        deep = TypeTag::Vector(Box::new(deep));
+    }
+    match check_type_arg_depth(&[TypeTag::Bool, deep], DEFAULT_MAX_TYPE_ARG_DEPTH) {
+        Err(CliError::CommandArgumentError(message)) => {
+            assert!(message.starts_with("Type argument 1 "), "{}", message)
+        },
+        result => panic!("Expected a CommandArgumentError, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_parse_member_id_with_named_addresses() {
+    let alice = AccountAddress::from_hex_literal("0x1234").unwrap();
+    let named_addresses = BTreeMap::from([("alice".to_string(), alice)]);
+
+    let member_id =
+        parse_member_id_with_named_addresses("alice::message::set_message", &named_addresses)
+            .unwrap();
+    assert_eq!(member_id.module_id.address(), &alice);
+    assert_eq!(member_id.module_id.name().as_str(), "message");
+    assert_eq!(member_id.member_id.as_str(), "set_message");
+    assert_eq!(
+        member_id.to_string(),
+        format!("{}::message::set_message", alice.to_hex_literal())
+    );
+
+    // Raw addresses are still accepted, and round trip through display
+    let member_id =
+        parse_member_id_with_named_addresses("0x1::coin::transfer", &named_addresses).unwrap();
+    assert_eq!(member_id.module_id.address(), &AccountAddress::ONE);
+    let reparsed = MemberId::from_str(&member_id.to_string()).unwrap();
+    assert_eq!(reparsed.module_id, member_id.module_id);
+    assert_eq!(reparsed.member_id, member_id.member_id);
+
+    // Named addresses aren't known without the map
+    assert!(MemberId::from_str("alice::message::set_message").is_err());
+    assert!(
+        parse_member_id_with_named_addresses("bob::message::set_message", &named_addresses)
+            .is_err()
+    );
+}
+
+fn profile_address_manifest() -> SourceManifest {
+    parse_source_manifest(
+        parse_move_manifest_string(
+            r#"
+            [package]
+            name = "hello"
+            version = "0.0.0"
+
+            [addresses]
+            hello = "_"
+
+            [dev-addresses]
+            hello = "0xbeef"
+            "#
+            .to_string(),
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_use_profile_address() {
+    let manifest = profile_address_manifest();
+    let account = AccountAddress::from_hex_literal("0x1234").unwrap();
+    let parse = |args: &[&str]| {
+        MovePackageDir::try_parse_from(std::iter::once("move").chain(args.iter().copied())).unwrap()
+    };
+
+    // The profile account is injected into the named addresses
+    let mut move_options = parse(&["--use-profile-address", "hello"]);
+    assert_eq!(move_options.use_profile_address.as_deref(), Some("hello"));
+    move_options
+        .add_profile_address("hello", &manifest, Some(account))
+        .unwrap();
+    assert_eq!(move_options.named_addresses().get("hello"), Some(&account));
+
+    // Passing the same value explicitly is fine, a different one is a conflict
+    let mut move_options = parse(&["--named-addresses", "hello=0x1234"]);
+    move_options
+        .add_profile_address("hello", &manifest, Some(account))
+        .unwrap();
+    assert_eq!(move_options.named_addresses().get("hello"), Some(&account));
+    let mut move_options = parse(&["--named-addresses", "hello=0x5678"]);
+    match move_options.add_profile_address("hello", &manifest, Some(account)) {
+        Err(CliError::CommandArgumentError(message)) => {
+            assert!(message.contains("--named-addresses"), "{}", message)
+        },
+        result => panic!("Expected a CommandArgumentError, got {:?}", result),
+    }
+
+    // The named address has to be declared in the Move.toml
+    let mut move_options = parse(&[]);
+    match move_options.add_profile_address("other", &manifest, Some(account)) {
+        Err(CliError::CommandArgumentError(message)) => {
+            assert!(message.contains("not declared"), "{}", message)
+        },
+        result => panic!("Expected a CommandArgumentError, got {:?}", result),
+    }
+    assert!(move_options.named_addresses().is_empty());
+
+    // Without an account, only dev mode can fall back to the dev address
+    assert!(move_options
+        .add_profile_address("hello", &manifest, None)
+        .is_err());
+    let mut move_options = parse(&["--dev"]);
+    move_options
+        .add_profile_address("hello", &manifest, None)
+        .unwrap();
+    assert!(move_options.named_addresses().is_empty());
+}
+
+#[test]
+fn test_reject_profile_address() {
+    let parse = |args: &[&str]| {
+        MovePackageDir::try_parse_from(std::iter::once("move").chain(args.iter().copied())).unwrap()
+    };
+
+    parse(&[]).reject_profile_address("CleanPackage").unwrap();
+    match parse(&["--use-profile-address", "hello"]).reject_profile_address("CleanPackage") {
+        Err(CliError::CommandArgumentError(message)) => {
+            assert!(message.contains("CleanPackage"), "{}", message)
+        },
+        result => panic!("Expected a CommandArgumentError, got {:?}", result),
+    }
+}
+
+#[test]
+fn test_gas_options_precedence() {
+    let parse = |args: &[&str]| {
+        GasOptions::try_parse_from(std::iter::once("gas").chain(args.iter().copied())).unwrap()
+    };
+    let estimated_price = || Some(Ok(150));
+    let simulated = || Some(Ok((1_000, 100_000)));
+    let failed = || Some(Err(CliError::ApiError("unavailable".to_string())));
+
+    // By default, both the price and max gas are estimated, with headroom on the gas used
+    let options = parse(&[]);
+    assert!(options.should_estimate_gas_unit_price());
+    assert!(options.should_estimate_max_gas());
+    assert_eq!(options.resolve_gas_unit_price(estimated_price()), 150);
+    assert_eq!(options.resolve_max_gas(simulated()), 1_500);
+
+    // Headroom is configurable, but capped by the max gas the simulation allowed
+    let options = parse(&["--gas-headroom-percent", "200"]);
+    assert_eq!(options.resolve_max_gas(simulated()), 2_000);
+    assert_eq!(
+        options.resolve_max_gas(Some(Ok((80_000, 100_000)))),
+        100_000
+    );
+
+    // Headroom can't drop the max gas below the simulated gas used
+    assert!(GasOptions::try_parse_from(["gas", "--gas-headroom-percent", "99"]).is_err());
+
+    // Explicit values always win over estimates
+    let options = parse(&["--gas-unit-price", "7", "--max-gas", "9"]);
+    assert!(!options.should_estimate_gas_unit_price());
+    assert!(!options.should_estimate_max_gas());
+    assert_eq!(options.resolve_gas_unit_price(estimated_price()), 7);
+    assert_eq!(options.resolve_max_gas(simulated()), 9);
+
+    // An explicit price still estimates the max gas
+    let options = parse(&["--gas-unit-price", "7"]);
+    assert!(!options.should_estimate_gas_unit_price());
+    assert!(options.should_estimate_max_gas());
+    assert_eq!(options.resolve_gas_unit_price(None), 7);
+    assert_eq!(options.resolve_max_gas(simulated()), 1_500);
+
+    // Only estimating max gas takes the price from the flag, or the default
+    let options = parse(&["--estimate-max-gas-only"]);
+    assert!(!options.should_estimate_gas_unit_price());
+    assert!(options.should_estimate_max_gas());
+    assert_eq!(options.resolve_gas_unit_price(None), GAS_UNIT_PRICE);
+    let options = parse(&["--estimate-max-gas-only", "--gas-unit-price", "7"]);
+    assert_eq!(options.resolve_gas_unit_price(None), 7);
+    assert_eq!(options.resolve_max_gas(simulated()), 1_500);
+
+    // Disabling estimation uses the defaults
+    let options = parse(&["--estimate-gas", "false"]);
+    assert!(!options.should_estimate_gas_unit_price());
+    assert!(!options.should_estimate_max_gas());
+    assert_eq!(options.resolve_gas_unit_price(None), GAS_UNIT_PRICE);
+    assert_eq!(options.resolve_max_gas(None), MAX_GAS_AMOUNT);
+
+    // Failed estimates fall back to the defaults, but explicit values are unaffected
+    let options = parse(&[]);
+    assert_eq!(options.resolve_gas_unit_price(failed()), GAS_UNIT_PRICE);
+    assert_eq!(options.resolve_max_gas(failed()), MAX_GAS_AMOUNT);
+    let options = parse(&["--max-gas", "9"]);
+    assert_eq!(options.resolve_max_gas(failed()), 9);
+}
+
+#[test]
+fn test_sign_only() {
+    let private_key = KeyGen::from_seed([7; 32]).generate_ed25519_private_key();
+    let sender = AccountAddress::from_hex_literal("0xcafe").unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let signed_transaction_file = temp_dir.path().join("signed.bcs");
+
+    let mut txn_options = TransactionOptions {
+        sender_account: Some(sender),
+        private_key_options: PrivateKeyInputOptions::from_private_key(&private_key).unwrap(),
+        // Nothing listens here, so any request to the network would fail
+        rest_options: RestOptions::new(Some("http://127.0.0.1:1".parse().unwrap()), Some(1)),
+        gas_options: GasOptions {
+            gas_unit_price: Some(100),
+            ..Default::default()
+        },
+        sign_only_options: SignOnlyOptions {
+            sign_only: true,
+            signed_transaction_file: Some(signed_transaction_file.clone()),
+            sender_sequence_number: Some(5),
+            chain_id: Some(ChainId::test()),
+        },
+        ..Default::default()
+    };
+    let payload = aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 10);
+    let summary = txn_options.sign_only(payload.clone()).unwrap();
+    assert_eq!(
+        summary.signed_transaction_file.as_ref(),
+        Some(&signed_transaction_file)
+    );
+    assert_eq!(summary.pending, None);
+    assert_eq!(summary.signed_transaction, None);
+
+    let bytes = std::fs::read(&signed_transaction_file).unwrap();
+    let transaction: SignedTransaction = bcs::from_bytes(&bytes).unwrap();
+    transaction.verify_signature().unwrap();
+    assert_eq!(transaction.sender(), sender);
+    assert_eq!(transaction.sequence_number(), 5);
+    assert_eq!(transaction.chain_id(), ChainId::test());
+    assert_eq!(transaction.payload(), &payload);
+    assert_eq!(transaction.gas_unit_price(), 100);
+    assert_eq!(transaction.max_gas_amount(), MAX_GAS_AMOUNT);
+    assert_eq!(
+        summary.transaction_hash,
+        HashValue::from(transaction.clone().committed_hash())
+    );
+
+    // Without a file, the signed transaction is returned in the summary instead
+    txn_options.sign_only_options.signed_transaction_file = None;
+    let summary = txn_options.sign_only(payload).unwrap();
+    assert_eq!(
+        summary.signed_transaction,
+        Some(format!("0x{}", hex::encode(bytes)))
+    );
+    assert_eq!(summary.signed_transaction_file, None);
+
+    // Submitting is refused rather than silently ignoring the option
+    let err = futures::executor::block_on(txn_options.submit_transaction(
+        aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 10),
+    ))
+    .unwrap_err();
+    assert!(matches!(err, CliError::CommandArgumentError(_)));
+}
+
 async fn assert_cmd_not_panic(args: &[&str]) {
     // When a command fails, it will have a panic in it due to an improperly setup command
     // thread 'main' panicked at 'Command propose: Argument names must be unique, but 'assume-yes' is
@@ -0,0 +1,33 @@
+// Copyright © Aptos Foundation
+
+use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
+use aptos_types::validator_txn::Topic;
+use once_cell::sync::Lazy;
+
+/// Count of the txns in the pool that were replaced by a newer txn of the same topic
+pub static REPLACED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vtxn_pool_replaced_txns",
+        "Count of the txns in the pool that were replaced by a newer txn of the same topic",
+        &["topic"]
+    )
+    .unwrap()
+});
+
+/// Count of the txns dropped from the pool because they expired
+pub static EXPIRED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_vtxn_pool_expired_txns",
+        "Count of the txns dropped from the pool because they expired",
+        &["topic"]
+    )
+    .unwrap()
+});
+
+pub fn topic_label(topic: Topic) -> &'static str {
+    match topic {
+        Topic::DKG => "dkg",
+        Topic::JWK_CONSENSUS => "jwk_consensus",
+        _ => "dummy",
+    }
+}
@@ -1,5 +1,8 @@
 // Copyright © Aptos Foundation
 
+mod counters;
+
+use crate::counters::{topic_label, EXPIRED_TXNS, REPLACED_TXNS};
 use aptos_channels::aptos_channel;
 #[cfg(test)]
 use aptos_channels::message_queues::QueueStyle;
@@ -59,7 +62,7 @@ struct PoolState {
     topics: Vec<Topic>,
 
     /// Currently only support 1 txn per topic.
-    txns: HashMap<Topic, Arc<ValidatorTransaction>>,
+    txns: HashMap<Topic, PoolEntry>,
 
     pull_notification_senders: HashMap<Topic, Mutex<PullNotificationSender>>,
 }
@@ -83,12 +86,58 @@ impl PoolState {
     }
 }
 
+impl PoolState {
+    /// Sets the txn of `topic`, replacing the existing one. Return the replaced txn, unless it
+    /// had already expired.
+    fn put(&mut self, topic: Topic, entry: Option<PoolEntry>) -> Option<Arc<ValidatorTransaction>> {
+        let is_replacement = entry.is_some();
+        let old_entry = if let Some(entry) = entry {
+            self.txns.insert(topic, entry)
+        } else {
+            self.txns.remove(&topic)
+        }?;
+
+        if old_entry.is_expired(Instant::now()) {
+            EXPIRED_TXNS.with_label_values(&[topic_label(topic)]).inc();
+            return None;
+        }
+        if is_replacement {
+            REPLACED_TXNS.with_label_values(&[topic_label(topic)]).inc();
+        }
+        Some(old_entry.txn)
+    }
+
+    /// Drops the txns that have expired by `now`.
+    fn remove_expired(&mut self, now: Instant) {
+        self.txns.retain(|topic, entry| {
+            let expired = entry.is_expired(now);
+            if expired {
+                EXPIRED_TXNS.with_label_values(&[topic_label(*topic)]).inc();
+            }
+            !expired
+        });
+    }
+}
+
 impl Default for PoolState {
     fn default() -> Self {
         Self::new(vec![])
     }
 }
 
+struct PoolEntry {
+    txn: Arc<ValidatorTransaction>,
+    /// If set, the txn is no longer pulled and gets dropped once this has passed.
+    expiration: Option<Instant>,
+}
+
+impl PoolEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expiration
+            .map_or(false, |expiration| expiration <= now)
+    }
+}
+
 pub struct ReadClient {
     pool: Arc<Mutex<PoolState>>,
 }
@@ -102,13 +151,14 @@ impl ReadClient {
         filter: TransactionFilter,
     ) -> Vec<ValidatorTransaction> {
         let pull_start_time = Instant::now();
-        let pool = self.pool.lock().unwrap();
+        let mut pool = self.pool.lock().unwrap();
+        pool.remove_expired(pull_start_time);
         let mut ret = vec![];
-        let mut txn_iterator = pool
-            .topics
-            .iter()
-            .copied()
-            .filter_map(|topic| pool.txns.get(&topic).cloned().map(|txn| (topic, txn)));
+        let mut txn_iterator = pool.topics.iter().copied().filter_map(|topic| {
+            pool.txns
+                .get(&topic)
+                .map(|entry| (topic, entry.txn.clone()))
+        });
         while pull_start_time.elapsed() < max_time && max_items >= 1 && max_bytes >= 1 {
             if let Some((topic, txn)) = txn_iterator.next() {
                 if filter.should_exclude(txn.as_ref()) {
@@ -140,13 +190,29 @@ pub struct SingleTopicWriteClient {
 }
 
 impl SingleTopicWriteClient {
+    /// Proposes `txn` for the topic, replacing the current proposal, or withdraws the current
+    /// proposal if `txn` is `None`.
+    /// Return the replaced or withdrawn txn, if it had not expired yet.
     pub fn put(&self, txn: Option<Arc<ValidatorTransaction>>) -> Option<Arc<ValidatorTransaction>> {
-        let mut pool = self.pool.lock().unwrap();
-        if let Some(txn) = txn {
-            pool.txns.insert(self.topic, txn)
-        } else {
-            pool.txns.remove(&self.topic)
-        }
+        let entry = txn.map(|txn| PoolEntry {
+            txn,
+            expiration: None,
+        });
+        self.pool.lock().unwrap().put(self.topic, entry)
+    }
+
+    /// Same as `put(Some(txn))`, but the txn is no longer pulled and gets dropped from the pool
+    /// once `expiration` has passed, e.g. when it is only valid until the end of the epoch.
+    pub fn put_with_expiration(
+        &self,
+        txn: Arc<ValidatorTransaction>,
+        expiration: Instant,
+    ) -> Option<Arc<ValidatorTransaction>> {
+        let entry = PoolEntry {
+            txn,
+            expiration: Some(expiration),
+        };
+        self.pool.lock().unwrap().put(self.topic, Some(entry))
     }
 }
 
@@ -262,3 +328,97 @@ async fn test_validator_txn_pool() {
     let notification = timeout(Duration::from_secs(1), dummy2_notification_rx.next()).await;
     assert_eq!(&dummy2_txn_ver_b, notification.unwrap().unwrap().as_ref());
 }
+
+#[cfg(test)]
+async fn pull_all(read_client: &ReadClient) -> Vec<ValidatorTransaction> {
+    read_client
+        .pull(
+            Duration::from_secs(3600),
+            999,
+            2048,
+            TransactionFilter::PendingTxnHashSet(HashSet::new()),
+        )
+        .await
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_validator_txn_pool_replacement() {
+    let (read_client, mut write_clients) = new(vec![(Topic::DUMMY1, None)]);
+    let write_client = write_clients.pop().unwrap();
+    let replaced_txns = || REPLACED_TXNS.with_label_values(&["dummy"]).get();
+
+    let txn_a = ValidatorTransaction::dummy1(b"txn_a".to_vec());
+    let txn_b = ValidatorTransaction::dummy1(b"txn_b".to_vec());
+    assert!(write_client.put(Some(Arc::new(txn_a.clone()))).is_none());
+
+    // A newer txn of the topic replaces the current one, which is returned.
+    let replaced_txns_before = replaced_txns();
+    let replaced = write_client.put_with_expiration(
+        Arc::new(txn_b.clone()),
+        Instant::now() + Duration::from_secs(3600),
+    );
+    assert_eq!(Some(&txn_a), replaced.as_deref());
+    assert!(replaced_txns() > replaced_txns_before);
+    assert_eq!(vec![txn_b.clone()], pull_all(&read_client).await);
+
+    // Withdrawing is not a replacement.
+    assert_eq!(Some(&txn_b), write_client.put(None).as_deref());
+    assert!(pull_all(&read_client).await.is_empty());
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_validator_txn_pool_expiration() {
+    let (read_client, mut write_clients) = new(vec![(Topic::DUMMY1, None)]);
+    let write_client = write_clients.pop().unwrap();
+
+    let txn = ValidatorTransaction::dummy1(b"txn".to_vec());
+    write_client.put_with_expiration(
+        Arc::new(txn.clone()),
+        Instant::now() + Duration::from_millis(200),
+    );
+    assert_eq!(vec![txn.clone()], pull_all(&read_client).await);
+
+    // Once expired, the txn is skipped by pulls and dropped from the pool.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(pull_all(&read_client).await.is_empty());
+    assert!(read_client.pool.lock().unwrap().txns.is_empty());
+    assert!(write_client.put(None).is_none());
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_validator_txn_pool_epoch_boundary() {
+    let (read_client, mut write_clients) = new(vec![(Topic::DUMMY1, None), (Topic::DUMMY2, None)]);
+    let dummy2_write_client = write_clients.pop().unwrap();
+    let dummy1_write_client = write_clients.pop().unwrap();
+
+    // Txns that are only valid until the end of the epoch, and one that is valid for longer.
+    let epoch_end = Instant::now() + Duration::from_millis(200);
+    let dummy1_txn = ValidatorTransaction::dummy1(b"dummy1_txn".to_vec());
+    let dummy2_txn = ValidatorTransaction::dummy2(b"dummy2_txn".to_vec());
+    dummy1_write_client.put_with_expiration(Arc::new(dummy1_txn.clone()), epoch_end);
+    dummy2_write_client.put(Some(Arc::new(dummy2_txn.clone())));
+    assert_eq!(
+        vec![dummy1_txn.clone(), dummy2_txn.clone()],
+        pull_all(&read_client).await
+    );
+
+    // After the epoch ends, only its txns are cleaned up.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(vec![dummy2_txn.clone()], pull_all(&read_client).await);
+
+    // A txn for the next epoch can be put in right away.
+    let next_epoch_txn = ValidatorTransaction::dummy1(b"next_epoch_txn".to_vec());
+    assert!(dummy1_write_client
+        .put_with_expiration(
+            Arc::new(next_epoch_txn.clone()),
+            Instant::now() + Duration::from_secs(3600),
+        )
+        .is_none());
+    assert_eq!(
+        vec![next_epoch_txn, dummy2_txn],
+        pull_all(&read_client).await
+    );
+}
@@ -0,0 +1,214 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flattens partitioner output back into a single sequential order, for comparing sharded
+//! execution against sequential execution of the same block when debugging determinism.
+
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_types::{
+    block_executor::partitioner::{
+        PartitionedTransactions, RoundId, ShardId, TransactionWithDependencies, TxnIndex,
+        GLOBAL_ROUND_ID, GLOBAL_SHARD_ID,
+    },
+    transaction::analyzed_transaction::AnalyzedTransaction,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+};
+
+/// Where the partitioner placed a transaction. Ordering by this gives the round-robin order of
+/// `SubBlocksForShard::flatten`: round by round, then shard by shard.
+type Position = (RoundId, ShardId, TxnIndex);
+
+/// The transactions of a partitioned block, keyed by their index in the block, along with the
+/// edges `(before, after)` that any sequential order has to respect.
+struct DependencyGraph<'a> {
+    txns: BTreeMap<TxnIndex, (Position, &'a AnalyzedTransaction)>,
+    edges: Vec<(TxnIndex, TxnIndex)>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    fn new(partitioned: &'a PartitionedTransactions) -> Result<Self> {
+        let mut graph = Self {
+            txns: BTreeMap::new(),
+            edges: Vec::new(),
+        };
+
+        // Each shard executes its sub blocks sequentially, round by round.
+        let mut last_txn_of_shards = Vec::new();
+        for sub_blocks in partitioned.sharded_txns() {
+            let mut prev_txn_idx = None;
+            for (round_id, sub_block) in sub_blocks.sub_block_iter().enumerate() {
+                for (txn_idx, txn_with_deps) in sub_block.txn_with_index_iter() {
+                    graph.add_txn(
+                        (round_id, sub_blocks.shard_id, txn_idx),
+                        txn_with_deps,
+                        prev_txn_idx,
+                    )?;
+                    prev_txn_idx = Some(txn_idx);
+                }
+            }
+            last_txn_of_shards.extend(prev_txn_idx);
+        }
+
+        // Global transactions execute sequentially once all the shards are done.
+        let global_start_idx = partitioned.num_sharded_txns();
+        for (pos, txn_with_deps) in partitioned.global_txns.iter().enumerate() {
+            let txn_idx = global_start_idx + pos;
+            let prev_txn_idx = if pos == 0 {
+                graph
+                    .edges
+                    .extend(last_txn_of_shards.iter().map(|&last| (last, txn_idx)));
+                None
+            } else {
+                Some(txn_idx - 1)
+            };
+            graph.add_txn(
+                (GLOBAL_ROUND_ID, GLOBAL_SHARD_ID, txn_idx),
+                txn_with_deps,
+                prev_txn_idx,
+            )?;
+        }
+
+        graph.check_required_edges(partitioned)?;
+        Ok(graph)
+    }
+
+    fn add_txn(
+        &mut self,
+        position: Position,
+        txn_with_deps: &'a TransactionWithDependencies<AnalyzedTransaction>,
+        prev_txn_idx: Option<TxnIndex>,
+    ) -> Result<()> {
+        let txn_idx = position.2;
+        ensure!(
+            self.txns
+                .insert(txn_idx, (position, txn_with_deps.txn()))
+                .is_none(),
+            "Transaction index {} appears more than once",
+            txn_idx
+        );
+        self.edges.extend(prev_txn_idx.map(|prev| (prev, txn_idx)));
+        for (required_idx, _) in txn_with_deps
+            .cross_shard_dependencies()
+            .required_edges_iter()
+        {
+            self.edges.push((required_idx.txn_index, txn_idx));
+        }
+        Ok(())
+    }
+
+    /// Makes sure every required edge points at the round and shard the transaction is in.
+    fn check_required_edges(&self, partitioned: &PartitionedTransactions) -> Result<()> {
+        let all_txns = partitioned
+            .sharded_txns()
+            .iter()
+            .flat_map(|sub_blocks| sub_blocks.iter())
+            .chain(partitioned.global_txns.iter());
+        for txn_with_deps in all_txns {
+            for (required_idx, _) in txn_with_deps
+                .cross_shard_dependencies()
+                .required_edges_iter()
+            {
+                let (position, _) = self.txns.get(&required_idx.txn_index).ok_or_else(|| {
+                    anyhow!("Required transaction {:?} doesn't exist", required_idx)
+                })?;
+                ensure!(
+                    (position.0, position.1) == (required_idx.round_id, required_idx.shard_id),
+                    "Required transaction {:?} is in round {} shard {}",
+                    required_idx,
+                    position.0,
+                    position.1
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merges the sub blocks of all the shards and rounds into a single global order that respects
+/// the sequential order within each shard and all the cross shard dependencies. Where there is a
+/// choice, transactions are taken round by round and shard by shard.
+///
+/// Fails if the dependencies form a cycle, since no sequential order can satisfy them.
+pub fn flatten_to_global_order(
+    partitioned: &PartitionedTransactions,
+) -> Result<Vec<AnalyzedTransaction>> {
+    let graph = DependencyGraph::new(partitioned)?;
+
+    let mut num_pending_deps: HashMap<TxnIndex, usize> = HashMap::new();
+    let mut dependents: HashMap<TxnIndex, Vec<TxnIndex>> = HashMap::new();
+    for &(before, after) in &graph.edges {
+        *num_pending_deps.entry(after).or_default() += 1;
+        dependents.entry(before).or_default().push(after);
+    }
+
+    let mut ready: BinaryHeap<Reverse<Position>> = graph
+        .txns
+        .iter()
+        .filter(|(txn_idx, _)| !num_pending_deps.contains_key(txn_idx))
+        .map(|(_, (position, _))| Reverse(*position))
+        .collect();
+    let mut order = Vec::with_capacity(graph.txns.len());
+    while let Some(Reverse(position)) = ready.pop() {
+        let txn_idx = position.2;
+        order.push(graph.txns[&txn_idx].1.clone());
+        for dependent in dependents.remove(&txn_idx).unwrap_or_default() {
+            let num_pending = num_pending_deps
+                .get_mut(&dependent)
+                .expect("Dependent must have pending dependencies");
+            *num_pending -= 1;
+            if *num_pending == 0 {
+                num_pending_deps.remove(&dependent);
+                ready.push(Reverse(graph.txns[&dependent].0));
+            }
+        }
+    }
+
+    if order.len() != graph.txns.len() {
+        bail!(
+            "Dependency cycle between transactions {:?}",
+            num_pending_deps.keys().collect::<Vec<_>>()
+        );
+    }
+    Ok(order)
+}
+
+/// Checks that `order` contains exactly the transactions of `partitioned`, and that no
+/// transaction comes before one it depends on.
+pub fn verify_no_dependency_violations(
+    order: &[AnalyzedTransaction],
+    partitioned: &PartitionedTransactions,
+) -> Result<()> {
+    let graph = DependencyGraph::new(partitioned)?;
+    ensure!(
+        order.len() == graph.txns.len(),
+        "Order has {} transactions, but the block has {}",
+        order.len(),
+        graph.txns.len()
+    );
+
+    let order_pos_by_txn: HashMap<&AnalyzedTransaction, usize> = order
+        .iter()
+        .enumerate()
+        .map(|(order_pos, txn)| (txn, order_pos))
+        .collect();
+    let mut order_pos_by_txn_idx = HashMap::new();
+    for (txn_idx, (_, txn)) in &graph.txns {
+        let order_pos = order_pos_by_txn
+            .get(txn)
+            .ok_or_else(|| anyhow!("Transaction {} is missing from the order", txn_idx))?;
+        order_pos_by_txn_idx.insert(*txn_idx, *order_pos);
+    }
+
+    for (before, after) in &graph.edges {
+        ensure!(
+            order_pos_by_txn_idx[before] < order_pos_by_txn_idx[after],
+            "Transaction {} must come before transaction {}",
+            before,
+            after
+        );
+    }
+    Ok(())
+}
@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 
 use aptos_block_partitioner::{
-    test_utils::P2PBlockGenerator, v2::config::PartitionerV2Config, PartitionerConfig,
+    flatten::{flatten_to_global_order, verify_no_dependency_violations},
+    test_utils::P2PBlockGenerator,
+    v2::config::PartitionerV2Config,
+    PartitionerConfig,
 };
 use aptos_logger::info;
 use clap::Parser;
@@ -25,6 +28,11 @@ struct Args {
 
     #[clap(long, default_value_t = 48)]
     pub num_shards: usize,
+
+    /// Flatten each partitioned block into a global order and check it against the
+    /// cross-shard dependencies.
+    #[clap(long)]
+    pub flatten_check: bool,
 }
 
 fn main() {
@@ -44,9 +52,15 @@ fn main() {
         let transactions = block_gen.rand_block(&mut rng, args.block_size);
         info!("Starting to partition");
         let now = Instant::now();
-        let _partitioned = partitioner.partition(transactions.clone(), args.num_shards);
+        let partitioned = partitioner.partition(transactions.clone(), args.num_shards);
         let elapsed = now.elapsed();
         info!("Time taken to partition: {:?}", elapsed);
+        if args.flatten_check {
+            let order = flatten_to_global_order(&partitioned).expect("Failed to flatten block");
+            verify_no_dependency_violations(&order, &partitioned)
+                .expect("Flattened order violates dependencies");
+            info!("Flattened {} transactions into a global order", order.len());
+        }
     }
 }
 
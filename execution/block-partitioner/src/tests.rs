@@ -2,16 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    flatten::{flatten_to_global_order, verify_no_dependency_violations},
     test_utils::{
         create_non_conflicting_p2p_transaction, create_signed_p2p_transaction,
-        generate_test_account, verify_partitioner_output,
+        generate_test_account, verify_partitioner_output, P2PBlockGenerator,
     },
     v2::config::PartitionerV2Config,
     PartitionerConfig,
 };
-use aptos_types::{block_executor::partitioner::SubBlocksForShard, transaction::Transaction};
+use aptos_types::{
+    block_executor::partitioner::{
+        CrossShardDependencies, PartitionedTransactions, ShardedTxnIndex, SubBlock,
+        SubBlocksForShard, TransactionWithDependencies,
+    },
+    transaction::{analyzed_transaction::AnalyzedTransaction, Transaction},
+};
 use move_core_types::account_address::AccountAddress;
-use rand::{rngs::OsRng, Rng};
+use rand::{rngs::OsRng, thread_rng, Rng};
 use std::{collections::HashMap, sync::Mutex};
 
 #[test]
@@ -143,3 +150,89 @@ fn test_no_conflict_across_shards_in_non_last_rounds() {
         }
     }
 }
+
+/// Builds a block with 2 shards and 2 rounds, plus a global transaction:
+/// - txn 0 in round 0, shard 0
+/// - txn 1 in round 0, shard 1
+/// - txn 2 in round 1, shard 0, which requires txn 3
+/// - txn 3 in round 1, shard 1, which requires txn 1
+/// - txn 4 in the global sub block, which requires txn 2
+fn partitioned_block_with_deps(
+    txns: &[AnalyzedTransaction],
+    extra_required_edge: Option<(usize, ShardedTxnIndex)>,
+) -> PartitionedTransactions {
+    let indices = [
+        ShardedTxnIndex::new(0, 0, 0),
+        ShardedTxnIndex::new(1, 1, 0),
+        ShardedTxnIndex::new(2, 0, 1),
+        ShardedTxnIndex::new(3, 1, 1),
+    ];
+    let mut required_edges = vec![(2, indices[3]), (3, indices[1])];
+    required_edges.extend(extra_required_edge);
+    let mut deps = vec![CrossShardDependencies::default(); txns.len()];
+    for (txn_idx, required_idx) in required_edges {
+        let location = txns[required_idx.txn_index].write_hints()[0].clone();
+        deps[txn_idx].add_required_edge(required_idx, location);
+    }
+    let txn_with_deps = |txn_idx: usize| {
+        TransactionWithDependencies::new(txns[txn_idx].clone(), deps[txn_idx].clone())
+    };
+    let sub_block = |txn_idx: usize| SubBlock::new(txn_idx, vec![txn_with_deps(txn_idx)]);
+
+    let mut global_deps = CrossShardDependencies::default();
+    global_deps.add_required_edge(indices[2], txns[2].write_hints()[0].clone());
+    PartitionedTransactions::new(
+        vec![
+            SubBlocksForShard::new(0, vec![sub_block(0), sub_block(2)]),
+            SubBlocksForShard::new(1, vec![sub_block(1), sub_block(3)]),
+        ],
+        vec![TransactionWithDependencies::new(
+            txns[4].clone(),
+            global_deps,
+        )],
+    )
+}
+
+#[test]
+fn test_flatten_to_global_order_respects_dependencies() {
+    let txns: Vec<AnalyzedTransaction> = (0..5)
+        .map(|_| create_non_conflicting_p2p_transaction())
+        .collect();
+    let partitioned = partitioned_block_with_deps(&txns, None);
+
+    let order = flatten_to_global_order(&partitioned).unwrap();
+    let expected: Vec<AnalyzedTransaction> =
+        [0, 1, 3, 2, 4].iter().map(|&i| txns[i].clone()).collect();
+    assert_eq!(order, expected);
+    verify_no_dependency_violations(&order, &partitioned).unwrap();
+
+    // The round-robin order ignores the dependency of txn 2 on txn 3.
+    let round_robin = PartitionedTransactions::flatten(partitioned.clone());
+    assert!(verify_no_dependency_violations(&round_robin, &partitioned).is_err());
+    // Missing transactions are caught too.
+    assert!(verify_no_dependency_violations(&order[..4], &partitioned).is_err());
+}
+
+#[test]
+fn test_flatten_to_global_order_rejects_cycles() {
+    let txns: Vec<AnalyzedTransaction> = (0..5)
+        .map(|_| create_non_conflicting_p2p_transaction())
+        .collect();
+    // Txn 3 requiring txn 2 closes a cycle with txn 2 requiring txn 3.
+    let partitioned = partitioned_block_with_deps(&txns, Some((3, ShardedTxnIndex::new(2, 0, 1))));
+    assert!(flatten_to_global_order(&partitioned).is_err());
+}
+
+#[test]
+fn test_flatten_partitioner_output() {
+    let mut rng = thread_rng();
+    let block_gen = P2PBlockGenerator::new(100);
+    let partitioner = PartitionerV2Config::default().build();
+    for _ in 0..10 {
+        let txns = block_gen.rand_block(&mut rng, 200);
+        let partitioned = partitioner.partition(txns.clone(), 4);
+        let order = flatten_to_global_order(&partitioned).unwrap();
+        assert_eq!(order.len(), txns.len());
+        verify_no_dependency_violations(&order, &partitioned).unwrap();
+    }
+}
@@ -94,3 +94,55 @@ fn test_partitioner_v2_connected_component_determinism() {
         assert_deterministic_result(partitioner);
     }
 }
+
+#[test]
+fn test_partitioner_v2_high_conflict_round_limit() {
+    // With only a few accounts nearly every pair of txns conflicts, so discarding keeps spilling.
+    let block_generator = P2PBlockGenerator::new(3);
+    let mut rng = thread_rng();
+    let num_shards = 8;
+    for max_partitioning_rounds in [1, 2, 4] {
+        for merge_discarded in [false, true] {
+            let partitioner = PartitionerV2::new(
+                8,
+                max_partitioning_rounds,
+                0.9,
+                64,
+                !merge_discarded,
+                Box::new(UniformPartitioner {}),
+            );
+            let block = block_generator.rand_block(&mut rng, 800);
+            let block_clone = block.clone();
+            let partitioned = partitioner.partition(block, num_shards);
+            crate::test_utils::verify_partitioner_output(&block_clone, &partitioned);
+
+            let num_txns_by_round = partitioned.num_txns_by_round();
+            let num_global_txns = partitioned.global_txns.len();
+            assert!(num_txns_by_round.len() <= max_partitioning_rounds);
+            assert_eq!(
+                800,
+                num_txns_by_round.iter().sum::<usize>() + num_global_txns
+            );
+
+            if merge_discarded {
+                // The merged last round is taken out of the shards and becomes the global txns.
+                assert!(num_txns_by_round.len() < max_partitioning_rounds);
+                if max_partitioning_rounds == 1 {
+                    assert_eq!(0, partitioned.num_rounds());
+                    assert_eq!(800, num_global_txns);
+                }
+            } else {
+                // The remainder stays spread over the shards it was pre-partitioned to.
+                assert_eq!(0, num_global_txns);
+                assert!(partitioned.num_rounds() > 0);
+                let last_round = partitioned.num_rounds() - 1;
+                let num_non_empty_shards = partitioned
+                    .num_txns_in_round_by_shard(last_round)
+                    .iter()
+                    .filter(|&&num_txns| num_txns > 0)
+                    .count();
+                assert!(num_non_empty_shards > 1);
+            }
+        }
+    }
+}
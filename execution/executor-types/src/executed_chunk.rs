@@ -84,6 +84,8 @@ impl ExecutedChunk {
 
     pub fn into_chunk_commit_notification(self) -> ChunkCommitNotification {
         let reconfiguration_occurred = self.has_reconfiguration();
+        let committed_version = (!self.ledger_update_output.to_commit.is_empty())
+            .then(|| self.ledger_update_output.transaction_accumulator.version());
 
         let mut committed_transactions =
             Vec::with_capacity(self.ledger_update_output.to_commit.len());
@@ -111,6 +113,7 @@ impl ExecutedChunk {
         ChunkCommitNotification {
             committed_transactions,
             subscribable_events,
+            committed_version,
             reconfiguration_occurred,
         }
     }
@@ -282,6 +282,7 @@ pub trait TransactionReplayer: Send {
 pub struct ChunkCommitNotification {
     pub subscribable_events: Vec<ContractEvent>,
     pub committed_transactions: Vec<Transaction>,
+    pub committed_version: Option<Version>, // The version of the last committed transaction (if any)
     pub reconfiguration_occurred: bool,
 }
 
@@ -191,6 +191,9 @@ pub enum LogEvent {
     CallbackFail,
     NetworkSendFail,
 
+    // state sync commit events
+    Duplicate,
+
     // garbage-collect txns events
     SystemTTLExpiration,
     ClientExpiration,
@@ -138,6 +138,16 @@ fn spawn_commit_notification_handler<NetworkClient, TransactionValidator>(
 
     tokio::spawn(async move {
         while let Some(commit_notification) = mempool_listener.next().await {
+            // State sync may notify mempool more than once for the same commit (e.g.,
+            // if a chunk is retried), so ignore notifications we've already processed.
+            if !mempool_listener.record_commit(&commit_notification) {
+                debug!(
+                    committed_version = ?commit_notification.committed_version,
+                    highest_committed_version = ?mempool_listener.highest_committed_version(),
+                    LogSchema::event_log(LogEntry::StateSyncCommit, LogEvent::Duplicate),
+                );
+                continue;
+            }
             handle_commit_notification(&mempool, &mempool_validator, commit_notification);
         }
     });
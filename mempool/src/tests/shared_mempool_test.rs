@@ -81,7 +81,7 @@ async fn test_mempool_notify_committed_txns() {
     let committed_txns = vec![Transaction::UserTransaction(committed_txn)];
     assert!(smp
         .mempool_notifier
-        .notify_new_commit(committed_txns, 1)
+        .notify_new_commit(committed_txns, 1, None)
         .await
         .is_ok());
 
@@ -19,6 +19,7 @@ use aptos_types::{
     transaction::Version,
 };
 use futures::{channel::mpsc::SendError, stream::FusedStream, Stream};
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
@@ -44,6 +45,8 @@ const RECONFIG_NOTIFICATION_CHANNEL_SIZE: usize = 1;
 pub enum Error {
     #[error("Cannot subscribe to zero event keys!")]
     CannotSubscribeToZeroEventKeys,
+    #[error("Cannot subscribe to zero event types!")]
+    CannotSubscribeToZeroEventTypes,
     #[error("Missing event subscription! Subscription ID: {0}")]
     MissingEventSubscription(u64),
     #[error("Unable to send event notification! Error: {0}")]
@@ -77,6 +80,8 @@ pub struct EventSubscriptionService {
     // Event subscription registry
     event_key_subscriptions: HashMap<EventKey, HashSet<SubscriptionId>>,
     event_v2_tag_subscriptions: HashMap<String, HashSet<SubscriptionId>>,
+    event_type_subscriptions: HashMap<TypeTag, HashSet<SubscriptionId>>,
+    all_event_subscriptions: HashSet<SubscriptionId>,
     subscription_id_to_event_subscription: HashMap<SubscriptionId, EventSubscription>,
 
     // Reconfig subscription registry
@@ -94,6 +99,8 @@ impl EventSubscriptionService {
         Self {
             event_key_subscriptions: HashMap::new(),
             event_v2_tag_subscriptions: HashMap::new(),
+            event_type_subscriptions: HashMap::new(),
+            all_event_subscriptions: HashSet::new(),
             subscription_id_to_event_subscription: HashMap::new(),
             reconfig_subscriptions: HashMap::new(),
             storage,
@@ -117,26 +124,7 @@ impl EventSubscriptionService {
             return Err(Error::CannotSubscribeToZeroEventKeys);
         }
 
-        let (notification_sender, notification_receiver) =
-            aptos_channel::new(QueueStyle::KLAST, EVENT_NOTIFICATION_CHANNEL_SIZE, None);
-
-        // Create a new event subscription
-        let subscription_id = self.get_new_subscription_id();
-        let event_subscription = EventSubscription {
-            notification_sender,
-            event_buffer: vec![],
-        };
-
-        // Store the new subscription
-        if let Some(old_subscription) = self
-            .subscription_id_to_event_subscription
-            .insert(subscription_id, event_subscription)
-        {
-            return Err(Error::UnexpectedErrorEncountered(format!(
-                "Duplicate event subscription found! This should not occur! ID: {}, subscription: {:?}",
-                subscription_id, old_subscription
-            )));
-        }
+        let (subscription_id, event_listener) = self.create_event_subscription()?;
 
         // Update the event key subscriptions to include the new subscription
         for event_key in event_keys {
@@ -158,9 +146,72 @@ impl EventSubscriptionService {
                 .or_insert_with(|| HashSet::from_iter([subscription_id].iter().cloned()));
         }
 
-        Ok(EventNotificationListener {
+        Ok(event_listener)
+    }
+
+    /// Returns an EventNotificationListener that will only be sent the events
+    /// whose type matches one of the given struct tags. This applies to both
+    /// v1 and v2 events, and the filtering happens before the events are
+    /// buffered, so the subscriber never sees events of other types.
+    pub fn subscribe_to_event_types(
+        &mut self,
+        type_tags: Vec<StructTag>,
+    ) -> Result<EventNotificationListener, Error> {
+        if type_tags.is_empty() {
+            return Err(Error::CannotSubscribeToZeroEventTypes);
+        }
+
+        let (subscription_id, event_listener) = self.create_event_subscription()?;
+
+        // Update the event type subscriptions to include the new subscription
+        for type_tag in type_tags {
+            self.event_type_subscriptions
+                .entry(TypeTag::Struct(Box::new(type_tag)))
+                .or_default()
+                .insert(subscription_id);
+        }
+
+        Ok(event_listener)
+    }
+
+    /// Returns an EventNotificationListener that will be sent every event
+    /// processed by the subscription service, regardless of key or type.
+    pub fn subscribe_all(&mut self) -> Result<EventNotificationListener, Error> {
+        let (subscription_id, event_listener) = self.create_event_subscription()?;
+        self.all_event_subscriptions.insert(subscription_id);
+
+        Ok(event_listener)
+    }
+
+    /// Creates and stores a new event subscription, returning the
+    /// subscription ID and the listener for the subscriber.
+    fn create_event_subscription(
+        &mut self,
+    ) -> Result<(SubscriptionId, EventNotificationListener), Error> {
+        let (notification_sender, notification_receiver) =
+            aptos_channel::new(QueueStyle::KLAST, EVENT_NOTIFICATION_CHANNEL_SIZE, None);
+
+        // Create a new event subscription
+        let subscription_id = self.get_new_subscription_id();
+        let event_subscription = EventSubscription {
+            notification_sender,
+            event_buffer: vec![],
+        };
+
+        // Store the new subscription
+        if let Some(old_subscription) = self
+            .subscription_id_to_event_subscription
+            .insert(subscription_id, event_subscription)
+        {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Duplicate event subscription found! This should not occur! ID: {}, subscription: {:?}",
+                subscription_id, old_subscription
+            )));
+        }
+
+        Ok((subscription_id, EventNotificationListener {
             notification_receiver,
-        })
+        }))
     }
 
     /// Returns a ReconfigNotificationListener that can be monitored for
@@ -222,20 +273,24 @@ impl EventSubscriptionService {
                     self.event_v2_tag_subscriptions.get(&tag)
                 },
             };
-            if let Some(subscription_ids) = maybe_subscription_ids {
-                // Add the event to the subscription's pending event buffer
-                // and store the subscriptions that will need to notified once all
-                // events have been processed.
-                for subscription_id in subscription_ids.iter() {
-                    if let Some(event_subscription) = self
-                        .subscription_id_to_event_subscription
-                        .get_mut(subscription_id)
-                    {
-                        event_subscription.buffer_event(event.clone());
-                        event_subscription_ids_to_notify.insert(*subscription_id);
-                    } else {
-                        return Err(Error::MissingEventSubscription(*subscription_id));
-                    }
+            let subscription_ids = maybe_subscription_ids
+                .into_iter()
+                .chain(self.event_type_subscriptions.get(event.type_tag()))
+                .flatten()
+                .chain(self.all_event_subscriptions.iter());
+
+            // Add the event to the subscription's pending event buffer
+            // and store the subscriptions that will need to notified once all
+            // events have been processed.
+            for subscription_id in subscription_ids {
+                if let Some(event_subscription) = self
+                    .subscription_id_to_event_subscription
+                    .get_mut(subscription_id)
+                {
+                    event_subscription.buffer_event(event.clone());
+                    event_subscription_ids_to_notify.insert(*subscription_id);
+                } else {
+                    return Err(Error::MissingEventSubscription(*subscription_id));
                 }
             }
 
@@ -23,7 +23,7 @@ use aptos_types::{
 use aptos_vm::AptosVM;
 use claims::{assert_lt, assert_matches, assert_ok};
 use futures::{FutureExt, StreamExt};
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use serde::{Deserialize, Serialize};
 use std::{convert::TryInto, str::FromStr, sync::Arc};
 
@@ -367,6 +367,12 @@ fn test_no_events_no_subscribers() {
         Err(Error::CannotSubscribeToZeroEventKeys)
     );
 
+    // Attempt to subscribe to zero event types
+    assert_matches!(
+        event_service.subscribe_to_event_types(vec![]),
+        Err(Error::CannotSubscribeToZeroEventTypes)
+    );
+
     // Add subscribers to the service
     let _event_listener =
         event_service.subscribe_to_events(vec![create_random_event_key()], vec![]);
@@ -413,6 +419,81 @@ fn test_event_v2_subscription_by_tag() {
     verify_no_event_notifications(vec![&mut listener_2]);
 }
 
+#[test]
+fn test_event_type_subscriptions() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Create the event types and keys
+    let event_type_1 = StructTag::from_str("0x0::module1::Event1").unwrap();
+    let event_type_2 = StructTag::from_str("0x0::module2::Event2").unwrap();
+    let event_key_1 = create_random_event_key();
+    let reconfig_event_key = on_chain_config::new_epoch_event_key();
+
+    // Create the filtered, unfiltered and reconfiguration subscribers
+    let mut type_listener = event_service
+        .subscribe_to_event_types(vec![event_type_1.clone()])
+        .unwrap();
+    let mut all_listener = event_service.subscribe_all().unwrap();
+    let mut reconfig_listener = event_service.subscribe_to_reconfigurations().unwrap();
+
+    // Notify the service of a mix of event types, including a reconfiguration.
+    // Note: the reconfiguration must be at version 0, as only genesis is in storage.
+    let version = 0;
+    let event_1_v1 = ContractEvent::new_v1(
+        event_key_1,
+        0,
+        TypeTag::Struct(Box::new(event_type_1.clone())),
+        b"abc".to_vec(),
+    );
+    let event_1_v2 =
+        ContractEvent::new_v2(TypeTag::Struct(Box::new(event_type_1)), b"def".to_vec());
+    let event_2_v2 =
+        ContractEvent::new_v2(TypeTag::Struct(Box::new(event_type_2)), b"ghi".to_vec());
+    let reconfig_event = create_test_event(reconfig_event_key);
+    notify_events(&mut event_service, version, vec![
+        event_1_v1.clone(),
+        event_2_v2.clone(),
+        reconfig_event.clone(),
+        event_1_v2.clone(),
+    ]);
+
+    // Verify the filtered listener only received the matching events
+    verify_event_notification_received(vec![&mut type_listener], version, vec![
+        event_1_v1.clone(),
+        event_1_v2.clone(),
+    ]);
+    verify_event_notification_received(vec![&mut all_listener], version, vec![
+        event_1_v1.clone(),
+        event_2_v2.clone(),
+        reconfig_event,
+        event_1_v2.clone(),
+    ]);
+    verify_reconfig_notifications_received(vec![&mut reconfig_listener], version, 1);
+
+    // Notify the service of events in the new epoch and verify the filter still applies
+    let next_version = version + 1;
+    notify_events(&mut event_service, next_version, vec![
+        event_2_v2.clone(),
+        event_1_v2.clone(),
+    ]);
+    verify_event_notification_received(vec![&mut type_listener], next_version, vec![
+        event_1_v2.clone()
+    ]);
+    verify_event_notification_received(vec![&mut all_listener], next_version, vec![
+        event_2_v2.clone(),
+        event_1_v2,
+    ]);
+    verify_no_reconfig_notifications(vec![&mut reconfig_listener]);
+
+    // Notify the service of non-matching events only and verify the filtered listener is skipped
+    notify_events(&mut event_service, next_version + 1, vec![
+        event_2_v2.clone()
+    ]);
+    verify_no_event_notifications(vec![&mut type_listener]);
+    verify_event_notification_received(vec![&mut all_listener], next_version + 1, vec![event_2_v2]);
+}
+
 /// Defines a new on-chain config for test purposes.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct TestOnChainConfig {
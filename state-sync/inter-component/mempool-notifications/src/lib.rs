@@ -4,7 +4,10 @@
 
 #![forbid(unsafe_code)]
 
-use aptos_types::{account_address::AccountAddress, transaction::Transaction};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{Transaction, Version},
+};
 use async_trait::async_trait;
 use futures::{channel::mpsc, stream::FusedStream, SinkExt, Stream};
 use serde::{Deserialize, Serialize};
@@ -15,6 +18,9 @@ use std::{
 };
 use thiserror::Error;
 
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod test_utils;
+
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
     #[error("Commit notification failed: {0}")]
@@ -30,10 +36,13 @@ pub enum Error {
 #[async_trait]
 pub trait MempoolNotificationSender: Send + Clone + Sync + 'static {
     /// Notify mempool of the newly committed transactions at the specified block timestamp.
+    /// If known, `committed_version` is the version of the last committed transaction, which
+    /// allows mempool to detect duplicate notifications (e.g., if a chunk is retried).
     async fn notify_new_commit(
         &self,
         committed_transactions: Vec<Transaction>,
         block_timestamp_usecs: u64,
+        committed_version: Option<Version>,
     ) -> Result<(), Error>;
 }
 
@@ -73,24 +82,17 @@ impl MempoolNotificationSender for MempoolNotifier {
         &self,
         transactions: Vec<Transaction>,
         block_timestamp_usecs: u64,
+        committed_version: Option<Version>,
     ) -> Result<(), Error> {
         // Get only user transactions from committed transactions
-        let user_transactions: Vec<CommittedTransaction> = transactions
-            .iter()
-            .filter_map(|transaction| match transaction {
-                Transaction::UserTransaction(signed_txn) => Some(CommittedTransaction {
-                    sender: signed_txn.sender(),
-                    sequence_number: signed_txn.sequence_number(),
-                }),
-                _ => None,
-            })
-            .collect();
+        let user_transactions = extract_committed_transactions(&transactions);
 
         // Mempool needs to be notified about all transactions (user and non-user transactions).
         // See https://github.com/aptos-labs/aptos-core/issues/1882 for more details.
         let commit_notification = MempoolCommitNotification {
             transactions: user_transactions,
             block_timestamp_usecs,
+            committed_version,
         };
 
         // Send the notification to mempool
@@ -110,18 +112,77 @@ impl MempoolNotificationSender for MempoolNotifier {
     }
 }
 
+/// Returns the committed user transactions (sender and sequence number) from the given transactions
+fn extract_committed_transactions(transactions: &[Transaction]) -> Vec<CommittedTransaction> {
+    transactions
+        .iter()
+        .filter_map(|transaction| match transaction {
+            Transaction::UserTransaction(signed_txn) => Some(CommittedTransaction {
+                sender: signed_txn.sender(),
+                sequence_number: signed_txn.sequence_number(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
 /// The mempool component responsible for responding to state sync notifications.
 #[derive(Debug)]
 pub struct MempoolNotificationListener {
     notification_receiver: mpsc::Receiver<MempoolCommitNotification>,
+    highest_committed_version: Option<Version>, // The highest version recorded so far
 }
 
 impl MempoolNotificationListener {
     fn new(notification_receiver: mpsc::Receiver<MempoolCommitNotification>) -> Self {
         MempoolNotificationListener {
             notification_receiver,
+            highest_committed_version: None,
         }
     }
+
+    /// Returns the highest committed version recorded by the listener (if any)
+    pub fn highest_committed_version(&self) -> Option<Version> {
+        self.highest_committed_version
+    }
+
+    /// Records the committed version of the given notification. Returns false if
+    /// the notification is a duplicate, i.e., mempool has already been notified of
+    /// a commit at (or beyond) the same version. Notifications without a version
+    /// are never considered duplicates.
+    pub fn record_commit(&mut self, notification: &MempoolCommitNotification) -> bool {
+        let committed_version = match notification.committed_version {
+            Some(committed_version) => committed_version,
+            None => return true,
+        };
+        if let Some(highest_committed_version) = self.highest_committed_version {
+            if committed_version <= highest_committed_version {
+                return false;
+            }
+        }
+        self.highest_committed_version = Some(committed_version);
+        true
+    }
+
+    /// Drains up to `limit` pending notifications (e.g., on shutdown) and returns
+    /// the number drained. Doesn't wait for new notifications to arrive.
+    ///
+    /// Note: state sync considers a notification delivered once it's in the
+    /// channel, so draining acks the notifications by freeing up capacity for
+    /// any senders blocked on a full channel.
+    pub async fn drain_and_ack(&mut self, limit: usize) -> usize {
+        let mut num_drained = 0;
+        while num_drained < limit {
+            match self.notification_receiver.try_next() {
+                Ok(Some(notification)) => {
+                    self.record_commit(&notification);
+                    num_drained += 1;
+                },
+                Ok(None) | Err(_) => break, // The channel is closed or empty
+            }
+        }
+        num_drained
+    }
 }
 
 impl Stream for MempoolNotificationListener {
@@ -143,14 +204,15 @@ impl FusedStream for MempoolNotificationListener {
 pub struct MempoolCommitNotification {
     pub transactions: Vec<CommittedTransaction>,
     pub block_timestamp_usecs: u64, // The timestamp of the committed block.
+    pub committed_version: Option<Version>, // The version of the last committed transaction.
 }
 
 impl fmt::Display for MempoolCommitNotification {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "MempoolCommitNotification [block_timestamp_usecs: {}, txns: {:?}]",
-            self.block_timestamp_usecs, self.transactions
+            "MempoolCommitNotification [block_timestamp_usecs: {}, committed_version: {:?}, txns: {:?}]",
+            self.block_timestamp_usecs, self.committed_version, self.transactions
         )
     }
 }
@@ -170,7 +232,10 @@ impl fmt::Display for CommittedTransaction {
 
 #[cfg(test)]
 mod tests {
-    use crate::{CommittedTransaction, Error, MempoolNotificationSender};
+    use crate::{
+        test_utils::{CountingMempoolNotifier, NoOpMempoolNotifier, RecordedCommitNotification},
+        CommittedTransaction, Error, MempoolNotificationSender,
+    };
     use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, SigningKey, Uniform};
     use aptos_types::{
         account_address::AccountAddress,
@@ -195,14 +260,14 @@ mod tests {
 
         // Send a notification and expect no failures
         let notify_result = mempool_notifier
-            .notify_new_commit(vec![create_user_transaction()], 0)
+            .notify_new_commit(vec![create_user_transaction()], 0, None)
             .await;
         assert_ok!(notify_result);
 
         // Drop the receiver and try again (this time we expect a failure)
         mempool_listener.notification_receiver.close();
         let notify_result = mempool_notifier
-            .notify_new_commit(vec![create_user_transaction()], 0)
+            .notify_new_commit(vec![create_user_transaction()], 0, None)
             .await;
         assert_matches!(notify_result, Err(Error::CommitNotificationError(_)));
     }
@@ -214,14 +279,14 @@ mod tests {
 
         // Send a notification and expect no failures
         let notify_result = mempool_notifier
-            .notify_new_commit(vec![create_user_transaction()], 0)
+            .notify_new_commit(vec![create_user_transaction()], 0, None)
             .await;
         assert_ok!(notify_result);
 
         // Send another notification (which should block!)
         let result = timeout(
             Duration::from_secs(5),
-            mempool_notifier.notify_new_commit(vec![create_user_transaction()], 0),
+            mempool_notifier.notify_new_commit(vec![create_user_transaction()], 0, None),
         )
         .await;
 
@@ -248,13 +313,15 @@ mod tests {
 
         // Send a notification and verify no failures
         let notify_result = mempool_notifier
-            .notify_new_commit(transactions.clone(), 0)
+            .notify_new_commit(transactions.clone(), 0, None)
             .await;
         assert_ok!(notify_result);
 
         // Send another notification with a single user transaction now included
         transactions.push(create_user_transaction());
-        let notify_result = mempool_notifier.notify_new_commit(transactions, 0).await;
+        let notify_result = mempool_notifier
+            .notify_new_commit(transactions, 0, None)
+            .await;
         assert_ok!(notify_result);
     }
 
@@ -269,7 +336,7 @@ mod tests {
         let transactions = vec![user_transaction.clone()];
         let block_timestamp_usecs = 101;
         let _ = mempool_notifier
-            .notify_new_commit(transactions, block_timestamp_usecs)
+            .notify_new_commit(transactions, block_timestamp_usecs, Some(10))
             .await;
 
         // Verify the notification arrives at the receiver
@@ -286,6 +353,7 @@ mod tests {
                         mempool_commit_notification.block_timestamp_usecs,
                         block_timestamp_usecs
                     );
+                    assert_eq!(mempool_commit_notification.committed_version, Some(10));
                 },
                 result => panic!("Expected user transaction but got: {:?}", result),
             },
@@ -293,6 +361,161 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn test_duplicate_commit_notifications() {
+        // Create runtime and mempool notifier
+        let (mempool_notifier, mut mempool_listener) =
+            crate::new_mempool_notifier_listener_pair(100);
+        assert_eq!(mempool_listener.highest_committed_version(), None);
+
+        // Send the same notification twice (e.g., because a chunk was retried)
+        for _ in 0..2 {
+            let notify_result = mempool_notifier
+                .notify_new_commit(vec![create_user_transaction()], 101, Some(10))
+                .await;
+            assert_ok!(notify_result);
+        }
+
+        // Verify the first notification is recorded and the second is a duplicate
+        let notification = mempool_listener.select_next_some().await;
+        assert_eq!(notification.committed_version, Some(10));
+        assert!(mempool_listener.record_commit(&notification));
+        assert_eq!(mempool_listener.highest_committed_version(), Some(10));
+
+        let notification = mempool_listener.select_next_some().await;
+        assert_eq!(notification.committed_version, Some(10));
+        assert!(!mempool_listener.record_commit(&notification));
+        assert_eq!(mempool_listener.highest_committed_version(), Some(10));
+
+        // Send notifications at an older version, without a version and at a newer version
+        for committed_version in [Some(9), None, Some(11)] {
+            let notify_result = mempool_notifier
+                .notify_new_commit(vec![create_user_transaction()], 102, committed_version)
+                .await;
+            assert_ok!(notify_result);
+        }
+
+        // Verify only the older version is a duplicate
+        let notification = mempool_listener.select_next_some().await;
+        assert!(!mempool_listener.record_commit(&notification));
+        let notification = mempool_listener.select_next_some().await;
+        assert!(mempool_listener.record_commit(&notification));
+        assert_eq!(mempool_listener.highest_committed_version(), Some(10));
+        let notification = mempool_listener.select_next_some().await;
+        assert!(mempool_listener.record_commit(&notification));
+        assert_eq!(mempool_listener.highest_committed_version(), Some(11));
+    }
+
+    #[tokio::test]
+    async fn test_drain_and_ack() {
+        // Create runtime and mempool notifier
+        let (mempool_notifier, mut mempool_listener) =
+            crate::new_mempool_notifier_listener_pair(100);
+
+        // Nothing to drain yet
+        assert_eq!(mempool_listener.drain_and_ack(10).await, 0);
+
+        // Enqueue several notifications
+        let num_notifications = 5;
+        for committed_version in 0..num_notifications {
+            let notify_result = mempool_notifier
+                .notify_new_commit(
+                    vec![create_user_transaction()],
+                    committed_version,
+                    Some(committed_version),
+                )
+                .await;
+            assert_ok!(notify_result);
+        }
+
+        // Drain with a limit and verify the remaining notifications are still pending
+        assert_eq!(mempool_listener.drain_and_ack(2).await, 2);
+        assert_eq!(mempool_listener.highest_committed_version(), Some(1));
+
+        // Drain everything else and verify the listener is now empty
+        assert_eq!(mempool_listener.drain_and_ack(100).await, 3);
+        assert_eq!(mempool_listener.highest_committed_version(), Some(4));
+        assert_eq!(mempool_listener.drain_and_ack(100).await, 0);
+        assert!(mempool_listener.select_next_some().now_or_never().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_op_notifier() {
+        // Send several notifications and verify no failures
+        let mempool_notifier = NoOpMempoolNotifier;
+        for block_timestamp_usecs in 0..5 {
+            let notify_result = mempool_notifier
+                .notify_new_commit(
+                    vec![create_user_transaction(), create_genesis_transaction()],
+                    block_timestamp_usecs,
+                    None,
+                )
+                .await;
+            assert_ok!(notify_result);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_counting_notifier() {
+        // Create the counting notifier and keep a clone for assertions
+        let counting_notifier = CountingMempoolNotifier::new();
+        let mempool_notifier = counting_notifier.clone();
+        assert_eq!(counting_notifier.num_notifications(), 0);
+
+        // Send a notification with only non-user transactions
+        let notify_result = mempool_notifier
+            .notify_new_commit(
+                vec![
+                    create_block_metadata_transaction(),
+                    create_genesis_transaction(),
+                ],
+                100,
+                None,
+            )
+            .await;
+        assert_ok!(notify_result);
+
+        // Send a notification with a mix of user and non-user transactions
+        let user_transaction = create_user_transaction();
+        let notify_result = mempool_notifier
+            .notify_new_commit(
+                vec![
+                    create_block_metadata_transaction(),
+                    user_transaction.clone(),
+                ],
+                200,
+                Some(5),
+            )
+            .await;
+        assert_ok!(notify_result);
+
+        // Verify both notifications were recorded (in order)
+        let committed_transaction = match user_transaction {
+            Transaction::UserTransaction(signed_transaction) => CommittedTransaction {
+                sender: signed_transaction.sender(),
+                sequence_number: signed_transaction.sequence_number(),
+            },
+            result => panic!("Expected user transaction but got: {:?}", result),
+        };
+        assert_eq!(counting_notifier.num_notifications(), 2);
+        assert_eq!(counting_notifier.notifications(), vec![
+            RecordedCommitNotification {
+                transactions: vec![],
+                block_timestamp_usecs: 100,
+                committed_version: None,
+            },
+            RecordedCommitNotification {
+                transactions: vec![committed_transaction.clone()],
+                block_timestamp_usecs: 200,
+                committed_version: Some(5),
+            },
+        ]);
+        assert_eq!(counting_notifier.committed_transactions(), vec![
+            committed_transaction
+        ]);
+        assert_eq!(counting_notifier.block_timestamps_usecs(), vec![100, 200]);
+    }
+
     fn create_user_transaction() -> Transaction {
         let private_key = Ed25519PrivateKey::generate_for_testing();
         let public_key = private_key.public_key();
@@ -0,0 +1,95 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    extract_committed_transactions, CommittedTransaction, Error, MempoolNotificationSender,
+};
+use aptos_infallible::Mutex;
+use aptos_types::transaction::{Transaction, Version};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A mempool notifier that drops all notifications and always succeeds.
+/// Useful for components that require a `MempoolNotificationSender`
+/// but don't care about the notifications themselves.
+#[derive(Clone, Debug, Default)]
+pub struct NoOpMempoolNotifier;
+
+#[async_trait]
+impl MempoolNotificationSender for NoOpMempoolNotifier {
+    async fn notify_new_commit(
+        &self,
+        _committed_transactions: Vec<Transaction>,
+        _block_timestamp_usecs: u64,
+        _committed_version: Option<Version>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// A single commit notification recorded by the `CountingMempoolNotifier`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordedCommitNotification {
+    pub transactions: Vec<CommittedTransaction>,
+    pub block_timestamp_usecs: u64,
+    pub committed_version: Option<Version>,
+}
+
+/// A mempool notifier that records every notification it receives (and always
+/// succeeds). Clones share the same records, so tests can keep a clone around
+/// to assert on the notifications sent by the component under test.
+#[derive(Clone, Debug, Default)]
+pub struct CountingMempoolNotifier {
+    notifications: Arc<Mutex<Vec<RecordedCommitNotification>>>,
+}
+
+impl CountingMempoolNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of notifications received so far
+    pub fn num_notifications(&self) -> usize {
+        self.notifications.lock().len()
+    }
+
+    /// Returns a copy of all notifications received so far (in order)
+    pub fn notifications(&self) -> Vec<RecordedCommitNotification> {
+        self.notifications.lock().clone()
+    }
+
+    /// Returns all committed user transactions received so far (in order)
+    pub fn committed_transactions(&self) -> Vec<CommittedTransaction> {
+        self.notifications
+            .lock()
+            .iter()
+            .flat_map(|notification| notification.transactions.clone())
+            .collect()
+    }
+
+    /// Returns the block timestamps of all notifications received so far (in order)
+    pub fn block_timestamps_usecs(&self) -> Vec<u64> {
+        self.notifications
+            .lock()
+            .iter()
+            .map(|notification| notification.block_timestamp_usecs)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl MempoolNotificationSender for CountingMempoolNotifier {
+    async fn notify_new_commit(
+        &self,
+        committed_transactions: Vec<Transaction>,
+        block_timestamp_usecs: u64,
+        committed_version: Option<Version>,
+    ) -> Result<(), Error> {
+        self.notifications.lock().push(RecordedCommitNotification {
+            transactions: extract_committed_transactions(&committed_transactions),
+            block_timestamp_usecs,
+            committed_version,
+        });
+        Ok(())
+    }
+}
@@ -310,6 +310,8 @@ impl<
         let committed_transactions = CommittedTransactions {
             events: consensus_commit_notification.subscribable_events.clone(),
             transactions: consensus_commit_notification.transactions.clone(),
+            // Consensus notifies each commit exactly once, so there are no duplicates to detect
+            committed_version: None,
         };
         utils::handle_committed_transactions(
             committed_transactions,
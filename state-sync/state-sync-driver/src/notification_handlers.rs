@@ -48,6 +48,7 @@ pub struct CommittedStateSnapshot {
 pub struct CommittedTransactions {
     pub events: Vec<ContractEvent>,
     pub transactions: Vec<Transaction>,
+    pub committed_version: Option<Version>, // The version of the last transaction (if known)
 }
 
 impl CommitNotification {
@@ -60,6 +61,7 @@ impl CommitNotification {
         let committed_transaction = CommittedTransactions {
             events,
             transactions,
+            committed_version: Some(version),
         };
         let committed_states = CommittedStateSnapshot {
             committed_transaction,
@@ -77,6 +79,7 @@ impl CommitNotification {
     >(
         events: Vec<ContractEvent>,
         transactions: Vec<Transaction>,
+        committed_version: Option<Version>,
         latest_synced_version: Version,
         latest_synced_ledger_info: LedgerInfoWithSignatures,
         mut mempool_notification_handler: MempoolNotificationHandler<M>,
@@ -102,6 +105,7 @@ impl CommitNotification {
             .notify_mempool_of_committed_transactions(
                 transactions.clone(),
                 blockchain_timestamp_usecs,
+                committed_version,
             )
             .await?;
 
@@ -406,15 +410,21 @@ impl<M: MempoolNotificationSender> MempoolNotificationHandler<M> {
         }
     }
 
-    /// Notifies mempool that transactions have been committed.
+    /// Notifies mempool that transactions have been committed. If known,
+    /// `committed_version` is the version of the last committed transaction.
     pub async fn notify_mempool_of_committed_transactions(
         &mut self,
         committed_transactions: Vec<Transaction>,
         block_timestamp_usecs: u64,
+        committed_version: Option<Version>,
     ) -> Result<(), Error> {
         let result = self
             .mempool_notification_sender
-            .notify_new_commit(committed_transactions, block_timestamp_usecs)
+            .notify_new_commit(
+                committed_transactions,
+                block_timestamp_usecs,
+                committed_version,
+            )
             .await;
 
         if let Err(error) = result {
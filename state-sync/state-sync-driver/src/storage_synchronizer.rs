@@ -728,6 +728,7 @@ fn spawn_commit_post_processor<
             let committed_transactions = CommittedTransactions {
                 events: notification.subscribable_events,
                 transactions: notification.committed_transactions,
+                committed_version: notification.committed_version,
             };
             utils::handle_committed_transactions(
                 committed_transactions,
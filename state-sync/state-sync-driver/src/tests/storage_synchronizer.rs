@@ -61,6 +61,7 @@ async fn test_apply_outputs() {
     let expected_commit_return = Ok(ChunkCommitNotification {
         subscribable_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
+        committed_version: Some(0),
         reconfiguration_occurred: false,
     });
     chunk_executor
@@ -297,6 +298,7 @@ async fn test_apply_outputs_commit_send_error() {
     let expected_commit_return = Ok(ChunkCommitNotification {
         subscribable_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
+        committed_version: Some(0),
         reconfiguration_occurred: false,
     });
     chunk_executor
@@ -348,6 +350,7 @@ async fn test_execute_transactions() {
     let expected_commit_return = Ok(ChunkCommitNotification {
         subscribable_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
+        committed_version: Some(0),
         reconfiguration_occurred: false,
     });
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
@@ -584,6 +587,7 @@ async fn test_execute_transactions_commit_send_error() {
     let expected_commit_return = Ok(ChunkCommitNotification {
         subscribable_events: vec![event_to_commit.clone()],
         committed_transactions: vec![transaction_to_commit.clone()],
+        committed_version: Some(0),
         reconfiguration_occurred: false,
     });
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
@@ -683,6 +687,7 @@ async fn test_initialize_state_synchronizer_receiver_error() {
 async fn test_save_states_completion() {
     // Create test data
     let target_ledger_info = create_epoch_ending_ledger_info();
+    let target_version = target_ledger_info.ledger_info().version();
     let epoch_change_proofs = [
         create_epoch_ending_ledger_info(),
         create_epoch_ending_ledger_info(),
@@ -759,6 +764,7 @@ async fn test_save_states_completion() {
     let expected_committed_transactions = CommittedTransactions {
         events: vec![expected_event.clone()],
         transactions: vec![expected_transaction.clone()],
+        committed_version: Some(target_version),
     };
     verify_snapshot_commit_notification(
         &mut commit_listener,
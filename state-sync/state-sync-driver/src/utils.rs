@@ -343,6 +343,7 @@ pub async fn handle_committed_transactions<
     if let Err(error) = CommitNotification::handle_transaction_notification(
         committed_transactions.events,
         committed_transactions.transactions,
+        committed_transactions.committed_version,
         latest_synced_version,
         latest_synced_ledger_info,
         mempool_notification_handler,
@@ -135,7 +135,7 @@ fn test_error_if_version_pruned() {
         db.error_if_ledger_pruned("Transaction", 9)
             .unwrap_err()
             .to_string(),
-        "AptosDB Other Error: Transaction at version 9 is pruned, min available version is 10."
+        "Transaction at version 9 is pruned, min available version is 10."
     );
     assert!(db.error_if_ledger_pruned("Transaction", 10).is_ok());
 }
@@ -187,14 +187,7 @@ impl AptosDB {
 
     fn error_if_ledger_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.ledger_pruner.get_min_readable_version();
-        ensure!(
-            version >= min_readable_version,
-            "{} at version {} is pruned, min available version is {}.",
-            data_type,
-            version,
-            min_readable_version
-        );
-        Ok(())
+        error_if_pruned(data_type, version, min_readable_version)
     }
 
     fn error_if_state_merkle_pruned(&self, data_type: &str, version: Version) -> Result<()> {
@@ -215,26 +208,15 @@ impl AptosDB {
         if version >= min_readable_epoch_snapshot_version {
             self.ledger_store.ensure_epoch_ending(version)
         } else {
-            bail!(
-                "{} at version {} is pruned. snapshots are available at >= {}, epoch snapshots are available at >= {}",
-                data_type,
-                version,
-                min_readable_version,
-                min_readable_epoch_snapshot_version,
-            )
+            // Only epoch ending versions are readable below `min_readable_version`, so that's
+            // what is reported as the oldest version.
+            error_if_pruned(data_type, version, min_readable_version)
         }
     }
 
     fn error_if_state_kv_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.state_store.state_kv_pruner.get_min_readable_version();
-        ensure!(
-            version >= min_readable_version,
-            "{} at version {} is pruned, min available version is {}.",
-            data_type,
-            version,
-            min_readable_version
-        );
-        Ok(())
+        error_if_pruned(data_type, version, min_readable_version)
     }
 }
 
@@ -252,6 +234,18 @@ fn error_if_too_many_requested(num_requested: u64, max_allowed: u64) -> Result<(
     }
 }
 
+fn error_if_pruned(data_type: &str, version: Version, min_readable_version: Version) -> Result<()> {
+    if version < min_readable_version {
+        Err(AptosDbError::Pruned {
+            data_type: data_type.to_string(),
+            requested_version: version,
+            min_readable_version,
+        })
+    } else {
+        Ok(())
+    }
+}
+
 fn gauged_api<T, F>(api_name: &'static str, api_impl: F) -> Result<T>
 where
     F: FnOnce() -> Result<T>,
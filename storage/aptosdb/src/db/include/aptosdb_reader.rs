@@ -266,6 +266,47 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_rev_iterator(
+        &self,
+        event_key: &EventKey,
+        start: Option<u64>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<EventWithVersion>> + '_>> {
+        gauged_api("get_events_rev_iterator", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+
+            let latest_seq_num = self
+                .event_store
+                .get_latest_sequence_number(ledger_version, event_key)?;
+            let start_seq_num = match (start, latest_seq_num) {
+                (_, None) => None,
+                (None, Some(latest_seq_num)) => Some(latest_seq_num),
+                // Consistent with `get_events`, asking for something beyond the latest sequence
+                // number yields nothing.
+                (Some(start), Some(latest_seq_num)) => (start <= latest_seq_num).then_some(start),
+            };
+
+            let iter = start_seq_num
+                .map(|start_seq_num| {
+                    self.event_store.rev_lookup_events_by_key_iter(
+                        event_key,
+                        start_seq_num,
+                        limit,
+                        ledger_version,
+                    )
+                })
+                .transpose()?
+                .into_iter()
+                .flatten()
+                .map(move |res| {
+                    let (seq, ver, idx) = res?;
+                    self.get_event_with_version_by_index(seq, ver, idx)
+                });
+            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<EventWithVersion>> + '_>)
+        })
+    }
+
     fn get_transaction_iterator(
         &self,
         start_version: Version,
@@ -981,20 +1022,7 @@ impl AptosDB {
 
         let mut events_with_version = event_indices
             .into_iter()
-            .map(|(seq, ver, idx)| {
-                let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
-                let v0 = match &event {
-                    ContractEvent::V1(event) => event,
-                    ContractEvent::V2(_) => bail!("Unexpected module event"),
-                };
-                ensure!(
-                    seq == v0.sequence_number(),
-                    "Index broken, expected seq:{}, actual:{}",
-                    seq,
-                    v0.sequence_number()
-                );
-                Ok(EventWithVersion::new(ver, event))
-            })
+            .map(|(seq, ver, idx)| self.get_event_with_version_by_index(seq, ver, idx))
             .collect::<Result<Vec<_>>>()?;
         if order == Order::Descending {
             events_with_version.reverse();
@@ -1003,6 +1031,28 @@ impl AptosDB {
         Ok(events_with_version)
     }
 
+    /// Reads the event an event key index entry points at, making sure it has the sequence
+    /// number the index says it has.
+    fn get_event_with_version_by_index(
+        &self,
+        seq: u64,
+        ver: Version,
+        idx: u64,
+    ) -> Result<EventWithVersion> {
+        let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+        let v0 = match &event {
+            ContractEvent::V1(event) => event,
+            ContractEvent::V2(_) => bail!("Unexpected module event"),
+        };
+        ensure!(
+            seq == v0.sequence_number(),
+            "Index broken, expected seq:{}, actual:{}",
+            seq,
+            v0.sequence_number()
+        );
+        Ok(EventWithVersion::new(ver, event))
+    }
+
     fn get_block_info_internal(&self, block_height: u64) -> Result<Option<BlockInfo>> {
         self.ledger_db
             .metadata_db()
@@ -15,7 +15,7 @@ use crate::{
     utils::truncation_helper::{
         find_closest_node_version_at_or_before, get_current_version_in_state_merkle_db,
         get_ledger_commit_progress, get_overall_commit_progress, get_state_kv_commit_progress,
-        truncate_state_merkle_db,
+        plan_truncation, truncate_state_merkle_db,
     },
 };
 use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
@@ -31,7 +31,7 @@ use std::{fs, path::PathBuf, sync::Arc};
 #[clap(about = "Delete all data after the provided version.")]
 #[clap(group(clap::ArgGroup::new("backup")
         .required(true)
-        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint"]),
+        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint", "dry_run"]),
 ))]
 pub struct Cmd {
     // TODO(grao): Support db_path_overrides here.
@@ -50,13 +50,19 @@ pub struct Cmd {
     #[clap(long, group = "backup")]
     opt_out_backup_checkpoint: bool,
 
+    /// Print what would be deleted from the ledger db and exit, without changing anything.
+    #[clap(long, group = "backup")]
+    dry_run: bool,
+
     #[clap(flatten)]
     sharding_config: ShardingConfig,
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
-        if !self.opt_out_backup_checkpoint {
+        if self.dry_run {
+            println!("Dry run, nothing will be deleted.");
+        } else if !self.opt_out_backup_checkpoint {
             let backup_checkpoint_dir = self.backup_checkpoint_dir.unwrap();
             ensure!(
                 !backup_checkpoint_dir.exists(),
@@ -81,7 +87,7 @@ impl Cmd {
         let (ledger_db, state_merkle_db, state_kv_db) = AptosDB::open_dbs(
             &StorageDirPaths::from_path(&self.db_dir),
             rocksdb_config,
-            /*readonly=*/ false,
+            /*readonly=*/ self.dry_run,
             /*max_num_nodes_per_lru_cache_shard=*/ 0,
         )?;
 
@@ -135,6 +141,18 @@ impl Cmd {
             };
         }
 
+        if self.dry_run {
+            let plan = plan_truncation(&ledger_db, target_version)?;
+            println!(
+                "Truncating the ledger db to version {} would delete:",
+                plan.target_version
+            );
+            for (cf_name, cf_plan) in &plan.column_families {
+                println!("{}: {} keys", cf_name, cf_plan.num_keys);
+            }
+            return Ok(());
+        }
+
         // TODO(grao): We are using a brute force implementation for now. We might be able to make
         // it faster, since our data is append only.
         if target_version < state_merkle_db_version {
@@ -286,12 +304,28 @@ mod test {
 
             let mut target_version = db_version - 70;
 
+            // A dry run leaves the db as it is
+            let cmd = Cmd {
+                db_dir: tmp_dir.path().to_path_buf(),
+                target_version,
+                ledger_db_batch_size: 15,
+                opt_out_backup_checkpoint: false,
+                backup_checkpoint_dir: None,
+                dry_run: true,
+                sharding_config: sharding_config.clone(),
+            };
+            cmd.run().unwrap();
+            let db = if input.1 { AptosDB::new_for_test_with_sharding(&tmp_dir, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD) } else { AptosDB::new_for_test(&tmp_dir) };
+            prop_assert_eq!(db.get_latest_version().unwrap(), db_version);
+            drop(db);
+
             let cmd = Cmd {
                 db_dir: tmp_dir.path().to_path_buf(),
                 target_version,
                 ledger_db_batch_size: 15,
                 opt_out_backup_checkpoint: true,
                 backup_checkpoint_dir: None,
+                dry_run: false,
                 sharding_config: sharding_config.clone(),
             };
 
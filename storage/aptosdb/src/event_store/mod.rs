@@ -12,7 +12,7 @@ use crate::{
         event::EventSchema, event_accumulator::EventAccumulatorSchema,
         event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
     },
-    utils::iterators::EventsByVersionIter,
+    utils::iterators::{EventsByKeyRevIter, EventsByVersionIter},
 };
 use anyhow::anyhow;
 use aptos_accumulator::{HashReader, MerkleAccumulator};
@@ -154,6 +154,32 @@ impl EventStore {
         Ok(result)
     }
 
+    /// Same as `lookup_events_by_key`, but iterates from `start_seq_num` downwards, so the
+    /// latest events of a long stream can be read without going through the older ones.
+    ///
+    /// The caller is expected to make sure `start_seq_num` exists as of `ledger_version`, e.g.
+    /// via `get_latest_sequence_number`.
+    pub fn rev_lookup_events_by_key_iter(
+        &self,
+        event_key: &EventKey,
+        start_seq_num: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<EventsByKeyRevIter> {
+        let mut iter = self
+            .event_db
+            .rev_iter::<EventByKeySchema>(ReadOptions::default())?;
+        iter.seek_for_prev(&(*event_key, start_seq_num))?;
+
+        Ok(EventsByKeyRevIter::new(
+            iter,
+            *event_key,
+            start_seq_num,
+            limit,
+            ledger_version,
+        ))
+    }
+
     fn lookup_event_by_key(
         &self,
         event_key: &EventKey,
@@ -72,6 +72,86 @@ fn traverse_events_by_key(
         .collect()
 }
 
+#[test]
+fn test_rev_lookup_events_by_key() {
+    const NUM_EVENTS: u64 = 5000;
+
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.event_store;
+    let event_db = &db.ledger_db.event_db();
+
+    // Interleave events of another key, so iterating past the start of the stream of
+    // `event_key` runs into them.
+    let address = AccountAddress::random();
+    let event_key = EventKey::new(1, address);
+    let other_event_key = EventKey::new(0, address);
+    let batch = SchemaBatch::new();
+    for seq_num in 0..NUM_EVENTS {
+        let events = [other_event_key, event_key].map(|key| {
+            ContractEvent::new_v1(key, seq_num, TypeTag::U64, bcs::to_bytes(&seq_num).unwrap())
+        });
+        event_db
+            .put_events(seq_num, &events, /*skip_index=*/ false, &batch)
+            .unwrap();
+    }
+    event_db.write_schemas(batch).unwrap();
+
+    let ledger_version = NUM_EVENTS - 1;
+    let rev_lookup = |start_seq_num, limit| {
+        store
+            .rev_lookup_events_by_key_iter(&event_key, start_seq_num, limit, ledger_version)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+    };
+    let expected = |seq_nums: &mut dyn Iterator<Item = u64>| -> Vec<_> {
+        seq_nums.map(|seq_num| (seq_num, seq_num, 1)).collect()
+    };
+
+    // From the latest event all the way down.
+    assert_eq!(
+        rev_lookup(NUM_EVENTS - 1, NUM_EVENTS).unwrap(),
+        expected(&mut (0..NUM_EVENTS).rev())
+    );
+    assert_eq!(
+        rev_lookup(NUM_EVENTS - 1, 3).unwrap(),
+        expected(&mut (NUM_EVENTS - 3..NUM_EVENTS).rev())
+    );
+    assert_eq!(
+        rev_lookup(100, 10).unwrap(),
+        expected(&mut (91..=100).rev())
+    );
+    assert!(rev_lookup(100, 0).unwrap().is_empty());
+
+    // Stops at sequence number 0, even if the limit allows for more.
+    assert_eq!(rev_lookup(0, 10).unwrap(), expected(&mut 0..=0));
+    assert_eq!(rev_lookup(2, 10).unwrap(), expected(&mut (0..=2).rev()));
+
+    // Events can't be beyond the ledger version.
+    assert!(store
+        .rev_lookup_events_by_key_iter(&event_key, NUM_EVENTS - 1, 1, ledger_version - 1)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .is_err());
+
+    // Once the first 1000 versions are pruned, reaching them is an error.
+    let batch = SchemaBatch::new();
+    event_db.prune_events(0, 1000, &batch).unwrap();
+    event_db.write_schemas(batch).unwrap();
+    assert_eq!(
+        rev_lookup(1009, 10).unwrap(),
+        expected(&mut (1000..=1009).rev())
+    );
+    assert!(rev_lookup(1009, 11).is_err());
+    assert!(rev_lookup(999, 1).is_err());
+    let mut iter = store
+        .rev_lookup_events_by_key_iter(&event_key, 1001, 10, ledger_version)
+        .unwrap();
+    assert_eq!(iter.next().unwrap().unwrap(), (1001, 1001, 1));
+    assert_eq!(iter.next().unwrap().unwrap(), (1000, 1000, 1));
+    assert!(iter.next().unwrap().is_err());
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -584,6 +584,17 @@ impl DbReader for FakeAptosDB {
             .get_events(event_key, start, order, limit, ledger_version)
     }
 
+    fn get_events_rev_iterator(
+        &self,
+        event_key: &aptos_types::event::EventKey,
+        start: Option<u64>,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<EventWithVersion>> + '_>> {
+        self.inner
+            .get_events_rev_iterator(event_key, start, limit, ledger_version)
+    }
+
     fn get_block_timestamp(&self, version: Version) -> Result<u64> {
         gauged_api("get_block_timestamp", || {
             ensure!(version <= self.get_latest_version()?);
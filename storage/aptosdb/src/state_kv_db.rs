@@ -8,10 +8,12 @@ use crate::{
     db_options::{gen_state_kv_cfds, state_kv_db_column_families},
     metrics::OTHER_TIMERS_SECONDS,
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
-    utils::truncation_helper::{get_state_kv_commit_progress, truncate_state_kv_db_shards},
+    utils::{
+        commit_sharded_batches,
+        truncation_helper::{get_state_kv_commit_progress, truncate_state_kv_db_shards},
+    },
 };
 use aptos_config::config::{RocksdbConfig, RocksdbConfigs, StorageDirPaths};
-use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_logger::prelude::info;
 use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{SchemaBatch, DB};
@@ -104,22 +106,18 @@ impl StateKvDb {
         let _timer = OTHER_TIMERS_SECONDS
             .with_label_values(&["state_kv_db__commit"])
             .start_timer();
-        THREAD_MANAGER.get_io_pool().scope(|s| {
+        {
             let _timer = OTHER_TIMERS_SECONDS
                 .with_label_values(&["state_kv_db__commit_shards"])
                 .start_timer();
-            let mut batches = sharded_state_kv_batches.into_iter();
-            for shard_id in 0..NUM_STATE_SHARDS {
-                let state_kv_batch = batches
-                    .next()
-                    .expect("Not sufficient number of sharded state kv batches");
-                s.spawn(move |_| {
-                    // TODO(grao): Consider propagating the error instead of panic, if necessary.
-                    self.commit_single_shard(version, shard_id as u8, state_kv_batch)
-                        .unwrap_or_else(|err| panic!("Failed to commit shard {shard_id}: {err}."));
-                });
+            for (shard_id, batch) in sharded_state_kv_batches.iter().enumerate() {
+                batch.put::<DbMetadataSchema>(
+                    &DbMetadataKey::StateKvShardCommitProgress(shard_id),
+                    &DbMetadataValue::Version(version),
+                )?;
             }
-        });
+            commit_sharded_batches(&self.state_kv_db_shards, sharded_state_kv_batches)?;
+        }
 
         {
             let _timer = OTHER_TIMERS_SECONDS
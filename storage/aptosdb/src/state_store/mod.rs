@@ -400,7 +400,7 @@ impl StateStore {
             if crash_if_difference_is_too_large {
                 assert_le!(difference, MAX_COMMIT_PROGRESS_DIFFERENCE);
             }
-            truncate_ledger_db(ledger_db, overall_commit_progress)
+            truncate_ledger_db(ledger_db, overall_commit_progress, None)
                 .expect("Failed to truncate ledger db.");
 
             if state_kv_commit_progress != overall_commit_progress {
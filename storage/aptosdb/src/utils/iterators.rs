@@ -3,17 +3,18 @@
 
 use crate::{
     schema::{
-        event::EventSchema, ledger_info::LedgerInfoSchema, state_value::StateValueSchema,
-        state_value_index::StateValueIndexSchema,
+        event::EventSchema, event_by_key::EventByKeySchema, ledger_info::LedgerInfoSchema,
+        state_value::StateValueSchema, state_value_index::StateValueIndexSchema,
         transaction_by_account::TransactionByAccountSchema,
     },
     state_kv_db::StateKvDb,
 };
 use aptos_schemadb::{iterator::SchemaIterator, ReadOptions};
-use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use aptos_storage_interface::{db_ensure as ensure, db_other_bail, AptosDbError, Result};
 use aptos_types::{
     account_address::AccountAddress,
     contract_event::ContractEvent,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
     transaction::Version,
@@ -97,6 +98,62 @@ where
     }
 }
 
+pub struct VersionBoundedIter<I, T> {
+    inner: I,
+    max_version: Version,
+    is_finished: bool,
+    _phantom: PhantomData<T>,
+}
+
+impl<I, T> VersionBoundedIter<I, T>
+where
+    I: Iterator<Item = Result<(Version, T)>>,
+{
+    fn next_impl(&mut self) -> Result<Option<(Version, T)>> {
+        if self.is_finished {
+            return Ok(None);
+        }
+
+        let ret = match self.inner.next().transpose()? {
+            Some((version, item)) if version <= self.max_version => Some((version, item)),
+            _ => {
+                // Either the underlying iterator is exhausted or we went past the bound, in both
+                // cases there's nothing more to yield.
+                self.is_finished = true;
+                None
+            },
+        };
+
+        Ok(ret)
+    }
+}
+
+impl<I, T> Iterator for VersionBoundedIter<I, T>
+where
+    I: Iterator<Item = Result<(Version, T)>>,
+{
+    type Item = Result<(Version, T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_impl().transpose()
+    }
+}
+
+/// Wraps an iterator over `(version, item)` pairs (e.g. a schema iterator keyed by version), and
+/// stops as soon as a version greater than `max_version` is decoded. Errors from the underlying
+/// iterator are passed through.
+pub fn version_bounded<I, T>(iter: I, max_version: Version) -> VersionBoundedIter<I, T>
+where
+    I: Iterator<Item = Result<(Version, T)>>,
+{
+    VersionBoundedIter {
+        inner: iter,
+        max_version,
+        is_finished: false,
+        _phantom: Default::default(),
+    }
+}
+
 pub struct PrefixedStateValueIterator<'a> {
     db: &'a StateKvDb,
     kv_iter: Option<SchemaIterator<'a, StateValueSchema>>,
@@ -434,3 +491,78 @@ impl<'a> Iterator for EventsByVersionIter<'a> {
         self.next_impl().transpose()
     }
 }
+
+/// Iterates the events of an event key from `start_seq_num` down to sequence number 0, yielding
+/// the sequence number, the version of the transaction that emitted it, and its index among the
+/// events of that transaction.
+///
+/// Like the ascending lookup, the sequence numbers must be contiguous and not go beyond
+/// `ledger_version`. Running out of events before reaching sequence number 0 means the rest were
+/// pruned, which is reported as an error.
+pub struct EventsByKeyRevIter<'a> {
+    inner: SchemaIterator<'a, EventByKeySchema>,
+    event_key: EventKey,
+    ledger_version: Version,
+    expected_next_seq_num: Option<u64>,
+    remaining: u64,
+}
+
+impl<'a> EventsByKeyRevIter<'a> {
+    pub(crate) fn new(
+        inner: SchemaIterator<'a, EventByKeySchema>,
+        event_key: EventKey,
+        start_seq_num: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Self {
+        Self {
+            inner,
+            event_key,
+            ledger_version,
+            expected_next_seq_num: Some(start_seq_num),
+            remaining: limit,
+        }
+    }
+
+    fn next_impl(&mut self) -> Result<Option<(u64, Version, u64)>> {
+        let expected_seq_num = match self.expected_next_seq_num {
+            Some(seq_num) if self.remaining > 0 => seq_num,
+            _ => return Ok(None),
+        };
+
+        match self.inner.next().transpose()? {
+            Some(((event_key, seq_num), (version, index))) if event_key == self.event_key => {
+                ensure!(
+                    seq_num == expected_seq_num,
+                    "DB corruption: Sequence number not continuous. expected: {}, actual: {}",
+                    expected_seq_num,
+                    seq_num,
+                );
+                ensure!(
+                    version <= self.ledger_version,
+                    "Event {} of {} is at version {}, beyond ledger version {}.",
+                    seq_num,
+                    self.event_key,
+                    version,
+                    self.ledger_version,
+                );
+                self.expected_next_seq_num = seq_num.checked_sub(1);
+                self.remaining -= 1;
+                Ok(Some((seq_num, version, index)))
+            },
+            _ => db_other_bail!(
+                "Event {} of {} is probably pruned.",
+                expected_seq_num,
+                self.event_key
+            ),
+        }
+    }
+}
+
+impl<'a> Iterator for EventsByKeyRevIter<'a> {
+    type Item = Result<(u64, Version, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_impl().transpose()
+    }
+}
@@ -8,10 +8,14 @@ use crate::{
     common::NUM_STATE_SHARDS,
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema},
 };
+use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
 use aptos_schemadb::{SchemaBatch, DB};
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{db_other_bail, AptosDbError, Result};
 use aptos_types::transaction::Version;
 use arr_macro::arr;
+use std::{collections::HashMap, sync::Arc};
 
 pub(crate) type ShardedStateKvSchemaBatch = [SchemaBatch; NUM_STATE_SHARDS];
 
@@ -21,6 +25,120 @@ pub(crate) fn get_progress(db: &DB, progress_key: &DbMetadataKey) -> Result<Opti
         .map(|v| v.expect_version()))
 }
 
+/// Reads the progress recorded under each of `progress_keys`, returning the key and version pairs
+/// in the same order as the input keys.
+pub fn get_all_progress(
+    db: &DB,
+    progress_keys: &[DbMetadataKey],
+) -> Result<Vec<(DbMetadataKey, Option<Version>)>> {
+    progress_keys
+        .iter()
+        .map(|key| Ok((key.clone(), get_progress(db, key)?)))
+        .collect()
+}
+
+/// Reads the progress of each shard. `progress_key` maps a shard id to the key the shard records
+/// its progress under, e.g. `DbMetadataKey::StateKvShardCommitProgress`.
+pub fn get_sharded_progress(
+    dbs: &[Arc<DB>],
+    progress_key: impl Fn(usize) -> DbMetadataKey,
+) -> Result<Vec<(usize, Option<Version>)>> {
+    dbs.iter()
+        .enumerate()
+        .map(|(shard_id, db)| Ok((shard_id, get_progress(db, &progress_key(shard_id))?)))
+        .collect()
+}
+
+/// Checks that all shards report the same progress. `progress_key` maps a shard id to the key the
+/// shard records its progress under, e.g. `DbMetadataKey::StateKvShardCommitProgress`.
+///
+/// If the shards disagree, returns the `(shard_id, progress)` pairs of all shards that differ from
+/// the most common progress. A shard whose progress can't be read is logged and always reported
+/// as divergent, with `None` progress. Use `get_sharded_progress` to get the storage errors.
+pub fn verify_sharded_progress_consistency(
+    dbs: &[Arc<DB>],
+    progress_key: impl Fn(usize) -> DbMetadataKey,
+) -> std::result::Result<(), Vec<(usize, Option<Version>)>> {
+    let mut unreadable = Vec::new();
+    let mut progress = Vec::new();
+    for (shard_id, db) in dbs.iter().enumerate() {
+        match get_progress(db, &progress_key(shard_id)) {
+            Ok(version) => progress.push((shard_id, version)),
+            Err(err) => {
+                warn!(
+                    shard_id = shard_id,
+                    error = ?err,
+                    "Failed to read shard progress."
+                );
+                unreadable.push((shard_id, None));
+            },
+        }
+    }
+
+    let mut counts: HashMap<Option<Version>, usize> = HashMap::new();
+    for (_, version) in &progress {
+        *counts.entry(*version).or_default() += 1;
+    }
+    if counts.len() <= 1 && unreadable.is_empty() {
+        return Ok(());
+    }
+
+    // Ties are broken in favor of the version reported by the lowest shard id.
+    let expected = progress
+        .iter()
+        .max_by_key(|(shard_id, version)| (counts[version], std::cmp::Reverse(*shard_id)))
+        .map(|(_, version)| *version);
+    let mut divergent: Vec<_> = progress
+        .into_iter()
+        .filter(|(_, version)| Some(*version) != expected)
+        .chain(unreadable)
+        .collect();
+    divergent.sort_by_key(|(shard_id, _)| *shard_id);
+    Err(divergent)
+}
+
 pub(crate) fn new_sharded_kv_schema_batch() -> ShardedStateKvSchemaBatch {
     arr![SchemaBatch::new(); 16]
 }
+
+/// Writes the batch of each shard to the db of that shard, with all shards written in parallel on
+/// the IO pool.
+///
+/// If any of the writes fails, the returned error lists all failed shard ids. Writes to the other
+/// shards are not rolled back, so callers must only record the overall progress once this
+/// succeeds.
+pub(crate) fn commit_sharded_batches(
+    dbs: &[Arc<DB>; NUM_STATE_SHARDS],
+    batches: ShardedStateKvSchemaBatch,
+) -> Result<()> {
+    let errors = Mutex::new(Vec::new());
+    THREAD_MANAGER.get_io_pool().scope(|s| {
+        for (shard_id, (db, batch)) in dbs.iter().zip(batches).enumerate() {
+            let errors = &errors;
+            s.spawn(move |_| {
+                if let Err(err) = db.write_schemas(batch) {
+                    errors.lock().push((shard_id, err));
+                }
+            });
+        }
+    });
+
+    let mut errors = errors.into_inner();
+    if !errors.is_empty() {
+        errors.sort_by_key(|(shard_id, _)| *shard_id);
+        let shard_ids: Vec<_> = errors.iter().map(|(shard_id, _)| *shard_id).collect();
+        let messages: Vec<_> = errors
+            .iter()
+            .map(|(shard_id, err)| format!("shard {}: {}", shard_id, err))
+            .collect();
+        db_other_bail!(
+            "Failed to commit shards {:?}: {}",
+            shard_ids,
+            messages.join("; ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,354 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    iterators::version_bounded,
+    truncation_helper::{plan_truncation, truncate_ledger_db},
+    *,
+};
+use crate::{
+    db::{
+        test_helper::{arb_blocks_to_commit, update_in_memory_state},
+        AptosDB,
+    },
+    ledger_db::LedgerDb,
+    schema::{
+        db_metadata::DbMetadataValue, epoch_by_version::EpochByVersionSchema, event::EventSchema,
+        event_accumulator::EventAccumulatorSchema, event_by_key::EventByKeySchema,
+        event_by_version::EventByVersionSchema, ledger_info::LedgerInfoSchema,
+        transaction::TransactionSchema, transaction_accumulator::TransactionAccumulatorSchema,
+        transaction_by_account::TransactionByAccountSchema,
+        transaction_by_hash::TransactionByHashSchema, transaction_info::TransactionInfoSchema,
+        version_data::VersionDataSchema, write_set::WriteSetSchema, DB_METADATA_CF_NAME,
+    },
+};
+use aptos_schemadb::{
+    schema::Schema, ColumnFamilyName, Options, ReadOptions, DEFAULT_COLUMN_FAMILY_NAME,
+};
+use aptos_storage_interface::AptosDbError;
+use aptos_temppath::TempPath;
+use proptest::prelude::*;
+use std::collections::BTreeMap;
+
+fn open_metadata_db(tmp_dir: &TempPath) -> DB {
+    let mut db_opts = Options::default();
+    db_opts.create_if_missing(true);
+    db_opts.create_missing_column_families(true);
+    DB::open(
+        tmp_dir.path(),
+        "test",
+        vec![DEFAULT_COLUMN_FAMILY_NAME, DB_METADATA_CF_NAME],
+        &db_opts,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_get_all_progress() {
+    let tmp_dir = TempPath::new();
+    let db = open_metadata_db(&tmp_dir);
+
+    let batch = SchemaBatch::new();
+    batch
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::LedgerCommitProgress,
+            &DbMetadataValue::Version(100),
+        )
+        .unwrap();
+    batch
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::StateKvShardCommitProgress(3),
+            &DbMetadataValue::Version(90),
+        )
+        .unwrap();
+    db.write_schemas(batch).unwrap();
+
+    let keys = [
+        DbMetadataKey::StateKvShardCommitProgress(3),
+        DbMetadataKey::OverallCommitProgress,
+        DbMetadataKey::LedgerCommitProgress,
+        DbMetadataKey::StateKvShardCommitProgress(4),
+    ];
+    assert_eq!(get_all_progress(&db, &keys).unwrap(), vec![
+        (DbMetadataKey::StateKvShardCommitProgress(3), Some(90)),
+        (DbMetadataKey::OverallCommitProgress, None),
+        (DbMetadataKey::LedgerCommitProgress, Some(100)),
+        (DbMetadataKey::StateKvShardCommitProgress(4), None),
+    ]);
+    assert!(get_all_progress(&db, &[]).unwrap().is_empty());
+}
+
+fn open_shards_with_progress(tmp_dirs: &[TempPath], progress: &[Option<Version>]) -> Vec<Arc<DB>> {
+    tmp_dirs
+        .iter()
+        .zip(progress)
+        .enumerate()
+        .map(|(shard_id, (tmp_dir, version))| {
+            let db = open_metadata_db(tmp_dir);
+            if let Some(version) = version {
+                db.put::<DbMetadataSchema>(
+                    &DbMetadataKey::StateKvShardCommitProgress(shard_id),
+                    &DbMetadataValue::Version(*version),
+                )
+                .unwrap();
+            }
+            Arc::new(db)
+        })
+        .collect()
+}
+
+#[test]
+fn test_verify_sharded_progress_consistency() {
+    let tmp_dirs: Vec<_> = (0..4).map(|_| TempPath::new()).collect();
+    let dbs = open_shards_with_progress(&tmp_dirs, &[Some(10), Some(10), Some(10), Some(10)]);
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateKvShardCommitProgress),
+        Ok(())
+    );
+
+    let tmp_dirs: Vec<_> = (0..4).map(|_| TempPath::new()).collect();
+    let dbs = open_shards_with_progress(&tmp_dirs, &[None, None, None, None]);
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateKvShardCommitProgress),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_sharded_progress_divergence() {
+    let tmp_dirs: Vec<_> = (0..4).map(|_| TempPath::new()).collect();
+    let dbs = open_shards_with_progress(&tmp_dirs, &[Some(7), Some(10), None, Some(10)]);
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateKvShardCommitProgress),
+        Err(vec![(0, Some(7)), (2, None)])
+    );
+
+    // Progress recorded under a different key is not considered.
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateMerkleShardCommitProgress),
+        Ok(())
+    );
+}
+
+/// Opens the dbs of all shards, where the shards in `failing_shards` lack the metadata column
+/// family, so any write of shard progress to them fails.
+fn open_shards_with_failures(
+    tmp_dirs: &[TempPath],
+    failing_shards: &[usize],
+) -> [Arc<DB>; NUM_STATE_SHARDS] {
+    let dbs: Vec<_> = tmp_dirs
+        .iter()
+        .enumerate()
+        .map(|(shard_id, tmp_dir)| {
+            if !failing_shards.contains(&shard_id) {
+                return Arc::new(open_metadata_db(tmp_dir));
+            }
+            let mut db_opts = Options::default();
+            db_opts.create_if_missing(true);
+            Arc::new(
+                DB::open(
+                    tmp_dir.path(),
+                    "test",
+                    vec![DEFAULT_COLUMN_FAMILY_NAME],
+                    &db_opts,
+                )
+                .unwrap(),
+            )
+        })
+        .collect();
+    dbs.try_into()
+        .unwrap_or_else(|_| panic!("Expected {} shards.", NUM_STATE_SHARDS))
+}
+
+#[test]
+fn test_commit_sharded_batches() {
+    let tmp_dirs: Vec<_> = (0..NUM_STATE_SHARDS).map(|_| TempPath::new()).collect();
+    let dbs = open_shards_with_failures(&tmp_dirs, &[]);
+    let batches = new_sharded_kv_schema_batch();
+    for (shard_id, batch) in batches.iter().enumerate() {
+        batch
+            .put::<DbMetadataSchema>(
+                &DbMetadataKey::StateKvShardCommitProgress(shard_id),
+                &DbMetadataValue::Version(10),
+            )
+            .unwrap();
+    }
+
+    commit_sharded_batches(&dbs, batches).unwrap();
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateKvShardCommitProgress),
+        Ok(())
+    );
+    assert_eq!(
+        get_progress(&dbs[0], &DbMetadataKey::StateKvShardCommitProgress(0)).unwrap(),
+        Some(10)
+    );
+}
+
+#[test]
+fn test_commit_sharded_batches_with_failing_shards() {
+    let tmp_dirs: Vec<_> = (0..NUM_STATE_SHARDS).map(|_| TempPath::new()).collect();
+    let dbs = open_shards_with_failures(&tmp_dirs, &[3, 11]);
+    let metadata_tmp_dir = TempPath::new();
+    let metadata_db = open_metadata_db(&metadata_tmp_dir);
+    let batches = new_sharded_kv_schema_batch();
+    for (shard_id, batch) in batches.iter().enumerate() {
+        batch
+            .put::<DbMetadataSchema>(
+                &DbMetadataKey::StateKvShardCommitProgress(shard_id),
+                &DbMetadataValue::Version(10),
+            )
+            .unwrap();
+    }
+
+    // Overall progress is only written once all shards are committed, as `StateKvDb` does.
+    let err = commit_sharded_batches(&dbs, batches)
+        .and_then(|()| {
+            metadata_db.put::<DbMetadataSchema>(
+                &DbMetadataKey::StateKvCommitProgress,
+                &DbMetadataValue::Version(10),
+            )
+        })
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Failed to commit shards [3, 11]"),
+        "{}",
+        err
+    );
+    assert_eq!(
+        get_progress(&metadata_db, &DbMetadataKey::StateKvCommitProgress).unwrap(),
+        None
+    );
+
+    // The other shards keep their writes.
+    assert_eq!(
+        get_progress(&dbs[0], &DbMetadataKey::StateKvShardCommitProgress(0)).unwrap(),
+        Some(10)
+    );
+
+    // Shards whose progress can't be read are reported as divergent.
+    assert_eq!(
+        verify_sharded_progress_consistency(&dbs, DbMetadataKey::StateKvShardCommitProgress),
+        Err(vec![(3, None), (11, None)])
+    );
+    assert!(get_sharded_progress(&dbs, DbMetadataKey::StateKvShardCommitProgress).is_err());
+}
+
+#[test]
+fn test_version_bounded() {
+    let items = (0..10u64).map(|v| Ok((v, v * 2)));
+    let bounded: Vec<_> = version_bounded(items, 5)
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(bounded, (0..=5u64).map(|v| (v, v * 2)).collect::<Vec<_>>());
+
+    // Nothing is yielded once the bound is exceeded, even if later items fall back under it.
+    let items = vec![1u64, 3, 8, 4].into_iter().map(|v| Ok((v, ())));
+    let versions: Vec<_> = version_bounded(items, 5)
+        .map(|res| res.unwrap().0)
+        .collect();
+    assert_eq!(versions, vec![1, 3]);
+
+    // Errors from the underlying iterator are passed through.
+    let items = vec![
+        Ok((0u64, ())),
+        Err(AptosDbError::Other("boom".to_string())),
+        Ok((1u64, ())),
+    ];
+    let mut iter = version_bounded(items.into_iter(), 5);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+
+    let mut iter = version_bounded(std::iter::empty::<Result<(Version, ())>>(), 5);
+    assert!(iter.next().is_none());
+}
+
+fn count_keys<S: Schema>(db: &DB) -> usize {
+    let mut iter = db.iter::<S>(ReadOptions::default()).unwrap();
+    iter.seek_to_first();
+    iter.count()
+}
+
+fn count_ledger_keys(ledger_db: &LedgerDb) -> BTreeMap<ColumnFamilyName, usize> {
+    fn entry<S: Schema>(db: &DB) -> (ColumnFamilyName, usize) {
+        (S::COLUMN_FAMILY_NAME, count_keys::<S>(db))
+    }
+
+    BTreeMap::from([
+        entry::<TransactionInfoSchema>(ledger_db.transaction_info_db()),
+        entry::<TransactionSchema>(ledger_db.transaction_db_raw()),
+        entry::<TransactionByAccountSchema>(ledger_db.transaction_db_raw()),
+        entry::<TransactionByHashSchema>(ledger_db.transaction_db_raw()),
+        entry::<VersionDataSchema>(ledger_db.metadata_db()),
+        entry::<EpochByVersionSchema>(ledger_db.metadata_db()),
+        entry::<LedgerInfoSchema>(ledger_db.metadata_db()),
+        entry::<WriteSetSchema>(ledger_db.write_set_db()),
+        entry::<EventSchema>(ledger_db.event_db_raw()),
+        entry::<EventByKeySchema>(ledger_db.event_db_raw()),
+        entry::<EventByVersionSchema>(ledger_db.event_db_raw()),
+        entry::<EventAccumulatorSchema>(ledger_db.event_db_raw()),
+        entry::<TransactionAccumulatorSchema>(ledger_db.transaction_accumulator_db()),
+    ])
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1))]
+
+    #[test]
+    fn test_plan_truncation(input in arb_blocks_to_commit()) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let mut in_memory_state = db.state_store.buffered_state().lock().current_state().clone();
+        let mut version = 0;
+        for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+            update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+            db.save_transactions_for_test(
+                txns_to_commit,
+                version,
+                version.checked_sub(1),
+                Some(ledger_info_with_sigs),
+                true,
+                in_memory_state.clone(),
+            )
+            .unwrap();
+            version += txns_to_commit.len() as u64;
+        }
+        let latest_version = version - 1;
+        prop_assume!(latest_version > 0);
+        let target_version = latest_version / 2;
+
+        let plan = plan_truncation(&db.ledger_db, target_version).unwrap();
+        prop_assert_eq!(plan.target_version, target_version);
+        let cf_plan = &plan.column_families[&TransactionInfoSchema::COLUMN_FAMILY_NAME];
+        prop_assert_eq!(
+            cf_plan.key_range::<TransactionInfoSchema>().unwrap(),
+            (target_version + 1, latest_version)
+        );
+        // Indices and accumulators are part of the plan as well.
+        prop_assert!(plan.num_keys(TransactionAccumulatorSchema::COLUMN_FAMILY_NAME) > 0);
+        prop_assert!(plan.num_keys(TransactionByHashSchema::COLUMN_FAMILY_NAME) > 0);
+
+        // A dry run doesn't mutate anything.
+        let num_keys_before = count_ledger_keys(&db.ledger_db);
+        prop_assert_eq!(&plan_truncation(&db.ledger_db, target_version).unwrap(), &plan);
+        prop_assert_eq!(&count_ledger_keys(&db.ledger_db), &num_keys_before);
+
+        // A plan for a different version is rejected.
+        prop_assert!(truncate_ledger_db(db.ledger_db.clone(), target_version + 1, Some(&plan)).is_err());
+        prop_assert_eq!(&count_ledger_keys(&db.ledger_db), &num_keys_before);
+
+        truncate_ledger_db(db.ledger_db.clone(), target_version, Some(&plan)).unwrap();
+        let num_keys_after = count_ledger_keys(&db.ledger_db);
+        for (cf_name, num_keys) in &num_keys_before {
+            prop_assert_eq!(num_keys - num_keys_after[cf_name], plan.num_keys(*cf_name));
+        }
+        // Every column family the plan deletes from is accounted for above.
+        for cf_name in plan.column_families.keys() {
+            prop_assert!(num_keys_before.contains_key(cf_name));
+        }
+
+        // The plan is stale now that the data is gone.
+        prop_assert!(plan_truncation(&db.ledger_db, target_version).unwrap().column_families.is_empty());
+        prop_assert!(truncate_ledger_db(db.ledger_db.clone(), target_version, Some(&plan)).is_err());
+    }
+}
@@ -30,15 +30,16 @@ use crate::{
 use aptos_jellyfish_merkle::{node_type::NodeKey, StaleNodeIndex};
 use aptos_logger::info;
 use aptos_schemadb::{
-    schema::{Schema, SeekKeyCodec},
-    ReadOptions, SchemaBatch, DB,
+    schema::{KeyCodec, Schema, SeekKeyCodec},
+    ColumnFamilyName, ReadOptions, SchemaBatch, DB,
 };
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
 use aptos_types::{proof::position::Position, transaction::Version};
 use claims::{assert_ge, assert_lt};
 use rayon::prelude::*;
 use status_line::StatusLine;
 use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{Display, Formatter},
     sync::{
         atomic::{AtomicU64, Ordering},
@@ -70,7 +71,113 @@ pub(crate) fn get_state_merkle_commit_progress(
     )
 }
 
-pub(crate) fn truncate_ledger_db(ledger_db: Arc<LedgerDb>, target_version: Version) -> Result<()> {
+/// Keys that truncation would delete from a single column family.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ColumnFamilyTruncationPlan {
+    pub num_keys: usize,
+    /// The smallest encoded key to delete
+    pub first_key: Vec<u8>,
+    /// The largest encoded key to delete
+    pub last_key: Vec<u8>,
+}
+
+impl ColumnFamilyTruncationPlan {
+    /// Decodes the range of keys to delete, with `S` the schema of the column family.
+    pub fn key_range<S: Schema>(&self) -> Result<(S::Key, S::Key)> {
+        Ok((
+            <S::Key as KeyCodec<S>>::decode_key(&self.first_key)?,
+            <S::Key as KeyCodec<S>>::decode_key(&self.last_key)?,
+        ))
+    }
+}
+
+/// A dry-run of `truncate_ledger_db`, listing the keys that would be deleted per column family.
+/// Column families with nothing to delete are omitted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct TruncationPlan {
+    pub target_version: Version,
+    pub column_families: BTreeMap<ColumnFamilyName, ColumnFamilyTruncationPlan>,
+}
+
+impl TruncationPlan {
+    pub fn num_keys(&self, cf_name: ColumnFamilyName) -> usize {
+        self.column_families
+            .get(cf_name)
+            .map_or(0, |cf_plan| cf_plan.num_keys)
+    }
+}
+
+/// Computes what truncating the ledger db to `target_version` would delete, without mutating it.
+///
+/// The plan is built from the same batches `truncate_ledger_db` writes, so it covers every column
+/// family the truncation deletes from.
+pub(crate) fn plan_truncation(
+    ledger_db: &Arc<LedgerDb>,
+    target_version: Version,
+) -> Result<TruncationPlan> {
+    let transaction_store = TransactionStore::new(Arc::clone(ledger_db));
+    let batch = prepare_ledger_db_truncation(ledger_db, &transaction_store, target_version + 1)?;
+
+    let mut keys_by_column_family: HashMap<ColumnFamilyName, BTreeSet<Vec<u8>>> = HashMap::new();
+    for schema_batch in [
+        &batch.ledger_metadata_db_batches,
+        &batch.event_db_batches,
+        &batch.transaction_accumulator_db_batches,
+        &batch.transaction_db_batches,
+        &batch.transaction_info_db_batches,
+        &batch.write_set_db_batches,
+    ] {
+        for (cf_name, keys) in schema_batch.keys_by_column_family() {
+            keys_by_column_family
+                .entry(cf_name)
+                .or_default()
+                .extend(keys);
+        }
+    }
+
+    let column_families = keys_by_column_family
+        .into_iter()
+        .filter_map(|(cf_name, keys)| {
+            let first_key = keys.first()?.clone();
+            let last_key = keys.last()?.clone();
+            Some((cf_name, ColumnFamilyTruncationPlan {
+                num_keys: keys.len(),
+                first_key,
+                last_key,
+            }))
+        })
+        .collect();
+
+    Ok(TruncationPlan {
+        target_version,
+        column_families,
+    })
+}
+
+/// Truncates the ledger db to `target_version`. If a `plan` previously computed by
+/// `plan_truncation` is provided, bails out without deleting anything unless the db still matches
+/// it.
+pub(crate) fn truncate_ledger_db(
+    ledger_db: Arc<LedgerDb>,
+    target_version: Version,
+    plan: Option<&TruncationPlan>,
+) -> Result<()> {
+    if let Some(plan) = plan {
+        ensure!(
+            plan.target_version == target_version,
+            "Truncation plan was computed for version {}, but truncating to {}.",
+            plan.target_version,
+            target_version,
+        );
+        let current_plan = plan_truncation(&ledger_db, target_version)?;
+        ensure!(
+            &current_plan == plan,
+            "Ledger db no longer matches the truncation plan. planned: {:?}, current: {:?}",
+            plan,
+            current_plan,
+        );
+    }
+
     let transaction_store = TransactionStore::new(Arc::clone(&ledger_db));
 
     let start_version = target_version + 1;
@@ -248,6 +355,25 @@ fn truncate_ledger_db_single_batch(
     transaction_store: &TransactionStore,
     start_version: Version,
 ) -> Result<()> {
+    let batch = prepare_ledger_db_truncation(ledger_db, transaction_store, start_version)?;
+
+    let progress_batch = SchemaBatch::new();
+    progress_batch.put::<DbMetadataSchema>(
+        &DbMetadataKey::LedgerCommitProgress,
+        &DbMetadataValue::Version(start_version - 1),
+    )?;
+    ledger_db.metadata_db().write_schemas(progress_batch)?;
+
+    ledger_db.write_schemas(batch)
+}
+
+/// Collects the deletion of all ledger data at or after `start_version` into batches, without
+/// writing them.
+fn prepare_ledger_db_truncation(
+    ledger_db: &LedgerDb,
+    transaction_store: &TransactionStore,
+    start_version: Version,
+) -> Result<LedgerDbSchemaBatches> {
     let batch = LedgerDbSchemaBatches::new();
 
     delete_transaction_index_data(
@@ -271,14 +397,7 @@ fn truncate_ledger_db_single_batch(
         &batch.transaction_accumulator_db_batches,
     )?;
 
-    let progress_batch = SchemaBatch::new();
-    progress_batch.put::<DbMetadataSchema>(
-        &DbMetadataKey::LedgerCommitProgress,
-        &DbMetadataValue::Version(start_version - 1),
-    )?;
-    ledger_db.metadata_db().write_schemas(progress_batch)?;
-
-    ledger_db.write_schemas(batch)
+    Ok(batch)
 }
 
 fn delete_transaction_index_data(
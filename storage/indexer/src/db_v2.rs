@@ -8,7 +8,10 @@
 use crate::{
     metadata::{MetadataKey, MetadataValue},
     schema::{
-        column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        column_families,
+        indexer_metadata::IndexerMetadataSchema,
+        table_info::{multi_get_table_info, TableInfoSchema},
+        table_info_first_seen::{prune_table_info_below, TableInfoFirstSeenSchema},
     },
 };
 use aptos_config::config::RocksdbConfig;
@@ -124,7 +127,8 @@ impl IndexerAsyncV2 {
     ) -> Result<()> {
         let end_version = first_version + write_sets.len() as Version;
         let mut table_info_parser = TableInfoParser::new(self, annotator, &self.pending_on);
-        'outer_loop: for write_set in write_sets {
+        'outer_loop: for (idx, write_set) in write_sets.iter().enumerate() {
+            table_info_parser.current_version = first_version + idx as Version;
             for (state_key, write_op) in write_set.iter() {
                 table_info_parser.parse_write_op(state_key, write_op)?;
                 // In the second sequential retry to parse write sets, we will end early if all pending on items are parsed
@@ -134,6 +138,9 @@ impl IndexerAsyncV2 {
             }
         }
         let mut batch = SchemaBatch::new();
+        for (table_handle, first_seen_version) in &table_info_parser.first_seen_versions {
+            batch.put::<TableInfoFirstSeenSchema>(&(*first_seen_version, *table_handle), &())?;
+        }
         match self.finish_table_info_parsing(&mut batch, &table_info_parser.result) {
             Ok(_) => {},
             Err(err) => {
@@ -184,8 +191,9 @@ impl IndexerAsyncV2 {
         let pending_keys: Vec<TableHandle> =
             self.pending_on.iter().map(|entry| *entry.key()).collect();
 
-        for handle in pending_keys.iter() {
-            if self.get_table_info(*handle)?.is_some() {
+        let table_infos = multi_get_table_info(&self.db, &pending_keys)?;
+        for (handle, table_info) in pending_keys.iter().zip(table_infos) {
+            if table_info.is_some() {
                 self.pending_on.remove(handle);
             }
         }
@@ -200,6 +208,18 @@ impl IndexerAsyncV2 {
         Ok(())
     }
 
+    /// Prunes the table info of all table handles first seen before `version`. The indexer
+    /// progress is left untouched. Returns the number of table handles pruned.
+    pub fn prune_table_info_below(&self, version: Version) -> Result<usize> {
+        let num_pruned = prune_table_info_below(&self.db, version)?;
+        info!(
+            version = version,
+            num_pruned = num_pruned,
+            "[DB] Pruned table info first seen below version",
+        );
+        Ok(num_pruned)
+    }
+
     pub fn next_version(&self) -> Version {
         self.next_version.load(Ordering::Relaxed)
     }
@@ -233,6 +253,10 @@ struct TableInfoParser<'a, R> {
     indexer_async_v2: &'a IndexerAsyncV2,
     annotator: &'a MoveValueAnnotator<'a, R>,
     result: HashMap<TableHandle, TableInfo>,
+    // Version of the write set where each newly found table handle was first seen
+    first_seen_versions: HashMap<TableHandle, Version>,
+    // Version of the write set currently being parsed
+    current_version: Version,
     pending_on: &'a DashMap<TableHandle, DashSet<Bytes>>,
 }
 
@@ -246,6 +270,8 @@ impl<'a, R: ModuleResolver> TableInfoParser<'a, R> {
             indexer_async_v2,
             annotator,
             result: HashMap::new(),
+            first_seen_versions: HashMap::new(),
+            current_version: 0,
             pending_on,
         }
     }
@@ -356,6 +382,8 @@ impl<'a, R: ModuleResolver> TableInfoParser<'a, R> {
     fn save_table_info(&mut self, handle: TableHandle, info: TableInfo) -> Result<()> {
         if self.get_table_info(handle)?.is_none() {
             self.result.insert(handle, info);
+            self.first_seen_versions
+                .insert(handle, self.current_version);
             if let Some(pending_items) = self.pending_on.remove(&handle) {
                 for bytes in pending_items.1 {
                     self.parse_table_item(handle, &bytes)?;
@@ -385,3 +413,149 @@ impl<'a, R: ModuleResolver> TableInfoParser<'a, R> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{access_path::AccessPath, write_set::WriteSetMut};
+    use move_core_types::{identifier::Identifier, language_storage::ModuleId, metadata::Metadata};
+    use move_ir_compiler::Compiler;
+    use std::str::FromStr;
+
+    const TEST_MODULE: &str = "
+        module 0xcafe.tables {
+            import 0x1.table;
+            struct Holder has key { tables: vector<table.Table<u64, bool>> }
+            struct Member has key { inner: table.Table<address, u128> }
+        }
+    ";
+
+    /// Serves the framework modules and the test module to the annotator.
+    struct TestModuleResolver {
+        modules: HashMap<ModuleId, Bytes>,
+    }
+
+    impl TestModuleResolver {
+        fn new() -> Self {
+            let framework_modules = aptos_cached_packages::head_release_bundle().compiled_modules();
+            let test_module = Compiler {
+                deps: framework_modules.iter().collect(),
+            }
+            .into_compiled_module(TEST_MODULE)
+            .unwrap();
+
+            let mut modules = HashMap::new();
+            for module in framework_modules.iter().chain([&test_module]) {
+                let mut bytes = vec![];
+                module.serialize(&mut bytes).unwrap();
+                modules.insert(module.self_id(), bytes.into());
+            }
+            Self { modules }
+        }
+    }
+
+    impl ModuleResolver for TestModuleResolver {
+        type Error = anyhow::Error;
+
+        fn get_module_metadata(&self, _module_id: &ModuleId) -> Vec<Metadata> {
+            vec![]
+        }
+
+        fn get_module(&self, id: &ModuleId) -> anyhow::Result<Option<Bytes>> {
+            Ok(self.modules.get(id).cloned())
+        }
+    }
+
+    fn test_struct_tag(name: &str) -> StructTag {
+        StructTag {
+            address: AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            module: Identifier::new("tables").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    fn open_indexer() -> (aptos_temppath::TempPath, IndexerAsyncV2) {
+        let tmpdir = aptos_temppath::TempPath::new();
+        let indexer =
+            IndexerAsyncV2::open(&tmpdir, RocksdbConfig::default(), DashMap::new()).unwrap();
+        (tmpdir, indexer)
+    }
+
+    fn assert_table_info(
+        indexer: &IndexerAsyncV2,
+        handle: AccountAddress,
+        key_type: TypeTag,
+        value_type: TypeTag,
+    ) {
+        assert_eq!(
+            indexer.get_table_info(TableHandle(handle)).unwrap(),
+            Some(TableInfo {
+                key_type,
+                value_type
+            })
+        );
+    }
+
+    #[test]
+    fn test_index_tables_in_vector() {
+        let (_tmpdir, indexer) = open_indexer();
+        let resolver = TestModuleResolver::new();
+        let annotator = MoveValueAnnotator::new(&resolver);
+
+        let handles = vec![AccountAddress::random(), AccountAddress::random()];
+        let state_key = StateKey::access_path(
+            AccessPath::resource_access_path(AccountAddress::ONE, test_struct_tag("Holder"))
+                .unwrap(),
+        );
+        let write_set = WriteSetMut::new(vec![(
+            state_key,
+            WriteOp::legacy_creation(bcs::to_bytes(&handles).unwrap().into()),
+        )])
+        .freeze()
+        .unwrap();
+
+        indexer
+            .index_with_annotator(&annotator, 0, &[&write_set], false)
+            .unwrap();
+        for handle in handles {
+            assert_table_info(&indexer, handle, TypeTag::U64, TypeTag::Bool);
+        }
+    }
+
+    #[test]
+    fn test_index_tables_in_resource_group() {
+        let (_tmpdir, indexer) = open_indexer();
+        let resolver = TestModuleResolver::new();
+        let annotator = MoveValueAnnotator::new(&resolver);
+
+        let member_handle = AccountAddress::random();
+        let vector_handle = AccountAddress::random();
+        let group: BTreeMap<StructTag, Vec<u8>> = BTreeMap::from([
+            (
+                test_struct_tag("Member"),
+                bcs::to_bytes(&member_handle).unwrap(),
+            ),
+            (
+                test_struct_tag("Holder"),
+                bcs::to_bytes(&vec![vector_handle]).unwrap(),
+            ),
+        ]);
+        let state_key = StateKey::access_path(AccessPath::resource_group_access_path(
+            AccountAddress::ONE,
+            StructTag::from_str("0x1::object::ObjectGroup").unwrap(),
+        ));
+        let write_set = WriteSetMut::new(vec![(
+            state_key,
+            WriteOp::legacy_creation(bcs::to_bytes(&group).unwrap().into()),
+        )])
+        .freeze()
+        .unwrap();
+
+        indexer
+            .index_with_annotator(&annotator, 0, &[&write_set], false)
+            .unwrap();
+        assert_table_info(&indexer, member_handle, TypeTag::Address, TypeTag::U128);
+        assert_table_info(&indexer, vector_handle, TypeTag::U64, TypeTag::Bool);
+    }
+}
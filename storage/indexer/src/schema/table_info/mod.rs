@@ -14,6 +14,7 @@ use anyhow::Result;
 use aptos_schemadb::{
     define_schema,
     schema::{KeyCodec, ValueCodec},
+    DB,
 };
 use aptos_types::state_store::table::{TableHandle, TableInfo};
 
@@ -39,5 +40,14 @@ impl ValueCodec<TableInfoSchema> for TableInfo {
     }
 }
 
+/// Looks up the table info of multiple table handles in one batched read. The result is in the
+/// same order as `handles`, with `None` for handles that have not been indexed yet.
+pub(crate) fn multi_get_table_info(
+    db: &DB,
+    handles: &[TableHandle],
+) -> Result<Vec<Option<TableInfo>>> {
+    Ok(db.multi_get::<TableInfoSchema>(handles)?)
+}
+
 #[cfg(test)]
 mod test;
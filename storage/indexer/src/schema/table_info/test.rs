@@ -3,6 +3,7 @@
 
 use super::*;
 use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
 use proptest::prelude::*;
 
 proptest! {
@@ -16,3 +17,40 @@ proptest! {
 }
 
 test_no_panic_decoding!(TableInfoSchema);
+
+#[test]
+fn test_multi_get_table_info() {
+    let tmpdir = aptos_temppath::TempPath::new();
+    let mut db_opts = aptos_schemadb::Options::default();
+    db_opts.create_if_missing(true);
+    db_opts.create_missing_column_families(true);
+    let db = DB::open(
+        tmpdir.path(),
+        "test",
+        crate::schema::column_families(),
+        &db_opts,
+    )
+    .unwrap();
+
+    let handle = |i: u8| TableHandle(AccountAddress::new([i; AccountAddress::LENGTH]));
+    let table_info = |key_type: TypeTag| TableInfo {
+        key_type,
+        value_type: TypeTag::U64,
+    };
+    db.put::<TableInfoSchema>(&handle(1), &table_info(TypeTag::U8))
+        .unwrap();
+    db.put::<TableInfoSchema>(&handle(2), &table_info(TypeTag::Address))
+        .unwrap();
+    db.put::<TableInfoSchema>(&handle(4), &table_info(TypeTag::Bool))
+        .unwrap();
+
+    let handles = [handle(4), handle(3), handle(1), handle(5), handle(2)];
+    assert_eq!(multi_get_table_info(&db, &handles).unwrap(), vec![
+        Some(table_info(TypeTag::Bool)),
+        None,
+        Some(table_info(TypeTag::U8)),
+        None,
+        Some(table_info(TypeTag::Address)),
+    ]);
+    assert!(multi_get_table_info(&db, &[]).unwrap().is_empty());
+}
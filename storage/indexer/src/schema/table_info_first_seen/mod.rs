@@ -0,0 +1,100 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema indexing table handles by the version at which
+//! their table info was first seen, so that old table info can be pruned in version order.
+//!
+//! ```text
+//! |<----------------key---------------->|
+//! | first_seen_version | table_handle |
+//! ```
+//!
+//! `first_seen_version` is serialized in big endian so that records in RocksDB will be in order of
+//! its numeric value.
+
+use crate::schema::{table_info::TableInfoSchema, TABLE_INFO_FIRST_SEEN_CF_NAME};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+    ReadOptions, SchemaBatch, DB,
+};
+use aptos_types::{state_store::table::TableHandle, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+type Key = (Version, TableHandle);
+
+define_schema!(
+    TableInfoFirstSeenSchema,
+    Key,
+    (),
+    TABLE_INFO_FIRST_SEEN_CF_NAME
+);
+
+impl KeyCodec<TableInfoFirstSeenSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (first_seen_version, handle) = self;
+        let mut encoded = first_seen_version.to_be_bytes().to_vec();
+        encoded.extend(bcs::to_bytes(handle)?);
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+
+        ensure!(
+            data.len() > VERSION_SIZE,
+            "Unexpected data len {}, expected to be greater than {}.",
+            data.len(),
+            VERSION_SIZE,
+        );
+        let first_seen_version = (&data[..VERSION_SIZE]).read_u64::<BigEndian>()?;
+        let handle = bcs::from_bytes(&data[VERSION_SIZE..])?;
+        Ok((first_seen_version, handle))
+    }
+}
+
+impl ValueCodec<TableInfoFirstSeenSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.is_empty(),
+            "Unexpected data len {}, expected to be 0.",
+            data.len(),
+        );
+        Ok(())
+    }
+}
+
+/// Deletes the table info of all table handles first seen at a version below `version`, together
+/// with their index entries, in a single batch. Returns the number of table handles pruned.
+///
+/// Only the table info and first-seen column families are touched, so the indexer progress marker
+/// is never pruned.
+pub(crate) fn prune_table_info_below(db: &DB, version: Version) -> Result<usize> {
+    let batch = SchemaBatch::new();
+    let mut iter = db.iter::<TableInfoFirstSeenSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+
+    let mut num_pruned = 0;
+    for item in iter {
+        let (key, ()) = item?;
+        let (first_seen_version, handle) = key;
+        if first_seen_version >= version {
+            break;
+        }
+        batch.delete::<TableInfoSchema>(&handle)?;
+        batch.delete::<TableInfoFirstSeenSchema>(&key)?;
+        num_pruned += 1;
+    }
+    db.write_schemas(batch)?;
+
+    Ok(num_pruned)
+}
+
+#[cfg(test)]
+mod test;
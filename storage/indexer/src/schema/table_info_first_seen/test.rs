@@ -0,0 +1,84 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    metadata::{MetadataKey, MetadataValue},
+    schema::indexer_metadata::IndexerMetadataSchema,
+};
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use aptos_types::state_store::table::TableInfo;
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        first_seen_version in any::<Version>(),
+        table_handle in any::<TableHandle>(),
+    ) {
+        assert_encode_decode::<TableInfoFirstSeenSchema>(&(first_seen_version, table_handle), &());
+    }
+}
+
+test_no_panic_decoding!(TableInfoFirstSeenSchema);
+
+#[test]
+fn test_prune_table_info_below() {
+    let tmpdir = aptos_temppath::TempPath::new();
+    let mut db_opts = aptos_schemadb::Options::default();
+    db_opts.create_if_missing(true);
+    db_opts.create_missing_column_families(true);
+    let db = DB::open(
+        tmpdir.path(),
+        "test",
+        crate::schema::column_families(),
+        &db_opts,
+    )
+    .unwrap();
+
+    let handle = |i: u8| TableHandle(AccountAddress::new([i; AccountAddress::LENGTH]));
+    let table_info = TableInfo {
+        key_type: TypeTag::Address,
+        value_type: TypeTag::U64,
+    };
+    let batch = SchemaBatch::new();
+    for (version, i) in [(0, 1), (5, 2), (5, 3), (10, 4), (11, 5)] {
+        batch
+            .put::<TableInfoSchema>(&handle(i), &table_info)
+            .unwrap();
+        batch
+            .put::<TableInfoFirstSeenSchema>(&(version, handle(i)), &())
+            .unwrap();
+    }
+    batch
+        .put::<IndexerMetadataSchema>(&MetadataKey::LatestVersion, &MetadataValue::Version(11))
+        .unwrap();
+    db.write_schemas(batch).unwrap();
+
+    assert_eq!(prune_table_info_below(&db, 10).unwrap(), 3);
+    for i in 1..=3 {
+        assert_eq!(db.get::<TableInfoSchema>(&handle(i)).unwrap(), None);
+    }
+    for i in 4..=5 {
+        assert_eq!(
+            db.get::<TableInfoSchema>(&handle(i)).unwrap(),
+            Some(table_info.clone())
+        );
+    }
+
+    let mut iter = db
+        .iter::<TableInfoFirstSeenSchema>(ReadOptions::default())
+        .unwrap();
+    iter.seek_to_first();
+    let remaining = iter.map(|item| item.unwrap().0).collect::<Vec<_>>();
+    assert_eq!(remaining, vec![(10, handle(4)), (11, handle(5))]);
+
+    // Pruning again below the same version is a no-op.
+    assert_eq!(prune_table_info_below(&db, 10).unwrap(), 0);
+    assert_eq!(
+        db.get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)
+            .unwrap(),
+        Some(MetadataValue::Version(11))
+    );
+}
@@ -40,7 +40,11 @@ pub use rocksdb::{
     BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, Options, ReadOptions,
     SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
-use std::{collections::HashMap, iter::Iterator, path::Path};
+use std::{
+    collections::{BTreeSet, HashMap},
+    iter::Iterator,
+    path::Path,
+};
 
 pub type ColumnFamilyName = &'static str;
 
@@ -99,6 +103,24 @@ impl SchemaBatch {
 
         Ok(())
     }
+
+    /// Returns the distinct encoded keys the batch writes or deletes per column family, e.g. to
+    /// report what writing the batch would change.
+    pub fn keys_by_column_family(&self) -> HashMap<ColumnFamilyName, BTreeSet<Vec<u8>>> {
+        self.rows
+            .lock()
+            .iter()
+            .map(|(cf_name, ops)| {
+                let keys = ops
+                    .iter()
+                    .map(|op| match op {
+                        WriteOp::Value { key, .. } | WriteOp::Deletion { key } => key.clone(),
+                    })
+                    .collect();
+                (*cf_name, keys)
+            })
+            .collect()
+    }
 }
 
 /// This DB is a schematized RocksDB wrapper where all data passed in and out are typed according to
@@ -202,6 +224,36 @@ impl DB {
             .map_err(Into::into)
     }
 
+    /// Reads multiple records by key in a single batched lookup. Results are returned in the same
+    /// order as `schema_keys`.
+    pub fn multi_get<S: Schema>(&self, schema_keys: &[S::Key]) -> DbResult<Vec<Option<S::Value>>> {
+        let _timer = APTOS_SCHEMADB_GET_LATENCY_SECONDS
+            .with_label_values(&[S::COLUMN_FAMILY_NAME])
+            .start_timer();
+
+        let keys = schema_keys
+            .iter()
+            .map(<S::Key as KeyCodec<S>>::encode_key)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let cf_handle = self.get_cf_handle(S::COLUMN_FAMILY_NAME)?;
+
+        self.inner
+            .batched_multi_get_cf(cf_handle, &keys, false)
+            .into_iter()
+            .map(|result| {
+                let result = result?;
+                APTOS_SCHEMADB_GET_BYTES
+                    .with_label_values(&[S::COLUMN_FAMILY_NAME])
+                    .observe(result.as_ref().map_or(0.0, |v| v.len() as f64));
+
+                result
+                    .map(|raw_value| <S::Value as ValueCodec<S>>::decode_value(&raw_value))
+                    .transpose()
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
     /// Writes single record.
     pub fn put<S: Schema>(&self, key: &S::Key, value: &S::Value) -> DbResult<()> {
         // Not necessary to use a batch, but we'd like a central place to bump counters.
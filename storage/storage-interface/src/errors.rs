@@ -18,6 +18,16 @@ pub enum AptosDbError {
     TooManyRequested(u64, u64),
     #[error("Missing state root node at version {0}, probably pruned.")]
     MissingRootError(u64),
+    /// The requested version was pruned.
+    #[error(
+        "{data_type} at version {requested_version} is pruned, min available version is \
+        {min_readable_version}."
+    )]
+    Pruned {
+        data_type: String,
+        requested_version: u64,
+        min_readable_version: u64,
+    },
     /// Other non-classified error.
     #[error("AptosDB Other Error: {0}")]
     Other(String),
@@ -73,6 +83,15 @@ impl From<AptosDbError> for StateviewError {
     fn from(error: AptosDbError) -> Self {
         match error {
             AptosDbError::NotFound(msg) => StateviewError::NotFound(msg),
+            AptosDbError::Pruned {
+                data_type,
+                requested_version,
+                min_readable_version,
+            } => StateviewError::Pruned {
+                data_type,
+                requested_version,
+                min_readable_version,
+            },
             AptosDbError::Other(msg) => StateviewError::Other(msg),
             _ => StateviewError::Other(format!("{}", error)),
         }
@@ -83,6 +102,15 @@ impl From<StateviewError> for AptosDbError {
     fn from(error: StateviewError) -> Self {
         match error {
             StateviewError::NotFound(msg) => AptosDbError::NotFound(msg),
+            StateviewError::Pruned {
+                data_type,
+                requested_version,
+                min_readable_version,
+            } => AptosDbError::Pruned {
+                data_type,
+                requested_version,
+                min_readable_version,
+            },
             StateviewError::Other(msg) => AptosDbError::Other(msg),
         }
     }
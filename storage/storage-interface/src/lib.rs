@@ -204,6 +204,17 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<Vec<EventWithVersion>>;
 
+        /// Returns up to `limit` events by given event key, from the `start` sequence number (or
+        /// the latest event if `None`) downwards. Yields nothing if `start` is beyond the latest
+        /// event as of `ledger_version`.
+        fn get_events_rev_iterator(
+            &self,
+            event_key: &EventKey,
+            start: Option<u64>,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Box<dyn Iterator<Item = Result<EventWithVersion>> + '_>>;
+
         fn get_transaction_iterator(
             &self,
             start_version: Version,
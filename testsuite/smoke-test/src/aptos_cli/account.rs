@@ -56,6 +56,7 @@ async fn test_account_flow() {
                 gas_unit_price: Some(2),
                 max_gas: None,
                 expiration_secs: 30,
+                ..Default::default()
             }),
         )
         .await
@@ -80,6 +81,7 @@ async fn test_account_flow() {
             //            Read the comment above to understand why.
             max_gas: Some(2),
             expiration_secs: 30,
+            ..Default::default()
         }),
     )
     .await
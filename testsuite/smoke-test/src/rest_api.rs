@@ -326,6 +326,14 @@ async fn test_bcs() {
     assert_eq!(json_module.bytecode.0, bcs_module);
     assert_eq!(module_bytecode.bytecode.0, bcs_module);
 
+    let abi_only_module = client
+        .get_account_module_abi(AccountAddress::ONE, module_id.name.as_str())
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(abi_only_module.bytecode.0.is_empty());
+    assert_eq!(abi_only_module.abi, json_module.abi);
+
     // Transfer money to make a transaction
     let pending_transaction = info
         .transfer(&mut local_account, &other_local_account, 500)
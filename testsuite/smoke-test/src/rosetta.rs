@@ -336,6 +336,7 @@ async fn test_account_balance() {
                 gas_unit_price: None,
                 max_gas: Some(1000),
                 expiration_secs: 30,
+                ..Default::default()
             }),
         )
         .await
@@ -1839,6 +1840,7 @@ async fn test_invalid_transaction_gas_charged() {
                 gas_unit_price: None,
                 max_gas: Some(1000),
                 expiration_secs: 30,
+                ..Default::default()
             }),
         )
         .await
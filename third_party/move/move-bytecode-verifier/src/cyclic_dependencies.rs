@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module contains verification of usage of dependencies for modules
+use crate::dependencies::module_relation_message;
 use move_binary_format::{
     access::ModuleAccess,
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
@@ -16,7 +17,8 @@ use std::collections::BTreeSet;
 //
 // During the DFS,
 // - 1) if the `target_module_id` is found, the exploration will be short-circuited and returns a
-//   PartialVMError bearing the StatusCode specified in `error_on_cycle`.
+//   PartialVMError bearing the StatusCode specified in `error_on_cycle`, naming the item in
+//   `items_to_explore` that leads back to `target_module_id`.
 // - 2) if the `target_module_id` is not found, the modules visited in the DFS will be returned at
 //   the end of function execution.
 fn collect_all_with_cycle_detection<F: Fn(&ModuleId) -> PartialVMResult<Vec<ModuleId>>>(
@@ -24,6 +26,7 @@ fn collect_all_with_cycle_detection<F: Fn(&ModuleId) -> PartialVMResult<Vec<Modu
     items_to_explore: &[ModuleId],
     immediate_nexts: &F,
     error_on_cycle: StatusCode,
+    relation: &str,
 ) -> PartialVMResult<BTreeSet<ModuleId>> {
     fn collect_all_with_cycle_detection_recursive<
         F: Fn(&ModuleId) -> PartialVMResult<Vec<ModuleId>>,
@@ -59,7 +62,13 @@ fn collect_all_with_cycle_detection<F: Fn(&ModuleId) -> PartialVMResult<Vec<Modu
             immediate_nexts,
             &mut visited_modules,
         )? {
-            return Err(PartialVMError::new(error_on_cycle));
+            return Err(
+                PartialVMError::new(error_on_cycle).with_message(module_relation_message(
+                    target_module_id,
+                    item,
+                    &format!("cyclic module {}", relation),
+                )),
+            );
         }
     }
     Ok(visited_modules)
@@ -91,6 +100,7 @@ where
         &module.immediate_dependencies(),
         &imm_deps,
         StatusCode::CYCLIC_MODULE_DEPENDENCY,
+        "dependency",
     )?;
 
     // collect and check that there is no cyclic friend relation
@@ -99,6 +109,7 @@ where
         &module.immediate_friends(),
         &imm_friends,
         StatusCode::CYCLIC_MODULE_FRIENDSHIP,
+        "friendship",
     )?;
 
     // check that any direct/transitive dependency is neither a direct nor transitive friend
@@ -106,9 +117,10 @@ where
         Some(overlap) => Err(PartialVMError::new(
             StatusCode::INVALID_FRIEND_DECL_WITH_MODULES_IN_DEPENDENCIES,
         )
-        .with_message(format!(
-            "At least one module, {}, appears in both the dependency set and the friend set",
-            overlap
+        .with_message(module_relation_message(
+            &self_id,
+            overlap,
+            "module appears in both the dependency set and the friend set",
         ))),
         None => Ok(()),
     }
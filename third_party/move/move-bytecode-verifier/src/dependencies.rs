@@ -18,6 +18,12 @@ use move_binary_format::{
 use move_core_types::{identifier::Identifier, language_storage::ModuleId, vm_status::StatusCode};
 use std::collections::{BTreeMap, BTreeSet};
 
+/// Formats the message of an error caused by a relation between two modules, e.g. a dependency
+/// or a friend declaration, as `source -> target: reason`. Tools may parse this, keep it stable.
+pub fn module_relation_message(source: &ModuleId, target: &ModuleId, reason: &str) -> String {
+    format!("{} -> {}: {}", source, target, reason)
+}
+
 struct Context<'a, 'b> {
     resolver: BinaryIndexedView<'a>,
     // (Module -> CompiledModule) for (at least) all immediate dependencies
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module contains verification of usage of dependencies for modules
+use crate::dependencies::module_relation_message;
 use move_binary_format::{
     access::ModuleAccess,
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
@@ -18,9 +19,12 @@ fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
     // cannot make friends with the module itself
     let self_handle = module.self_handle();
     if module.friend_decls().contains(self_handle) {
-        return Err(PartialVMError::new(
-            StatusCode::INVALID_FRIEND_DECL_WITH_SELF,
-        ));
+        let self_id = module.self_id();
+        return Err(
+            PartialVMError::new(StatusCode::INVALID_FRIEND_DECL_WITH_SELF).with_message(
+                module_relation_message(&self_id, &self_id, "friend declaration with self"),
+            ),
+        );
     }
 
     // cannot make friends with modules outside of the account address
@@ -34,14 +38,19 @@ fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
     // constraint and we may consider lifting this limitation in the future.
     let self_address =
         module.address_identifier_at(module.module_handle_at(module.self_handle_idx()).address);
-    let has_external_friend = module
+    let external_friend = module
         .friend_decls()
         .iter()
-        .any(|handle| module.address_identifier_at(handle.address) != self_address);
-    if has_external_friend {
+        .find(|handle| module.address_identifier_at(handle.address) != self_address);
+    if let Some(handle) = external_friend {
         return Err(PartialVMError::new(
             StatusCode::INVALID_FRIEND_DECL_WITH_MODULES_OUTSIDE_ACCOUNT_ADDRESS,
-        ));
+        )
+        .with_message(module_relation_message(
+            &module.self_id(),
+            &module.module_id_for_handle(handle),
+            "friend declared outside of the account address",
+        )));
     }
 
     Ok(())
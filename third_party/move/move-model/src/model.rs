@@ -2817,6 +2817,7 @@ impl<'env> ModuleEnv<'env> {
                 print_code: true,
                 print_basic_blocks: true,
                 print_locals: true,
+                no_code_offsets: false,
             },
         );
         Some(
@@ -11,6 +11,7 @@ mod instantiation_tests;
 mod invariant_violation_tests;
 mod leak_tests;
 mod loader_tests;
+mod module_relation_error_tests;
 mod mutated_accounts_tests;
 mod native_tests;
 mod nested_loop_tests;
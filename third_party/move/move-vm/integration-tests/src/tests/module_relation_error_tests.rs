@@ -0,0 +1,109 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_binary_format::file_format::{AddressIdentifierIndex, CompiledModule};
+use move_core_types::{account_address::AccountAddress, vm_status::StatusCode};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas::UnmeteredGasMeter;
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+const OTHER_ADDR: AccountAddress = AccountAddress::new([43; AccountAddress::LENGTH]);
+
+fn compile_module(code: &str, name: &str) -> CompiledModule {
+    let code = code.replace("{{ADDR}}", &format!("0x{}", TEST_ADDR.to_hex()));
+    compile_units(&code)
+        .unwrap()
+        .into_iter()
+        .map(as_module)
+        .find(|m| m.self_id().name().as_str() == name)
+        .unwrap()
+}
+
+fn serialize(m: &CompiledModule) -> Vec<u8> {
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+    blob
+}
+
+#[test]
+fn test_cyclic_dependency_error_names_modules() {
+    let code = r#"
+        module {{ADDR}}::A {
+            public fun foo(): u64 { 5 }
+        }
+        module {{ADDR}}::B {
+            use {{ADDR}}::A;
+            public fun foo(): u64 { A::foo() }
+        }
+    "#;
+    let a = compile_module(code, "A");
+    let b = compile_module(code, "B");
+
+    // A republished to depend on B, compiled against a B without dependencies
+    let code = r#"
+        module {{ADDR}}::B {
+            public fun foo(): u64 { 0 }
+        }
+        module {{ADDR}}::A {
+            use {{ADDR}}::B;
+            public fun foo(): u64 { B::foo() }
+        }
+    "#;
+    let a_cyclic = compile_module(code, "A");
+
+    let storage = InMemoryStorage::new();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    sess.publish_module(serialize(&a), TEST_ADDR, &mut UnmeteredGasMeter)
+        .unwrap();
+    sess.publish_module(serialize(&b), TEST_ADDR, &mut UnmeteredGasMeter)
+        .unwrap();
+
+    let err = sess
+        .publish_module(serialize(&a_cyclic), TEST_ADDR, &mut UnmeteredGasMeter)
+        .unwrap_err();
+    assert_eq!(err.major_status(), StatusCode::CYCLIC_MODULE_DEPENDENCY);
+    let message = err.message().unwrap();
+    assert!(message.contains(&a.self_id().to_string()), "{}", message);
+    assert!(message.contains(&b.self_id().to_string()), "{}", message);
+    assert!(message.contains("cyclic module dependency"), "{}", message);
+}
+
+#[test]
+fn test_external_friend_error_names_modules() {
+    let code = r#"
+        module {{ADDR}}::N {}
+        module {{ADDR}}::M {
+            friend {{ADDR}}::N;
+        }
+    "#;
+    let mut m = compile_module(code, "M");
+
+    // The compiler rejects friends at other addresses, so move the friend there after the fact
+    m.address_identifiers.push(OTHER_ADDR);
+    m.friend_decls[0].address = AddressIdentifierIndex((m.address_identifiers.len() - 1) as u16);
+    let friend_id = m.module_id_for_handle(&m.friend_decls[0]);
+    assert_eq!(friend_id.address(), &OTHER_ADDR);
+
+    let storage = InMemoryStorage::new();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let mut sess = vm.new_session(&storage);
+    let err = sess
+        .publish_module(serialize(&m), TEST_ADDR, &mut UnmeteredGasMeter)
+        .unwrap_err();
+    assert_eq!(
+        err.major_status(),
+        StatusCode::INVALID_FRIEND_DECL_WITH_MODULES_OUTSIDE_ACCOUNT_ADDRESS
+    );
+    let message = err.message().unwrap();
+    assert_eq!(
+        message,
+        &format!(
+            "{} -> {}: friend declared outside of the account address",
+            m.self_id(),
+            friend_id
+        )
+    );
+}
@@ -887,12 +887,6 @@ impl Loader {
         allow_module_loading_failure: bool,
         dependencies_depth: usize,
     ) -> VMResult<Arc<Module>> {
-        // dependency loading does not permit cycles
-        if visited.contains(id) {
-            return Err(PartialVMError::new(StatusCode::CYCLIC_MODULE_DEPENDENCY)
-                .finish(Location::Undefined));
-        }
-
         // module self-check
         let module = self.load_and_verify_module(id, data_store, allow_module_loading_failure)?;
         visited.insert(id.clone());
@@ -949,6 +943,16 @@ impl Loader {
                 bundle_deps.push(cached);
             } else {
                 let loaded = match module_store.module_at(&module_id) {
+                    // dependency loading does not permit cycles
+                    None if visited.contains(&module_id) => {
+                        return Err(PartialVMError::new(StatusCode::CYCLIC_MODULE_DEPENDENCY)
+                            .with_message(dependencies::module_relation_message(
+                                &module.self_id(),
+                                &module_id,
+                                "cyclic module dependency",
+                            ))
+                            .finish(Location::Undefined));
+                    },
                     None => self.load_and_verify_module_and_dependencies(
                         &module_id,
                         bundle_verified,
@@ -43,6 +43,10 @@ pub struct DisassemblerOptions {
     /// Print the locals inside each function body.
     #[clap(long = "print-locals")]
     pub print_locals: bool,
+
+    /// Leave out the offset of each instruction within its function.
+    #[clap(long = "no-code-offsets")]
+    pub no_code_offsets: bool,
 }
 
 impl DisassemblerOptions {
@@ -52,6 +56,7 @@ impl DisassemblerOptions {
             print_code: true,
             print_basic_blocks: true,
             print_locals: true,
+            no_code_offsets: false,
         }
     }
 }
@@ -279,13 +284,18 @@ impl<'a> Disassembler<'a> {
         function_coverage_map: Option<&FunctionCoverage>,
         instruction: String,
     ) -> String {
+        let instruction = if self.options.no_code_offsets {
+            instruction
+        } else {
+            format!("{}: {}", pc, instruction)
+        };
         if self.coverage_map.is_none() {
-            return format!("\t{}: {}", pc, instruction);
+            return format!("\t{}", instruction);
         }
         let coverage = function_coverage_map.and_then(|map| map.get(&(pc as u64)));
         match coverage {
-            Some(coverage) => format!("[{}]\t{}: {}", coverage, pc, instruction).green(),
-            None => format!("\t{}: {}", pc, instruction).red(),
+            Some(coverage) => format!("[{}]\t{}", coverage, instruction).green(),
+            None => format!("\t{}", instruction).red(),
         }
         .to_string()
     }
@@ -510,6 +510,33 @@ impl PartitionedTransactions {
         self.num_sharded_txns() + self.global_txns.len()
     }
 
+    pub fn num_rounds(&self) -> usize {
+        self.sharded_txns
+            .iter()
+            .map(|sub_blocks| sub_blocks.num_sub_blocks())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of sharded txns in each round, summed over all shards. Global txns are not included.
+    pub fn num_txns_by_round(&self) -> Vec<usize> {
+        (0..self.num_rounds())
+            .map(|round| self.num_txns_in_round_by_shard(round).iter().sum())
+            .collect()
+    }
+
+    /// The number of txns each shard executes in the given round.
+    pub fn num_txns_in_round_by_shard(&self, round: usize) -> Vec<usize> {
+        self.sharded_txns
+            .iter()
+            .map(|sub_blocks| {
+                sub_blocks
+                    .get_sub_block(round)
+                    .map_or(0, |sub_block| sub_block.num_txns())
+            })
+            .collect()
+    }
+
     pub fn add_checkpoint_txn(&mut self, last_txn: SignatureVerifiedTransaction) {
         assert!(matches!(
             last_txn.expect_valid(),
@@ -3,11 +3,15 @@
 #[cfg(test)]
 use crate::move_any::Any as MoveAny;
 use crate::{move_any::AsMoveAny, move_utils::as_move_value::AsMoveValue};
-use anyhow::{anyhow, ensure};
+use anyhow::{anyhow, bail, ensure, Result};
 use move_core_types::value::{MoveStruct, MoveValue};
 use serde::{Deserialize, Serialize};
 #[cfg(test)]
 use std::str::FromStr;
+use thiserror::Error;
+
+/// The size of the RSA moduli that are supported, i.e. RSA-2048.
+pub const RSA_MODULUS_BYTES: usize = 256;
 
 /// Move type `0x1::jwks::RSA_JWK` in rust.
 #[allow(non_camel_case_types)]
@@ -31,54 +35,120 @@ impl RSA_JWK {
             n: n.to_string(),
         }
     }
+
+    /// Checks that the base64url-encoded modulus `n` decodes to `RSA_MODULUS_BYTES` bytes.
+    pub fn validate_modulus_size(&self) -> Result<()> {
+        let modulus = base64::decode_config(&self.n, base64::URL_SAFE_NO_PAD)
+            .map_err(|e| anyhow!("Field `n` is not valid base64url: {}", e))?;
+        ensure!(
+            modulus.len() == RSA_MODULUS_BYTES,
+            "RSA modulus is {} bytes, expected {}",
+            modulus.len(),
+            RSA_MODULUS_BYTES
+        );
+        Ok(())
+    }
+
+    /// Parses the Move struct produced by `as_move_value`, e.g. when read off-chain.
+    pub fn from_move_value(value: &MoveValue) -> Result<Self> {
+        let fields = match value.clone().undecorate() {
+            MoveValue::Struct(move_struct) => move_struct.into_fields(),
+            _ => bail!("RSA_JWK move value is not a struct"),
+        };
+        ensure!(
+            fields.len() == 5,
+            "RSA_JWK move value has {} fields, expected 5",
+            fields.len()
+        );
+        let mut fields = fields.into_iter();
+        let mut next_string = |name: &str| {
+            string_from_move_value(fields.next().unwrap())
+                .map_err(|e| anyhow!("Field `{}` is not a string: {}", name, e))
+        };
+
+        Ok(Self {
+            kid: next_string("kid")?,
+            kty: next_string("kty")?,
+            alg: next_string("alg")?,
+            e: next_string("e")?,
+            n: next_string("n")?,
+        })
+    }
+
+    /// Emits the JWK as JSON, as found in a JWKS. The inverse of `try_from`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "kid": self.kid,
+            "kty": self.kty,
+            "alg": self.alg,
+            "e": self.e,
+            "n": self.n,
+            "use": "sig",
+        })
+    }
+}
+
+/// The inverse of `String::as_move_value`: a `0x1::string::String` struct wrapping utf8 bytes.
+fn string_from_move_value(value: MoveValue) -> Result<String> {
+    let fields = match value {
+        MoveValue::Struct(move_struct) => move_struct.into_fields(),
+        _ => bail!("not a struct"),
+    };
+    let bytes = match fields.as_slice() {
+        [MoveValue::Vector(bytes)] => bytes
+            .iter()
+            .map(|byte| match byte {
+                MoveValue::U8(byte) => Ok(*byte),
+                _ => Err(anyhow!("not a byte vector")),
+            })
+            .collect::<Result<Vec<u8>>>()?,
+        _ => bail!("expected a single byte vector field"),
+    };
+    Ok(String::from_utf8(bytes)?)
 }
 
 impl AsMoveAny for RSA_JWK {
     const MOVE_TYPE_NAME: &'static str = "0x1::jwks::RSA_JWK";
 }
 
+/// Why a JSON value could not be converted to an `RSA_JWK`.
+#[derive(Clone, Debug, Eq, PartialEq, Error)]
+pub enum RsaJwkError {
+    #[error("Field `{0}` not found")]
+    MissingField(&'static str),
+    #[error("Field `{0}` is not a string")]
+    NotAString(&'static str),
+    #[error("json to rsa jwk conversion failed with incorrect kty `{0}`")]
+    UnsupportedKty(String),
+}
+
+fn get_string_field(
+    json_value: &serde_json::Value,
+    field: &'static str,
+) -> Result<String, RsaJwkError> {
+    Ok(json_value
+        .get(field)
+        .ok_or(RsaJwkError::MissingField(field))?
+        .as_str()
+        .ok_or(RsaJwkError::NotAString(field))?
+        .to_string())
+}
+
 impl TryFrom<&serde_json::Value> for RSA_JWK {
-    type Error = anyhow::Error;
+    type Error = RsaJwkError;
 
     fn try_from(json_value: &serde_json::Value) -> Result<Self, Self::Error> {
-        let kty = json_value
-            .get("kty")
-            .ok_or_else(|| anyhow!("Field `kty` not found"))?
-            .as_str()
-            .ok_or_else(|| anyhow!("Field `kty` is not a string"))?
-            .to_string();
-
-        ensure!(
-            kty.as_str() == "RSA",
-            "json to rsa jwk conversion failed with incorrect kty"
-        );
+        let kty = get_string_field(json_value, "kty")?;
+        if kty.as_str() != "RSA" {
+            return Err(RsaJwkError::UnsupportedKty(kty));
+        }
 
         let ret = Self {
             kty,
-            kid: json_value
-                .get("kid")
-                .ok_or_else(|| anyhow!("Field `kid` not found"))?
-                .as_str()
-                .ok_or_else(|| anyhow!("Field `kid` is not a string"))?
-                .to_string(),
-            alg: json_value
-                .get("alg")
-                .ok_or_else(|| anyhow!("Field `alg` not found"))?
-                .as_str()
-                .ok_or_else(|| anyhow!("Field `alg` is not a string"))?
-                .to_string(),
-            e: json_value
-                .get("e")
-                .ok_or_else(|| anyhow!("Field `e` not found"))?
-                .as_str()
-                .ok_or_else(|| anyhow!("Field `e` is not a string"))?
-                .to_string(),
-            n: json_value
-                .get("n")
-                .ok_or_else(|| anyhow!("Field `n` not found"))?
-                .as_str()
-                .ok_or_else(|| anyhow!("Field `n` is not a string"))?
-                .to_string(),
+            kid: get_string_field(json_value, "kid")?,
+            alg: get_string_field(json_value, "alg")?,
+            e: get_string_field(json_value, "e")?,
+            n: get_string_field(json_value, "n")?,
         };
 
         Ok(ret)
@@ -110,57 +180,134 @@ fn test_rsa_jwk_from_json() {
     // JWK JSON without `kid` should be rejected.
     let json_str = r#"{"alg": "RS256", "e": "AQAB", "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::MissingField("kid"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON with wrong `kid` type should be rejected.
     let json_str =
         r#"{"alg": "RS256", "kid": {}, "e": "AQAB", "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::NotAString("kid"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON without `alg` should be rejected.
     let json_str = r#"{"kid": "kid1", "e": "AQAB", "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::MissingField("alg"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON with wrong `alg` type should be rejected.
     let json_str =
         r#"{"alg": 0, "kid": "kid1", "e": "AQAB", "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::NotAString("alg"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON without `kty` should be rejected.
     let json_str = r#"{"alg": "RS256", "kid": "kid1", "e": "AQAB", "use": "sig", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::MissingField("kty"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON with wrong `kty` value should be rejected.
     let json_str =
         r#"{"alg": "RS256", "kid": "kid1", "e": "AQAB", "use": "sig", "kty": "RSB", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::UnsupportedKty("RSB".to_string()),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
+    assert_eq!(
+        "json to rsa jwk conversion failed with incorrect kty `RSB`",
+        RSA_JWK::try_from(&json).unwrap_err().to_string()
+    );
 
     // JWK JSON without `e` should be rejected.
     let json_str = r#"{"alg": "RS256", "kid": "kid1", "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::MissingField("e"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON with wrong `e` type should be rejected.
     let json_str =
         r#"{"alg": "RS256", "kid": "kid1", "e": 65537, "use": "sig", "kty": "RSA", "n": "13131"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::NotAString("e"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON without `n` should be rejected.
     let json_str = r#"{"alg": "RS256", "kid": "kid1", "e": "AQAB", "use": "sig", "kty": "RSA"}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::MissingField("n"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
 
     // JWK JSON with wrong `n` type should be rejected.
     let json_str =
         r#"{"alg": "RS256", "kid": "kid1", "e": "AQAB", "use": "sig", "kty": "RSA", "n": false}"#;
     let json = serde_json::Value::from_str(json_str).unwrap();
-    assert!(RSA_JWK::try_from(&json).is_err());
+    assert_eq!(
+        RsaJwkError::NotAString("n"),
+        RSA_JWK::try_from(&json).unwrap_err()
+    );
+}
+
+#[test]
+fn test_rsa_jwk_to_json() {
+    let json_str = r#"{
+        "kty": "RSA",
+        "use": "sig",
+        "n": "6S7asUuzq5Q_3U9rbs-PkDVIdjgmtgWreG5qWPsC9xXZKiMV1AiV9LXyqQsAYpCqEDM3XbfmZqGb48yLhb_XqZaKgSYaC_h2DjM7lgrIQAp9902Rr8fUmLN2ivr5tnLxUUOnMOc2SQtr9dgzTONYW5Zu3PwyvAWk5D6ueIUhLtYzpcB-etoNdL3Ir2746KIy_VUsDwAM7dhrqSK8U2xFCGlau4ikOTtvzDownAMHMrfE7q1B6WZQDAQlBmxRQsyKln5DIsKv6xauNsHRgBAKctUxZG8M4QJIx3S6Aughd3RZC4Ca5Ae9fd8L8mlNYBCrQhOZ7dS0f4at4arlLcajtw",
+        "e": "AQAB",
+        "kid": "d7b939771a7800c413f90051012d975981916d71",
+        "alg": "RS256"
+    }"#;
+    let json = serde_json::Value::from_str(json_str).unwrap();
+    let rsa_jwk = RSA_JWK::try_from(&json).unwrap();
+    assert!(rsa_jwk.validate_modulus_size().is_ok());
+
+    let emitted = rsa_jwk.to_json();
+    assert_eq!(json, emitted);
+    assert_eq!(rsa_jwk, RSA_JWK::try_from(&emitted).unwrap());
+}
+
+#[test]
+fn test_rsa_jwk_validate_modulus_size() {
+    let jwk_with_modulus = |modulus: &[u8]| {
+        let n = base64::encode_config(modulus, base64::URL_SAFE_NO_PAD);
+        RSA_JWK::new_for_testing("kid1", "RSA", "RS256", "AQAB", &n)
+    };
+
+    assert!(jwk_with_modulus(&[0xAB; RSA_MODULUS_BYTES])
+        .validate_modulus_size()
+        .is_ok());
+
+    let err = jwk_with_modulus(&[0xAB; RSA_MODULUS_BYTES - 1])
+        .validate_modulus_size()
+        .unwrap_err();
+    assert!(err.to_string().contains("is 255 bytes"), "{}", err);
+    assert!(jwk_with_modulus(&[0xAB; RSA_MODULUS_BYTES + 1])
+        .validate_modulus_size()
+        .is_err());
+
+    // Padded or standard base64 isn't base64url.
+    let rsa_jwk = RSA_JWK::new_for_testing("kid1", "RSA", "RS256", "AQAB", "13131+/=");
+    assert!(rsa_jwk.validate_modulus_size().is_err());
 }
 
 #[test]
@@ -176,6 +323,33 @@ fn test_rsa_jwk_as_move_value() {
     );
 }
 
+#[test]
+fn test_rsa_jwk_from_move_value() {
+    let rsa_jwk = RSA_JWK::new_for_testing("kid1", "RSA", "RS256", "AQAB", "13131");
+    let move_value = rsa_jwk.as_move_value();
+    assert_eq!(rsa_jwk, RSA_JWK::from_move_value(&move_value).unwrap());
+
+    // Too few fields
+    let move_value = MoveValue::Struct(MoveStruct::Runtime(vec![
+        "kid1".to_string().as_move_value(),
+        "RSA".to_string().as_move_value(),
+    ]));
+    assert!(RSA_JWK::from_move_value(&move_value).is_err());
+
+    // A field that isn't a string
+    let move_value = MoveValue::Struct(MoveStruct::Runtime(vec![
+        "kid1".to_string().as_move_value(),
+        "RSA".to_string().as_move_value(),
+        "RS256".to_string().as_move_value(),
+        MoveValue::U64(65537),
+        "13131".to_string().as_move_value(),
+    ]));
+    let err = RSA_JWK::from_move_value(&move_value).unwrap_err();
+    assert!(err.to_string().contains("Field `e`"), "{}", err);
+
+    assert!(RSA_JWK::from_move_value(&MoveValue::Bool(true)).is_err());
+}
+
 #[test]
 fn test_rsa_jwk_as_move_any() {
     let rsa_jwk = RSA_JWK::new_for_testing("kid1", "RSA", "RS256", "AQAB", "1313131313131");
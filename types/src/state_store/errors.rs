@@ -8,6 +8,16 @@ use thiserror::Error;
 pub enum StateviewError {
     #[error("{0} not found.")]
     NotFound(String),
+    /// The requested version was pruned.
+    #[error(
+        "{data_type} at version {requested_version} is pruned, min available version is \
+        {min_readable_version}."
+    )]
+    Pruned {
+        data_type: String,
+        requested_version: u64,
+        min_readable_version: u64,
+    },
     /// Other non-classified error.
     #[error("{0}")]
     Other(String),
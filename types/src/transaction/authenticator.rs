@@ -6,6 +6,7 @@ use crate::{
     account_address::AccountAddress,
     transaction::{
         webauthn::PartialAuthenticatorAssertionResponse, RawTransaction, RawTransactionWithData,
+        SignedTransaction,
     },
 };
 use anyhow::{bail, ensure, Error, Result};
@@ -34,7 +35,7 @@ pub const MAX_NUM_OF_SIGS: usize = 32;
 #[error("{:?}", self)]
 pub enum AuthenticationError {
     /// The number of signatures exceeds the maximum supported.
-    MaxSignaturesExceeded,
+    MaxSignaturesExceeded { num_signatures: usize, max: usize },
 }
 
 /// Each transaction submitted to the Aptos blockchain contains a `TransactionAuthenticator`. During
@@ -128,17 +129,27 @@ impl TransactionAuthenticator {
         Self::SingleSender { sender }
     }
 
-    /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
-    pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
-        let num_sigs: usize = self.sender().number_of_signatures()
+    /// Returns the number of signatures of the sender and secondary signers, or an error with
+    /// that number if it exceeds `MAX_NUM_OF_SIGS`.
+    pub fn check_num_signatures(&self) -> std::result::Result<usize, AuthenticationError> {
+        let num_signatures: usize = self.sender().number_of_signatures()
             + self
                 .secondary_signers()
                 .iter()
                 .map(|auth| auth.number_of_signatures())
                 .sum::<usize>();
-        if num_sigs > MAX_NUM_OF_SIGS {
-            return Err(Error::new(AuthenticationError::MaxSignaturesExceeded));
+        if num_signatures > MAX_NUM_OF_SIGS {
+            return Err(AuthenticationError::MaxSignaturesExceeded {
+                num_signatures,
+                max: MAX_NUM_OF_SIGS,
+            });
         }
+        Ok(num_signatures)
+    }
+
+    /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
+    pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
+        self.check_num_signatures().map_err(Error::new)?;
         match self {
             Self::Ed25519 {
                 public_key,
@@ -441,6 +452,23 @@ impl fmt::Display for TransactionAuthenticator {
     }
 }
 
+/// Returns the name of the scheme the sender of `txn` authenticates with, for diagnostics.
+/// Multi-agent and fee payer transactions report the scheme of their primary sender.
+pub fn authenticator_scheme_name(txn: &SignedTransaction) -> &'static str {
+    match txn.authenticator_ref() {
+        TransactionAuthenticator::Ed25519 { .. } => "ed25519",
+        TransactionAuthenticator::MultiEd25519 { .. } => "multi_ed25519",
+        TransactionAuthenticator::MultiAgent { sender, .. }
+        | TransactionAuthenticator::FeePayer { sender, .. }
+        | TransactionAuthenticator::SingleSender { sender } => match sender {
+            AccountAuthenticator::Ed25519 { .. } => "ed25519",
+            AccountAuthenticator::MultiEd25519 { .. } => "multi_ed25519",
+            AccountAuthenticator::SingleKey { .. } => "single_key",
+            AccountAuthenticator::MultiKey { .. } => "multi_key",
+        },
+    }
+}
+
 #[derive(Debug)]
 #[repr(u8)]
 pub enum Scheme {
@@ -1035,9 +1063,10 @@ impl AnyPublicKey {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::transaction::{webauthn::AssertionSignature, SignedTransaction};
+    use crate::transaction::webauthn::AssertionSignature;
     use aptos_crypto::{
         ed25519::Ed25519PrivateKey,
+        multi_ed25519::MultiEd25519PrivateKey,
         secp256k1_ecdsa,
         secp256r1_ecdsa::{PublicKey, Signature},
         PrivateKey, SigningKey, Uniform,
@@ -1223,6 +1252,125 @@ mod tests {
         .unwrap_err();
     }
 
+    #[test]
+    fn test_check_num_signatures() {
+        let sender = Ed25519PrivateKey::generate_for_testing();
+        let sender_pub = sender.public_key();
+        let raw_txn = crate::test_helpers::transaction_test_helpers::get_test_signed_transaction(
+            AuthenticationKey::ed25519(&sender_pub).account_address(),
+            0,
+            &sender,
+            sender_pub.clone(),
+            None,
+            0,
+            0,
+            None,
+        )
+        .into_raw_transaction();
+        let account_auth =
+            AccountAuthenticator::ed25519(sender_pub, sender.sign(&raw_txn).unwrap());
+        let multi_agent_auth = |num_secondary_signers: usize| {
+            TransactionAuthenticator::multi_agent(
+                account_auth.clone(),
+                vec![AccountAddress::ONE; num_secondary_signers],
+                vec![account_auth.clone(); num_secondary_signers],
+            )
+        };
+
+        assert_eq!(
+            multi_agent_auth(MAX_NUM_OF_SIGS - 2).check_num_signatures(),
+            Ok(MAX_NUM_OF_SIGS - 1)
+        );
+        assert_eq!(
+            multi_agent_auth(MAX_NUM_OF_SIGS - 1).check_num_signatures(),
+            Ok(MAX_NUM_OF_SIGS)
+        );
+
+        let too_many_sigs = multi_agent_auth(MAX_NUM_OF_SIGS);
+        let expected_err = AuthenticationError::MaxSignaturesExceeded {
+            num_signatures: MAX_NUM_OF_SIGS + 1,
+            max: MAX_NUM_OF_SIGS,
+        };
+        assert_eq!(
+            too_many_sigs.check_num_signatures(),
+            Err(expected_err.clone())
+        );
+        let err = too_many_sigs.verify(&raw_txn).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<AuthenticationError>(),
+            Some(&expected_err)
+        );
+    }
+
+    #[test]
+    fn test_authenticator_scheme_name() {
+        let sender = Ed25519PrivateKey::generate_for_testing();
+        let sender_pub = sender.public_key();
+        let raw_txn = crate::test_helpers::transaction_test_helpers::get_test_signed_transaction(
+            AuthenticationKey::ed25519(&sender_pub).account_address(),
+            0,
+            &sender,
+            sender_pub.clone(),
+            None,
+            0,
+            0,
+            None,
+        )
+        .into_raw_transaction();
+
+        let signature = sender.sign(&raw_txn).unwrap();
+        let ed25519_txn =
+            SignedTransaction::new(raw_txn.clone(), sender_pub.clone(), signature.clone());
+        assert_eq!(authenticator_scheme_name(&ed25519_txn), "ed25519");
+
+        let multi_sender = MultiEd25519PrivateKey::new(
+            vec![
+                Ed25519PrivateKey::generate_for_testing(),
+                Ed25519PrivateKey::generate_for_testing(),
+            ],
+            1,
+        )
+        .unwrap();
+        let multi_ed25519_txn = SignedTransaction::new_multisig(
+            raw_txn.clone(),
+            multi_sender.public_key(),
+            multi_sender.sign(&raw_txn).unwrap(),
+        );
+        assert_eq!(
+            authenticator_scheme_name(&multi_ed25519_txn),
+            "multi_ed25519"
+        );
+
+        let any_sender_pub = AnyPublicKey::ed25519(sender_pub.clone());
+        let any_signature = AnySignature::ed25519(signature.clone());
+        let single_key_txn = SignedTransaction::new_single_sender(
+            raw_txn.clone(),
+            AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+                any_sender_pub.clone(),
+                any_signature.clone(),
+            )),
+        );
+        assert_eq!(authenticator_scheme_name(&single_key_txn), "single_key");
+
+        let multi_key = MultiKey::new(vec![any_sender_pub], 1).unwrap();
+        let multi_key_txn = SignedTransaction::new_single_sender(
+            raw_txn.clone(),
+            AccountAuthenticator::multi_key(
+                MultiKeyAuthenticator::new(multi_key, vec![(0, any_signature)]).unwrap(),
+            ),
+        );
+        assert_eq!(authenticator_scheme_name(&multi_key_txn), "multi_key");
+
+        // Multi-agent transactions report the scheme of the primary sender.
+        let multi_agent_txn = SignedTransaction::new_multi_agent(
+            raw_txn,
+            AccountAuthenticator::ed25519(sender_pub.clone(), signature.clone()),
+            vec![AccountAddress::ONE],
+            vec![AccountAuthenticator::ed25519(sender_pub, signature)],
+        );
+        assert_eq!(authenticator_scheme_name(&multi_agent_txn), "ed25519");
+    }
+
     #[test]
     fn verify_fee_payer_with_optional_fee_payer_address() {
         // This massive test basically verifies that various combinations of signatures work